@@ -0,0 +1,604 @@
+# English messages (default and fallback language).
+# Keys are shared by all binaries; see src/i18n.rs.
+# Keys starting with "web-" are also sent to the browser by GET /api/i18n.
+
+## Shared
+
+cancelled = Cancelled
+locked-note = Locked note
+note-not-found = Note with ID { $id } not found
+error-saving = Error saving changes: { $error }
+error-editor = Cannot open editor: { $error }
+open-notebook-failed = Cannot open notebook: { $error }
+device-id-failed = Cannot save device id to { $path }: { $error }
+bytes = { $count ->
+    [one] 1 byte
+   *[other] { $count } bytes
+}
+
+prompt-notebook-passphrase = Notebook passphrase:{" "}
+prompt-note-passphrase = Note passphrase:{" "}
+prompt-new-passphrase = New passphrase:{" "}
+prompt-repeat-passphrase = Repeat passphrase:{" "}
+passphrase-mismatch = passphrases do not match
+passphrase-empty = passphrase cannot be empty
+prompt-new-password = New password:{" "}
+prompt-repeat-password = Repeat password:{" "}
+password-mismatch = passwords do not match
+crypto-decrypt-failed = wrong passphrase or corrupted data
+crypto-invalid-data = invalid encrypted data: { $error }
+
+sync-done = 🔄 Synced with { $url }: { $report }
+sync-failed = Sync failed: { $error }
+sync-report = { $added } added, { $updated } updated, { $deleted } deleted, { $conflicts ->
+    [one] 1 conflict
+   *[other] { $conflicts } conflicts
+}
+sync-report-blobs = { $sent ->
+    [one] 1 attachment sent
+   *[other] { $sent } attachments sent
+}, { $received } received
+sync-report-blobs-missing = { $count ->
+    [one] 1 attachment missing on both sides
+   *[other] { $count } attachments missing on both sides
+}
+
+schedules-ran = 🔁 Created { $count ->
+    [one] 1 note
+   *[other] { $count } notes
+} from recurring schedules
+schedules-error = Error running recurring schedules: { $error }
+schedules-invalid = { $path } is not a valid schedules file: { $error }
+schedule-skipped = Skipping schedule '{ $name }': { $error }
+schedule-weekday-missing = Weekly schedules need a weekday (e.g. mon)
+schedule-invalid-weekday = Invalid weekday: { $day }
+schedule-invalid-recurrence = Invalid recurrence: { $every } (use daily or weekly)
+schedule-daily = daily
+schedule-weekly-0 = every Monday
+schedule-weekly-1 = every Tuesday
+schedule-weekly-2 = every Wednesday
+schedule-weekly-3 = every Thursday
+schedule-weekly-4 = every Friday
+schedule-weekly-5 = every Saturday
+schedule-weekly-6 = every Sunday
+template-invalid-name = Invalid template name: { $name }
+attachment-invalid-hash = Invalid attachment hash: { $hash }
+
+conflict-base = base
+conflict-ours = ours
+conflict-theirs = theirs ({ $date })
+conflict-merged = merged
+conflict-merged-clean = merged (clean)
+conflict-merged-markers = merged (with conflict markers)
+conflict-unknown = (unknown)
+conflict-markers-left = Conflict markers are still present; note left unresolved
+
+select-invalid-id = Invalid ID '{ $id }' in '{ $spec }'
+select-range-reversed = Invalid range '{ $range }': start is after end
+select-range-too-large = Range '{ $range }' is too large
+select-no-ids = No IDs in '{ $spec }'
+select-invalid-date = Expected YYYY-MM-DD, got '{ $date }'
+select-invalid-tag = Invalid tag '{ $tag }': use a single word
+
+## Dates
+
+month-1 = Jan
+month-2 = Feb
+month-3 = Mar
+month-4 = Apr
+month-5 = May
+month-6 = Jun
+month-7 = Jul
+month-8 = Aug
+month-9 = Sep
+month-10 = Oct
+month-11 = Nov
+month-12 = Dec
+date = { $month } { $day }, { $year }
+datetime = { $date }, { $hour }:{ $minute }
+
+## CLI (rust_app)
+
+label-created = Created: { $date }
+label-updated = Updated: { $date }
+label-attachments = Attachments: { $count }
+label-notebook = Notebook: { $notebook }
+label-tags = Tags: { $tags }
+marker-pinned = pinned
+marker-conflict = conflict
+marker-archived = Archived
+
+status-deleted = Note { $id } deleted
+status-archived = Note { $id } archived
+status-unarchived = Note { $id } unarchived
+status-tagged = Note { $id } tagged
+status-moved = Note { $id } moved
+
+cli-id-required = Please provide a note ID (the picker needs a terminal)
+cli-nothing-to-pick = No matching notes to pick from
+cli-picker-failed = Picker failed: { $error }
+cli-skipping-missing = Skipping missing note(s): { $ids }
+cli-no-match = No notes match
+cli-confirm-needs-yes = { $action } { $count } notes? Pass --yes to confirm without a terminal
+cli-confirm-list = { $action } { $count ->
+    [one] this note
+   *[other] these { $count } notes
+}:
+cli-confirm-prompt = Continue? [y/N]
+cli-confirm-yes = yes
+cli-error-completions = Error writing completions: { $error }
+cli-error-man = Error writing man page: { $error }
+
+cli-prompt-show = Show
+cli-prompt-edit = Edit
+cli-prompt-delete = Delete
+cli-prompt-tag = Tag
+cli-prompt-move = Move
+cli-prompt-archive = Archive
+cli-prompt-unarchive = Unarchive
+cli-prompt-pin = Pin
+cli-prompt-unpin = Unpin
+cli-prompt-links = Links from
+cli-prompt-backlinks = Backlinks to
+cli-prompt-attachments = Attachments of
+cli-prompt-lock = Lock
+cli-prompt-unlock = Unlock
+cli-prompt-reveal = Reveal
+cli-prompt-resolve = Resolve
+
+cli-added = ✅ Note added successfully!
+cli-added-from-template = ✅ Note { $id } created from template '{ $name }'
+cli-template-not-found = Template '{ $name }' not found: { $error }
+cli-edit-locked = Note { $id } is locked; unlock it before editing
+cli-edit-empty = Note is empty; use delete to remove it
+cli-unchanged = Note { $id } unchanged
+cli-edited = ✅ Note { $id } updated
+cli-deleted = ✅ Note { $id } deleted successfully!
+cli-deleted-many = ✅ Deleted { $count } notes
+cli-tagged = 🏷️ Updated tags on { $count ->
+    [one] 1 note
+   *[other] { $count } notes
+}
+cli-moved = 📁 Moved { $count ->
+    [one] 1 note
+   *[other] { $count } notes
+} to { $notebook }
+cli-moved-out = 📁 Took { $count ->
+    [one] 1 note
+   *[other] { $count } notes
+} out of their notebook
+cli-archived = 🗄️ Archived { $count ->
+    [one] 1 note
+   *[other] { $count } notes
+}
+cli-unarchived = 🗄️ Unarchived { $count ->
+    [one] 1 note
+   *[other] { $count } notes
+}
+cli-pinned = 📌 Note { $id } pinned
+cli-unpinned = 📌 Note { $id } unpinned
+cli-already-pinned = Note { $id } is already pinned
+cli-already-unpinned = Note { $id } is already unpinned
+
+cli-list-empty = No notes found.
+cli-list-header = 📝 Your Notes:
+cli-no-links = Note { $id } has no links.
+cli-links-header = 🔗 Links from note { $id }:
+cli-link-broken = broken link
+cli-no-backlinks = No notes link to note { $id }.
+cli-backlinks-header = 🔙 Notes linking to note { $id }:
+cli-no-broken-links = ✅ No broken links found.
+cli-broken-links-header = ❌ Broken links:
+
+cli-read-failed = Cannot read { $path }: { $error }
+cli-attach-failed = Error storing attachment: { $error }
+cli-attached = 📎 Attached { $name } ({ $size }) to note { $id }
+cli-no-attachments = Note { $id } has no attachments.
+cli-attachments-header = 📎 Attachments of note { $id }:
+cli-attachment-not-found = Attachment { $name } not found on note { $id }
+cli-detached = ✅ Removed { $name } from note { $id }
+cli-gc-failed = Error cleaning attachments: { $error }
+cli-gc-nothing = ✅ No orphaned attachments.
+cli-gc-done = 🧹 Removed { $count ->
+    [one] 1 orphaned attachment
+   *[other] { $count } orphaned attachments
+}, freed { $size }
+
+cli-already-encrypted = Notebook is already encrypted
+cli-encrypt-git = Git storage cannot be encrypted; switch back with `storage json` first
+cli-encrypt-failed = Error encrypting notebook: { $error }
+cli-encrypted = 🔐 Notebook encrypted ({ $file })
+cli-not-encrypted = Notebook is not encrypted
+cli-decrypt-failed = Error decrypting notebook: { $error }
+cli-decrypted = 🔓 Notebook decrypted ({ $file })
+cli-attachments-encrypted = 🔐 { $count } attachment files encrypted
+cli-attachments-decrypted = 🔓 { $count } attachment files decrypted
+cli-already-locked = Note { $id } is already locked
+cli-not-locked = Note { $id } is not locked
+cli-passphrase-mismatch = passphrases do not match or are empty
+cli-reveal-failed = Cannot reveal note { $id }: { $error }
+cli-lock-failed = Cannot lock note { $id }: { $error }
+cli-unlock-failed = Cannot unlock note { $id }: { $error }
+cli-locked = ✅ Note { $id } locked
+cli-unlocked = ✅ Note { $id } unlocked
+
+cli-no-conflicts = ✅ No conflicts.
+cli-conflicts-header = ⚠️  Notes with conflicting edits:
+cli-conflicts-hint = Resolve with: rust_app resolve <id>
+cli-no-conflict = ✅ Note { $id } has no conflict
+cli-resolve-needs-choice = Pick --ours, --theirs, --merged or --edit when using machine-readable output
+cli-resolve-prompt = Keep [o]urs, [t]heirs, [m]erged or [e]dit in $EDITOR?
+cli-resolve-not-clean = Automatic merge has conflicting lines; use --edit to fix them by hand
+cli-resolved = ✅ Conflict on note { $id } resolved
+
+cli-log-needs-git = History needs git storage. Run: rust_app storage git
+cli-log-failed = Error reading history: { $error }
+cli-log-empty = No history yet.
+cli-log-header = 📜 History:
+cli-storage-current = Current storage: { $backend }
+cli-storage-unchanged = Storage is already { $backend }
+cli-storage-encrypted = Decrypt the notebook first: git storage cannot be encrypted
+cli-storage-git-failed = Error creating git storage: { $error }
+cli-storage-to-git = ✅ Notes moved to git storage ({ $path })
+cli-storage-switch-failed = Error switching storage: { $error }
+cli-storage-to-json = ✅ Notes moved to { $file } (git history kept in { $path })
+
+cli-template-save-failed = Error saving template: { $error }
+cli-template-saved = ✅ Template '{ $name }' saved
+cli-templates-empty = No templates found.
+cli-templates-header = 📄 Templates:
+cli-schedule-no-template = Template '{ $name }' not found
+cli-schedule-save-failed = Error saving schedules: { $error }
+cli-schedule-added = ✅ Schedule added
+cli-schedules-empty = No schedules found.
+cli-schedules-header = 🔁 Schedules:
+cli-schedule-never = never
+cli-schedule-last-run = last run: { $date }
+cli-schedule-not-found = Schedule { $number } not found
+cli-schedule-removed = ✅ Schedule { $number } removed
+
+## TUI (rust_app_tui)
+
+tui-needs-terminal = rust_app_tui needs a terminal; use --screen-reader or the rust_app CLI in scripts
+tui-terminal-error = Terminal error: { $error }
+tui-watch-failed = Live reload disabled: { $error }
+tui-list-title = Notes ({ $count })
+tui-detail-title = Detail
+tui-empty = No notes yet. Press { $key } to add one.
+tui-no-match = No notes match the search.
+tui-field-created = Created:
+tui-field-updated = Updated:
+tui-field-notebook = Notebook:
+tui-field-tags = Tags:
+tui-field-files = Files:
+tui-field-links = Links:
+tui-field-backlinks = Linked from:
+tui-link-broken = broken
+tui-conflict-hint = Conflicting edits — press { $key } to resolve
+tui-search-hint = Type to filter, ↑/↓ move, Enter keep, Esc clear
+tui-hint-add = add
+tui-hint-edit = edit
+tui-hint-delete = delete
+tui-hint-search = search
+tui-hint-help = help
+tui-hint-quit = quit
+tui-editor-edit-title = Edit note { $id } — Ctrl-S save, Ctrl-E $EDITOR, Esc cancel
+tui-editor-new-title = New note — Ctrl-S save, Ctrl-E $EDITOR, Esc cancel
+tui-sync-title = Sync server URL — Enter sync, Esc cancel
+tui-resolve-title = Resolve note { $id } — [o]urs [t]heirs [m]erged [e]dit, Esc cancel
+tui-help-title = Keys — press any key to close
+tui-help-editor = Editor: Ctrl-S save, Ctrl-E $EDITOR, Esc cancel
+tui-help-escape = Esc clears the search; Ctrl-C always quits
+
+tui-action-up = Previous note / scroll detail up
+tui-action-down = Next note / scroll detail down
+tui-action-page-up = Up one page
+tui-action-page-down = Down one page
+tui-action-top = First note
+tui-action-bottom = Last note
+tui-action-toggle-focus = Switch between list and detail
+tui-action-search = Search notes
+tui-action-add = Add a note
+tui-action-edit = Edit the selected note
+tui-action-delete = Delete the selected note
+tui-action-undo = Undo the last delete
+tui-action-pin = Pin / unpin
+tui-action-sync = Sync with a server
+tui-action-resolve = Resolve a conflict
+tui-action-help = Show this help
+tui-action-quit = Quit
+
+tui-keymap-unknown-key = Unknown key '{ $key }' in '{ $spec }'
+tui-keymap-empty = Empty key binding
+tui-keymap-shadows = Key { $keys } for { $action } shadows { $other } for { $other_action }
+tui-keymap-ignored = Ignoring { $path }: { $error }
+
+tui-no-conflict = Selected note has no conflict
+tui-edit-locked = Note is locked; unlock it with `rust_app unlock` first
+tui-empty-note = Note cannot be empty
+tui-added = ✅ Note added
+tui-saved-as-new = ⚠️ Note { $id } was deleted on disk; saved as a new note
+tui-updated = ✅ Note { $id } updated
+tui-no-changes = No changes
+tui-edit-conflict = ⚠️ Note { $id } was changed on disk while you edited it; both versions kept as a conflict ({ $key } to resolve)
+tui-reload-failed = Cannot reload notes: { $error }
+tui-editing-deleted = ⚠️ Note { $id } was deleted on disk while you are editing it
+tui-editing-changed = ⚠️ Note { $id } was changed on disk while you are editing it
+tui-reloaded = 🔄 Notes changed on disk, reloaded
+tui-deleted = ✅ Note { $id } deleted
+tui-deleted-undo = ✅ Note { $id } deleted — press { $key } to undo
+tui-nothing-to-undo = Nothing to undo
+tui-restored = ✅ Note { $id } restored
+tui-pinned = 📌 Pinned note { $id }
+tui-unpinned = Unpinned note { $id }
+tui-sync-no-url = No server URL given
+tui-syncing = 🔄 Syncing with { $url }...
+tui-resolve-not-clean = Automatic merge has conflicting lines, choose [e]dit instead
+tui-resolved = ✅ Conflict on note { $id } resolved
+
+## Screen reader mode (rust_app_tui --screen-reader)
+
+linear-welcome = Note manager, screen reader mode. Type help for commands.
+linear-help =
+    Commands:
+      list                 list notes
+      show N               read note N
+      search TEXT          list notes containing TEXT
+      add                  add a note
+      edit N               replace the content of note N
+      delete N             delete note N
+      pin N, unpin N       pin or unpin note N
+      help                 show this help
+      quit                 leave
+linear-note = Note { $id }: { $title }.
+linear-pinned = Pinned.
+linear-has-conflict = Has a conflict.
+linear-no-notes = No notes.
+linear-no-match = No notes contain "{ $search }".
+linear-count = { $count ->
+    [one] 1 note.
+   *[other] { $count } notes.
+}
+linear-dates = Created { $created }. Updated { $updated }.
+linear-notebook = Notebook: { $notebook }.
+linear-tags = Tags: { $tags }.
+linear-locked = The content is locked. Unlock it with the rust_app CLI.
+linear-end = End of note { $id }.
+linear-type-note = Type the note. End with a line containing only a period.
+linear-reloaded = Notes changed on disk, reloaded.
+linear-not-found = Note { $id } not found.
+linear-need-id = Give a note number, for example: { $command } 3
+linear-added = Note { $id } added.
+linear-add-cancelled = Cancelled, the note was empty.
+linear-edit-locked = Note is locked. Unlock it with the rust_app CLI first.
+linear-updated = Note { $id } updated.
+linear-no-changes = No changes.
+linear-edit-cancelled = Cancelled, the note was not changed.
+linear-delete-confirm = Delete note { $id }, { $title }? Type yes to confirm.
+linear-deleted = Note { $id } deleted.
+linear-pinned-note = Note { $id } pinned.
+linear-unpinned-note = Note { $id } unpinned.
+linear-unknown = Unknown command { $command }. Type help for commands.
+linear-goodbye = Goodbye.
+
+## Web server (rust_app_web) console
+
+server-encrypted = Notebook is encrypted (set RUST_APP_PASSPHRASE to unlock non-interactively)
+server-running = Server running on { $url }
+server-open = Open { $url } in your browser
+server-sync = Sync: { $report }
+server-state-recovered = Recovered from an internal error; notes reloaded from disk
+server-accounts-failed = Could not read user accounts: { $error }
+server-no-users = No user accounts yet; every API request will be refused. Create one with: rust_app_web user add <name>
+server-shares-failed = Could not read shared notes: { $error }
+server-config-read = Could not read server config { $path }: { $error }
+server-config-address = Cannot listen on { $address }: not a valid address
+server-config-tls-pair = TLS needs both a certificate (tls-cert) and a private key (tls-key)
+server-config-tls-socket = TLS is not supported on a Unix socket; terminate TLS in the proxy in front of it
+server-config-cors-origin = Invalid CORS origin "{ $origin }": expected scheme://host[:port], e.g. https://notes.example.com, or *
+server-config-trusted-proxy = Invalid trusted proxy "{ $proxy }": expected an IP address, or unix for the proxy on the Unix socket
+server-tls-failed = Could not load TLS file { $path }: { $error }
+server-tls-no-key = No private key found in { $path }
+server-tls-no-certificate = no PEM certificate found
+server-socket-unsupported = Unix sockets are not supported on this platform
+server-bind-failed = Cannot listen on { $address }: { $error }
+server-accept-failed = Could not accept a connection: { $error }
+server-storage-failed = Could not start the storage writer: { $error }
+server-storage-stopped = The storage writer has stopped; changes were not saved
+server-shutting-down = Shutting down: finishing open requests...
+server-shutdown-timeout = Some connections were still open after { $seconds } seconds; closing them
+server-stopped = All changes saved, server stopped
+
+## Web server accounts (rust_app_web user / token)
+
+account-invalid-name = Invalid username '{ $name }': use up to { $limit } letters, digits, '_', '-' or '.'
+account-password-too-short = Passwords must be at least { $limit } characters
+account-exists = User '{ $name }' already exists
+account-not-found = No user '{ $name }'
+user-added = User '{ $name }' created
+user-added-admin = User '{ $name }' created as admin
+user-removed = User '{ $name }' removed; their notes stay in the notebook
+user-password-changed = Password of '{ $name }' changed; their sessions were ended
+user-role-admin = admin
+users-empty = No users yet.
+token-created = Token { $id } created for '{ $user }'. It is printed below and will not be shown again.
+token-revoked = Token { $id } revoked
+token-not-found = No token { $id }
+tokens-empty = No API tokens.
+
+## Web API errors (problem+json "detail")
+
+api-invalid-request = The request has invalid fields
+api-content-empty = Note content cannot be empty
+api-content-too-long = Note content is larger than { $size }
+api-too-many-tags = A note can have at most { $limit } tags
+api-notebook-too-long = Notebook names can be at most { $limit } characters
+api-note-locked = Note { $id } is locked; unlock it with the rust_app CLI before changing its content
+api-no-files = The upload contains no files
+api-attachment-not-found = Note { $id } has no attachment { $hash }
+api-blob-not-found = Attachment { $hash } is not stored on this server
+api-blob-mismatch = The uploaded content does not match hash { $hash }
+api-internal-error = Internal server error
+api-rate-limited = Too many changes from this address; try again in { $seconds } s
+api-audit-limit = limit must be between 1 and { $limit }
+audit-write-failed = Could not write the audit log: { $error }
+audit-read-failed = Could not read the audit log: { $error }
+api-state-unavailable = Notes could not be reloaded after an internal error: { $error }
+api-unauthorized = Sign in first, or send an API token as "Authorization: Bearer <token>"
+api-login-failed = Wrong username or password
+api-login-limited = Too many failed sign-ins for this account; try again in { $seconds } s
+api-admin-only = Only admins can do this
+api-token-name-invalid = Token names must be 1 to { $limit } characters
+api-read-only = Note { $id } is shared with you read-only
+api-owner-only = Only the owner of note { $id } can do this
+api-share-target = Send either a note id or a notebook name
+api-share-self = You cannot share with yourself
+api-share-not-found = No share { $id }
+api-link-expiry = Links can expire after 1 to { $limit } days, not { $days }
+api-link-not-found = This link does not exist or has expired
+api-note-live = Note { $id } is being edited live; join the live editor to change its content
+
+## Live editing (rust_app_web)
+
+collab-length-mismatch = The edit expects { $expected } characters but the note has { $actual }
+collab-invalid-operation = Invalid edit operation
+collab-out-of-sync = This editor fell behind; close it and open it again
+collab-save-failed = Could not save live edits of note { $id }: { $error }
+
+## Web page (static/index.html)
+
+web-page-title = 📝 Note Manager - Rust Web App
+web-title = 📝 Note Manager
+web-placeholder = Write a new note here...
+web-add-note = ➕ Add Note
+web-loading = Loading notes...
+web-empty = No notes yet. Add one above! 👆
+web-created = Created: { $date }
+web-locked = Locked note
+web-reveal = Reveal
+web-resolve-conflict = Resolve conflict
+web-attach = Attach file
+web-delete = Delete
+web-broken-link = Broken link
+web-linked-from = Linked from:
+web-confirm-delete = Are you sure you want to delete this note?
+web-passphrase-prompt = Passphrase for this note:
+web-wrong-passphrase = Wrong passphrase
+web-empty-note = Note cannot be empty!
+web-failed-load = Failed to load notes
+web-failed-add = Failed to add note
+web-failed-delete = Failed to delete note
+web-failed-reveal = Failed to reveal note
+web-failed-conflicts = Failed to load conflicts
+web-failed-resolve = Failed to resolve conflict
+web-failed-upload = Failed to upload attachment
+web-error-load = Error loading notes: { $error }
+web-error-add = Error adding note: { $error }
+web-error-delete = Error deleting note: { $error }
+web-error-reveal = Error revealing note: { $error }
+web-error-conflict = Error loading conflict: { $error }
+web-error-resolve = Error resolving conflict: { $error }
+web-error-upload = Error uploading attachment: { $error }
+web-merge-title = Conflicting edits on note #{ $id }
+web-merge-base = Base
+web-merge-ours = Ours
+web-merge-theirs = Theirs ({ $date })
+web-merge-unknown = (unknown)
+web-merge-result = Result
+web-merge-result-markers = Result — fix the conflict markers
+web-cancel = Cancel
+web-use-ours = Use ours
+web-use-theirs = Use theirs
+web-save-result = Save result
+web-login-title = Sign in
+web-username = Username
+web-password = Password
+web-login = Sign in
+web-logout = Sign out
+web-signed-in-as = Signed in as { $name }
+web-failed-login = Failed to sign in
+web-share = Share
+web-share-link = Public link
+web-share-user-prompt = Share with which user?
+web-share-access-prompt = Access (read or write):
+web-shared-with = Shared with { $name }
+web-shared-by = Shared by { $name }
+web-shared-with-you = Shared with you
+web-link-expiry-prompt = Link expires after how many days? (leave empty to never expire)
+web-link-created = Anyone with this link can read the note. Copy it now; it will not be shown again:
+web-empty-shared = Nothing is shared through this link yet.
+web-failed-share = Failed to share note
+web-error-share = Error sharing note: { $error }
+web-edit-together = Edit together
+web-live-title = Live editing note #{ $id }
+web-live-connecting = Connecting...
+web-live-read-only = You can only read this note; edits from others appear here as they type.
+web-live-disconnected = Disconnected. Close the editor and open it again to continue.
+web-live-out-of-sync = The editor is out of sync
+web-live-reset = The note was changed elsewhere; the editor now shows the saved text and your unsent edits were dropped.
+web-live-alone = Nobody else is editing right now
+web-live-peer = { $name } (line { $line }, column { $col })
+web-close = Close
+
+## Desktop GUI (main_egui.rs, main_linux.rs)
+
+gui-window-title = Note Manager - Rust GUI
+gui-title = 📝 Note Manager
+gui-new-note = New Note:
+gui-placeholder = Write a new note here...
+gui-add = ➕ Add
+gui-empty = No notes yet. Add one above! 👆
+gui-your-notes = Your Notes:
+gui-created = Created: { $date }
+gui-delete = 🗑️ Delete
+
+## Object detection (rust_object_detection)
+
+detect-usage = Usage:
+detect-example = Example:
+detect-image-required = Error: Please provide image path
+detect-unknown-mode = Error: Unknown mode '{ $mode }'
+detect-modes = Available modes: image, webcam
+detect-error = Error: { $error }
+detect-none = No objects detected.
+detect-count = Detected { $count ->
+    [one] 1 object
+   *[other] { $count } objects
+}:
+detect-column-class = Class
+detect-column-confidence = Confidence
+detect-column-bbox = Bounding Box
+detect-loading-model = Loading model: { $path }
+detect-image-not-found = Image not found: { $path }
+detect-detecting = Detecting objects in: { $path }
+detect-image-load-failed = Failed to load image: { $error }
+detect-image-loaded = Image loaded: { $width }x{ $height }
+detect-inference-placeholder = Running inference (placeholder - implement with ONNX Runtime)
+detect-save-failed = Error saving results: { $error }
+detect-saved = Results saved to: { $path }
+detect-webcam-starting = Starting webcam detection...
+detect-webcam-requires = Note: Requires opencv feature enabled in Cargo.toml
+detect-webcam-done = Webcam detection completed.
+detect-webcam-checklist =
+    Make sure:
+      1. Webcam is connected
+      2. opencv feature is enabled in Cargo.toml
+      3. Required system libraries are installed:
+detect-webcam-disabled = Error: Webcam feature not enabled!
+detect-webcam-enable = To enable webcam support:
+detect-webcam-enable-step1 = 1. Uncomment opencv dependency in Cargo.toml:
+detect-webcam-enable-step2 = 2. Run with feature flag:
+detect-webcam-opening = Opening webcam...
+detect-webcam-open-failed = Failed to open webcam: { $error }
+detect-webcam-check-failed = Failed to check webcam: { $error }
+detect-webcam-opened = Webcam opened successfully!
+detect-webcam-keys = Press 'q' to quit, 's' to save current frame
+detect-frame-read-failed = Error reading frame: { $error }
+detect-frame-header = Frame { $frame } - Detections:
+detect-quitting = Quitting...
+detect-frame-save-failed = Failed to save frame: { $error }
+detect-frame-saved = Frame saved to: { $path }
+detect-no-window = Warning: Cannot display window: { $error }. Processing frames...
+detect-webcam-stopped = Webcam detection stopped. Processed { $count } frames.
+detect-frame-size-failed = Failed to get frame size: { $error }
+detect-draw-failed = Failed to draw detections: { $error }
@@ -0,0 +1,561 @@
+# Pesan Bahasa Indonesia.
+# Key sama dengan locales/en.ftl; key yang belum ada di sini jatuh ke English.
+
+## Umum
+
+cancelled = Dibatalkan
+locked-note = Note terkunci
+note-not-found = Note dengan ID { $id } tidak ditemukan
+error-saving = Gagal menyimpan perubahan: { $error }
+error-editor = Tidak bisa membuka editor: { $error }
+open-notebook-failed = Tidak bisa membuka notebook: { $error }
+device-id-failed = Tidak bisa menyimpan device id ke { $path }: { $error }
+bytes = { $count } byte
+
+prompt-notebook-passphrase = Passphrase notebook:{" "}
+prompt-note-passphrase = Passphrase note:{" "}
+prompt-new-passphrase = Passphrase baru:{" "}
+prompt-repeat-passphrase = Ulangi passphrase:{" "}
+passphrase-mismatch = passphrase tidak sama
+passphrase-empty = passphrase tidak boleh kosong
+prompt-new-password = Password baru:{" "}
+prompt-repeat-password = Ulangi password:{" "}
+password-mismatch = password tidak sama
+crypto-decrypt-failed = passphrase salah atau data rusak
+crypto-invalid-data = data terenkripsi tidak valid: { $error }
+
+sync-done = 🔄 Tersinkron dengan { $url }: { $report }
+sync-failed = Sinkronisasi gagal: { $error }
+sync-report = { $added } ditambah, { $updated } diubah, { $deleted } dihapus, { $conflicts } konflik
+sync-report-blobs = { $sent } attachment dikirim, { $received } diterima
+sync-report-blobs-missing = { $count } attachment tidak ada di kedua sisi
+
+schedules-ran = 🔁 Membuat { $count } note dari jadwal berulang
+schedules-error = Gagal menjalankan jadwal berulang: { $error }
+schedules-invalid = { $path } bukan file jadwal yang valid: { $error }
+schedule-skipped = Melewati jadwal '{ $name }': { $error }
+schedule-weekday-missing = Jadwal mingguan butuh nama hari (misalnya mon)
+schedule-invalid-weekday = Nama hari tidak valid: { $day }
+schedule-invalid-recurrence = Pengulangan tidak valid: { $every } (pakai daily atau weekly)
+schedule-daily = setiap hari
+schedule-weekly-0 = setiap Senin
+schedule-weekly-1 = setiap Selasa
+schedule-weekly-2 = setiap Rabu
+schedule-weekly-3 = setiap Kamis
+schedule-weekly-4 = setiap Jumat
+schedule-weekly-5 = setiap Sabtu
+schedule-weekly-6 = setiap Minggu
+template-invalid-name = Nama template tidak valid: { $name }
+attachment-invalid-hash = Hash lampiran tidak valid: { $hash }
+
+conflict-base = dasar
+conflict-ours = milik kita
+conflict-theirs = milik mereka ({ $date })
+conflict-merged = gabungan
+conflict-merged-clean = gabungan (bersih)
+conflict-merged-markers = gabungan (dengan penanda konflik)
+conflict-unknown = (tidak diketahui)
+conflict-markers-left = Penanda konflik masih ada; note belum diselesaikan
+
+select-invalid-id = ID '{ $id }' tidak valid di '{ $spec }'
+select-range-reversed = Rentang '{ $range }' tidak valid: awal setelah akhir
+select-range-too-large = Rentang '{ $range }' terlalu besar
+select-no-ids = Tidak ada ID di '{ $spec }'
+select-invalid-date = Format harus YYYY-MM-DD, bukan '{ $date }'
+select-invalid-tag = Tag '{ $tag }' tidak valid: pakai satu kata
+
+## Tanggal
+
+month-1 = Jan
+month-2 = Feb
+month-3 = Mar
+month-4 = Apr
+month-5 = Mei
+month-6 = Jun
+month-7 = Jul
+month-8 = Agu
+month-9 = Sep
+month-10 = Okt
+month-11 = Nov
+month-12 = Des
+date = { $day } { $month } { $year }
+datetime = { $date } { $hour }.{ $minute }
+
+## CLI (rust_app)
+
+label-created = Dibuat: { $date }
+label-updated = Diubah: { $date }
+label-attachments = Lampiran: { $count }
+label-notebook = Notebook: { $notebook }
+label-tags = Tag: { $tags }
+marker-pinned = disematkan
+marker-conflict = konflik
+marker-archived = Diarsipkan
+
+status-deleted = Note { $id } dihapus
+status-archived = Note { $id } diarsipkan
+status-unarchived = Note { $id } dikeluarkan dari arsip
+status-tagged = Tag note { $id } diubah
+status-moved = Note { $id } dipindahkan
+
+cli-id-required = Masukkan ID note (picker butuh terminal)
+cli-nothing-to-pick = Tidak ada note yang cocok untuk dipilih
+cli-picker-failed = Picker gagal: { $error }
+cli-skipping-missing = Melewati note yang tidak ada: { $ids }
+cli-no-match = Tidak ada note yang cocok
+cli-confirm-needs-yes = { $action } { $count } note? Tambahkan --yes untuk konfirmasi tanpa terminal
+cli-confirm-list = { $action } { $count } note ini:
+cli-confirm-prompt = Lanjutkan? [y/N]
+cli-confirm-yes = ya
+cli-error-completions = Gagal menulis completion: { $error }
+cli-error-man = Gagal menulis man page: { $error }
+
+cli-prompt-show = Tampilkan
+cli-prompt-edit = Ubah
+cli-prompt-delete = Hapus
+cli-prompt-tag = Beri tag
+cli-prompt-move = Pindahkan
+cli-prompt-archive = Arsipkan
+cli-prompt-unarchive = Keluarkan dari arsip
+cli-prompt-pin = Sematkan
+cli-prompt-unpin = Lepas sematan
+cli-prompt-links = Link dari
+cli-prompt-backlinks = Backlink ke
+cli-prompt-attachments = Lampiran dari
+cli-prompt-lock = Kunci
+cli-prompt-unlock = Buka kunci
+cli-prompt-reveal = Tampilkan isi
+cli-prompt-resolve = Selesaikan
+
+cli-added = ✅ Note berhasil ditambahkan!
+cli-added-from-template = ✅ Note { $id } dibuat dari template '{ $name }'
+cli-template-not-found = Template '{ $name }' tidak ditemukan: { $error }
+cli-edit-locked = Note { $id } terkunci; buka kuncinya sebelum mengubah
+cli-edit-empty = Note kosong; pakai delete untuk menghapusnya
+cli-unchanged = Note { $id } tidak berubah
+cli-edited = ✅ Note { $id } diubah
+cli-deleted = ✅ Note { $id } berhasil dihapus!
+cli-deleted-many = ✅ { $count } note dihapus
+cli-tagged = 🏷️ Tag diubah di { $count } note
+cli-moved = 📁 { $count } note dipindahkan ke { $notebook }
+cli-moved-out = 📁 { $count } note dikeluarkan dari notebook
+cli-archived = 🗄️ { $count } note diarsipkan
+cli-unarchived = 🗄️ { $count } note dikeluarkan dari arsip
+cli-pinned = 📌 Note { $id } disematkan
+cli-unpinned = 📌 Sematan note { $id } dilepas
+cli-already-pinned = Note { $id } sudah disematkan
+cli-already-unpinned = Note { $id } memang tidak disematkan
+
+cli-list-empty = Belum ada note.
+cli-list-header = 📝 Note Kamu:
+cli-no-links = Note { $id } tidak punya link.
+cli-links-header = 🔗 Link dari note { $id }:
+cli-link-broken = link rusak
+cli-no-backlinks = Tidak ada note yang menautkan ke note { $id }.
+cli-backlinks-header = 🔙 Note yang menautkan ke note { $id }:
+cli-no-broken-links = ✅ Tidak ada link rusak.
+cli-broken-links-header = ❌ Link rusak:
+
+cli-read-failed = Tidak bisa membaca { $path }: { $error }
+cli-attach-failed = Gagal menyimpan lampiran: { $error }
+cli-attached = 📎 { $name } ({ $size }) dilampirkan ke note { $id }
+cli-no-attachments = Note { $id } tidak punya lampiran.
+cli-attachments-header = 📎 Lampiran note { $id }:
+cli-attachment-not-found = Lampiran { $name } tidak ada di note { $id }
+cli-detached = ✅ { $name } dilepas dari note { $id }
+cli-gc-failed = Gagal membersihkan lampiran: { $error }
+cli-gc-nothing = ✅ Tidak ada lampiran yatim.
+cli-gc-done = 🧹 { $count } lampiran yatim dihapus, { $size } dibebaskan
+
+cli-already-encrypted = Notebook sudah terenkripsi
+cli-encrypt-git = Storage git tidak bisa dienkripsi; kembali ke `storage json` dulu
+cli-encrypt-failed = Gagal mengenkripsi notebook: { $error }
+cli-encrypted = 🔐 Notebook terenkripsi ({ $file })
+cli-not-encrypted = Notebook tidak terenkripsi
+cli-decrypt-failed = Gagal mendekripsi notebook: { $error }
+cli-decrypted = 🔓 Notebook didekripsi ({ $file })
+cli-attachments-encrypted = 🔐 { $count } file lampiran dienkripsi
+cli-attachments-decrypted = 🔓 { $count } file lampiran didekripsi
+cli-already-locked = Note { $id } sudah terkunci
+cli-not-locked = Note { $id } tidak terkunci
+cli-passphrase-mismatch = passphrase tidak sama atau kosong
+cli-reveal-failed = Tidak bisa menampilkan note { $id }: { $error }
+cli-lock-failed = Tidak bisa mengunci note { $id }: { $error }
+cli-unlock-failed = Tidak bisa membuka kunci note { $id }: { $error }
+cli-locked = ✅ Note { $id } dikunci
+cli-unlocked = ✅ Kunci note { $id } dibuka
+
+cli-no-conflicts = ✅ Tidak ada konflik.
+cli-conflicts-header = ⚠️  Note dengan perubahan yang bentrok:
+cli-conflicts-hint = Selesaikan dengan: rust_app resolve <id>
+cli-no-conflict = ✅ Note { $id } tidak punya konflik
+cli-resolve-needs-choice = Pilih --ours, --theirs, --merged atau --edit saat memakai output machine
+cli-resolve-prompt = Pakai [o]urs (milik kita), [t]heirs (milik mereka), [m]erged (gabungan) atau [e]dit di $EDITOR?
+cli-resolve-not-clean = Penggabungan otomatis masih bentrok; pakai --edit untuk memperbaiki manual
+cli-resolved = ✅ Konflik di note { $id } selesai
+
+cli-log-needs-git = Riwayat butuh storage git. Jalankan: rust_app storage git
+cli-log-failed = Gagal membaca riwayat: { $error }
+cli-log-empty = Belum ada riwayat.
+cli-log-header = 📜 Riwayat:
+cli-storage-current = Storage saat ini: { $backend }
+cli-storage-unchanged = Storage sudah { $backend }
+cli-storage-encrypted = Dekripsi notebook dulu: storage git tidak bisa dienkripsi
+cli-storage-git-failed = Gagal membuat storage git: { $error }
+cli-storage-to-git = ✅ Note dipindahkan ke storage git ({ $path })
+cli-storage-switch-failed = Gagal mengganti storage: { $error }
+cli-storage-to-json = ✅ Note dipindahkan ke { $file } (riwayat git tetap di { $path })
+
+cli-template-save-failed = Gagal menyimpan template: { $error }
+cli-template-saved = ✅ Template '{ $name }' disimpan
+cli-templates-empty = Belum ada template.
+cli-templates-header = 📄 Template:
+cli-schedule-no-template = Template '{ $name }' tidak ditemukan
+cli-schedule-save-failed = Gagal menyimpan jadwal: { $error }
+cli-schedule-added = ✅ Jadwal ditambahkan
+cli-schedules-empty = Belum ada jadwal.
+cli-schedules-header = 🔁 Jadwal:
+cli-schedule-never = belum pernah
+cli-schedule-last-run = terakhir jalan: { $date }
+cli-schedule-not-found = Jadwal { $number } tidak ditemukan
+cli-schedule-removed = ✅ Jadwal { $number } dihapus
+
+## TUI (rust_app_tui)
+
+tui-needs-terminal = rust_app_tui butuh terminal; pakai --screen-reader atau CLI rust_app di script
+tui-terminal-error = Error terminal: { $error }
+tui-watch-failed = Reload otomatis dimatikan: { $error }
+tui-list-title = Note ({ $count })
+tui-detail-title = Detail
+tui-empty = Belum ada note. Tekan { $key } untuk menambah.
+tui-no-match = Tidak ada note yang cocok dengan pencarian.
+tui-field-created = Dibuat:
+tui-field-updated = Diubah:
+tui-field-notebook = Notebook:
+tui-field-tags = Tag:
+tui-field-files = File:
+tui-field-links = Link:
+tui-field-backlinks = Ditautkan dari:
+tui-link-broken = rusak
+tui-conflict-hint = Perubahan bentrok — tekan { $key } untuk menyelesaikan
+tui-search-hint = Ketik untuk menyaring, ↑/↓ pindah, Enter simpan, Esc hapus
+tui-hint-add = tambah
+tui-hint-edit = ubah
+tui-hint-delete = hapus
+tui-hint-search = cari
+tui-hint-help = bantuan
+tui-hint-quit = keluar
+tui-editor-edit-title = Ubah note { $id } — Ctrl-S simpan, Ctrl-E $EDITOR, Esc batal
+tui-editor-new-title = Note baru — Ctrl-S simpan, Ctrl-E $EDITOR, Esc batal
+tui-sync-title = URL server sinkronisasi — Enter sinkron, Esc batal
+tui-resolve-title = Selesaikan note { $id } — [o]urs [t]heirs [m]erged [e]dit, Esc batal
+tui-help-title = Tombol — tekan tombol apa saja untuk menutup
+tui-help-editor = Editor: Ctrl-S simpan, Ctrl-E $EDITOR, Esc batal
+tui-help-escape = Esc menghapus pencarian; Ctrl-C selalu keluar
+
+tui-action-up = Note sebelumnya / gulir detail ke atas
+tui-action-down = Note berikutnya / gulir detail ke bawah
+tui-action-page-up = Naik satu halaman
+tui-action-page-down = Turun satu halaman
+tui-action-top = Note pertama
+tui-action-bottom = Note terakhir
+tui-action-toggle-focus = Pindah antara daftar dan detail
+tui-action-search = Cari note
+tui-action-add = Tambah note
+tui-action-edit = Ubah note terpilih
+tui-action-delete = Hapus note terpilih
+tui-action-undo = Batalkan hapus terakhir
+tui-action-pin = Sematkan / lepas sematan
+tui-action-sync = Sinkron dengan server
+tui-action-resolve = Selesaikan konflik
+tui-action-help = Tampilkan bantuan ini
+tui-action-quit = Keluar
+
+tui-keymap-unknown-key = Tombol '{ $key }' tidak dikenal di '{ $spec }'
+tui-keymap-empty = Binding tombol kosong
+tui-keymap-shadows = Tombol { $keys } untuk { $action } menutupi { $other } untuk { $other_action }
+tui-keymap-ignored = Mengabaikan { $path }: { $error }
+
+tui-no-conflict = Note terpilih tidak punya konflik
+tui-edit-locked = Note terkunci; buka dulu dengan `rust_app unlock`
+tui-empty-note = Note tidak boleh kosong
+tui-added = ✅ Note ditambahkan
+tui-saved-as-new = ⚠️ Note { $id } sudah dihapus di disk; disimpan sebagai note baru
+tui-updated = ✅ Note { $id } diubah
+tui-no-changes = Tidak ada perubahan
+tui-edit-conflict = ⚠️ Note { $id } berubah di disk saat kamu mengubahnya; kedua versi disimpan sebagai konflik ({ $key } untuk menyelesaikan)
+tui-reload-failed = Tidak bisa memuat ulang note: { $error }
+tui-editing-deleted = ⚠️ Note { $id } dihapus di disk saat kamu sedang mengubahnya
+tui-editing-changed = ⚠️ Note { $id } berubah di disk saat kamu sedang mengubahnya
+tui-reloaded = 🔄 Note berubah di disk, dimuat ulang
+tui-deleted = ✅ Note { $id } dihapus
+tui-deleted-undo = ✅ Note { $id } dihapus — tekan { $key } untuk membatalkan
+tui-nothing-to-undo = Tidak ada yang bisa dibatalkan
+tui-restored = ✅ Note { $id } dikembalikan
+tui-pinned = 📌 Note { $id } disematkan
+tui-unpinned = Sematan note { $id } dilepas
+tui-sync-no-url = URL server belum diisi
+tui-syncing = 🔄 Sinkron dengan { $url }...
+tui-resolve-not-clean = Penggabungan otomatis masih bentrok, pilih [e]dit
+tui-resolved = ✅ Konflik di note { $id } selesai
+
+## Mode screen reader (rust_app_tui --screen-reader)
+
+linear-welcome = Pengelola note, mode screen reader. Ketik help untuk daftar perintah.
+linear-help =
+    Perintah:
+      list                 daftar note
+      show N               baca note N
+      search TEKS          daftar note yang berisi TEKS
+      add                  tambah note
+      edit N               ganti isi note N
+      delete N             hapus note N
+      pin N, unpin N       sematkan atau lepas sematan note N
+      help                 tampilkan bantuan ini
+      quit                 keluar
+linear-note = Note { $id }: { $title }.
+linear-pinned = Disematkan.
+linear-has-conflict = Ada konflik.
+linear-no-notes = Tidak ada note.
+linear-no-match = Tidak ada note yang berisi "{ $search }".
+linear-count = { $count } note.
+linear-dates = Dibuat { $created }. Diubah { $updated }.
+linear-notebook = Notebook: { $notebook }.
+linear-tags = Tag: { $tags }.
+linear-locked = Isinya terkunci. Buka dengan CLI rust_app.
+linear-end = Akhir note { $id }.
+linear-type-note = Ketik note. Akhiri dengan baris yang hanya berisi titik.
+linear-reloaded = Note berubah di disk, dimuat ulang.
+linear-not-found = Note { $id } tidak ditemukan.
+linear-need-id = Sebutkan nomor note, misalnya: { $command } 3
+linear-added = Note { $id } ditambahkan.
+linear-add-cancelled = Dibatalkan, note kosong.
+linear-edit-locked = Note terkunci. Buka dulu dengan CLI rust_app.
+linear-updated = Note { $id } diubah.
+linear-no-changes = Tidak ada perubahan.
+linear-edit-cancelled = Dibatalkan, note tidak diubah.
+linear-delete-confirm = Hapus note { $id }, { $title }? Ketik ya untuk konfirmasi.
+linear-deleted = Note { $id } dihapus.
+linear-pinned-note = Note { $id } disematkan.
+linear-unpinned-note = Sematan note { $id } dilepas.
+linear-unknown = Perintah { $command } tidak dikenal. Ketik help untuk daftar perintah.
+linear-goodbye = Sampai jumpa.
+
+## Konsol web server (rust_app_web)
+
+server-encrypted = Notebook terenkripsi (isi RUST_APP_PASSPHRASE untuk membuka tanpa prompt)
+server-running = Server berjalan di { $url }
+server-open = Buka { $url } di browser
+server-sync = Sinkronisasi: { $report }
+server-state-recovered = Pulih dari error internal; note dimuat ulang dari disk
+server-accounts-failed = Akun user tidak bisa dibaca: { $error }
+server-no-users = Belum ada akun user; semua request API akan ditolak. Buat dengan: rust_app_web user add <nama>
+server-shares-failed = Data berbagi note tidak bisa dibaca: { $error }
+server-config-read = Config server { $path } tidak bisa dibaca: { $error }
+server-config-address = Tidak bisa listen di { $address }: alamat tidak valid
+server-config-tls-pair = TLS butuh sertifikat (tls-cert) dan private key (tls-key) sekaligus
+server-config-tls-socket = TLS tidak didukung di Unix socket; pasang TLS di proxy di depannya
+server-config-cors-origin = Origin CORS "{ $origin }" tidak valid: harus scheme://host[:port], misalnya https://notes.example.com, atau *
+server-config-trusted-proxy = Proxy terpercaya "{ $proxy }" tidak valid: harus alamat IP, atau unix untuk proxy di Unix socket
+server-tls-failed = File TLS { $path } tidak bisa dimuat: { $error }
+server-tls-no-key = Tidak ada private key di { $path }
+server-tls-no-certificate = tidak ada sertifikat PEM
+server-socket-unsupported = Unix socket tidak didukung di platform ini
+server-bind-failed = Tidak bisa listen di { $address }: { $error }
+server-accept-failed = Koneksi tidak bisa diterima: { $error }
+server-storage-failed = Penulis storage tidak bisa dijalankan: { $error }
+server-storage-stopped = Penulis storage sudah berhenti; perubahan tidak tersimpan
+server-shutting-down = Mematikan server: menyelesaikan request yang masih berjalan...
+server-shutdown-timeout = Masih ada koneksi terbuka setelah { $seconds } detik; koneksi ditutup
+server-stopped = Semua perubahan tersimpan, server berhenti
+
+## Akun web server (rust_app_web user / token)
+
+account-invalid-name = Username '{ $name }' tidak valid: gunakan maksimal { $limit } huruf, angka, '_', '-' atau '.'
+account-password-too-short = Password minimal { $limit } karakter
+account-exists = User '{ $name }' sudah ada
+account-not-found = Tidak ada user '{ $name }'
+user-added = User '{ $name }' dibuat
+user-added-admin = User '{ $name }' dibuat sebagai admin
+user-removed = User '{ $name }' dihapus; note miliknya tetap ada di notebook
+user-password-changed = Password '{ $name }' diganti; semua session-nya diakhiri
+user-role-admin = admin
+users-empty = Belum ada user.
+token-created = Token { $id } dibuat untuk '{ $user }'. Token dicetak di bawah dan tidak akan ditampilkan lagi.
+token-revoked = Token { $id } dicabut
+token-not-found = Tidak ada token { $id }
+tokens-empty = Tidak ada API token.
+
+## Error web API ("detail" di problem+json)
+
+api-invalid-request = Ada field request yang tidak valid
+api-content-empty = Isi note tidak boleh kosong
+api-content-too-long = Isi note lebih besar dari { $size }
+api-too-many-tags = Satu note paling banyak punya { $limit } tag
+api-notebook-too-long = Nama notebook paling panjang { $limit } karakter
+api-note-locked = Note { $id } terkunci; buka dengan CLI rust_app sebelum mengubah isinya
+api-no-files = Upload tidak berisi file
+api-attachment-not-found = Note { $id } tidak punya lampiran { $hash }
+api-blob-not-found = Lampiran { $hash } tidak tersimpan di server ini
+api-blob-mismatch = Isi yang diunggah tidak cocok dengan hash { $hash }
+api-internal-error = Error internal server
+api-rate-limited = Terlalu banyak perubahan dari alamat ini; coba lagi dalam { $seconds } detik
+api-audit-limit = limit harus antara 1 dan { $limit }
+audit-write-failed = Audit log tidak bisa ditulis: { $error }
+audit-read-failed = Audit log tidak bisa dibaca: { $error }
+api-state-unavailable = Note tidak bisa dimuat ulang setelah error internal: { $error }
+api-unauthorized = Login dulu, atau kirim API token sebagai "Authorization: Bearer <token>"
+api-login-failed = Username atau password salah
+api-login-limited = Terlalu banyak login gagal untuk akun ini; coba lagi dalam { $seconds } detik
+api-admin-only = Hanya admin yang boleh melakukan ini
+api-token-name-invalid = Nama token harus 1 sampai { $limit } karakter
+api-read-only = Note { $id } dibagikan kepada Anda hanya untuk dibaca
+api-owner-only = Hanya pemilik note { $id } yang boleh melakukan ini
+api-share-target = Kirim id note atau nama notebook, salah satu saja
+api-share-self = Tidak bisa berbagi dengan diri sendiri
+api-share-not-found = Tidak ada share { $id }
+api-link-expiry = Link bisa kedaluwarsa setelah 1 sampai { $limit } hari, bukan { $days }
+api-link-not-found = Link ini tidak ada atau sudah kedaluwarsa
+api-note-live = Note { $id } sedang diedit bersama; gabung ke editor langsung untuk mengubah isinya
+
+## Edit bersama (rust_app_web)
+
+collab-length-mismatch = Perubahan mengharapkan { $expected } karakter tapi note berisi { $actual }
+collab-invalid-operation = Operasi edit tidak valid
+collab-out-of-sync = Editor ini tertinggal; tutup lalu buka lagi
+collab-save-failed = Hasil edit bersama note { $id } tidak bisa disimpan: { $error }
+
+## Halaman web (static/index.html)
+
+web-page-title = 📝 Pengelola Note - Rust Web App
+web-title = 📝 Pengelola Note
+web-placeholder = Tulis note baru di sini...
+web-add-note = ➕ Tambah Note
+web-loading = Memuat note...
+web-empty = Belum ada note. Tambahkan di atas! 👆
+web-created = Dibuat: { $date }
+web-locked = Note terkunci
+web-reveal = Tampilkan
+web-resolve-conflict = Selesaikan konflik
+web-attach = Lampirkan file
+web-delete = Hapus
+web-broken-link = Link rusak
+web-linked-from = Ditautkan dari:
+web-confirm-delete = Yakin ingin menghapus note ini?
+web-passphrase-prompt = Passphrase untuk note ini:
+web-wrong-passphrase = Passphrase salah
+web-empty-note = Note tidak boleh kosong!
+web-failed-load = Gagal memuat note
+web-failed-add = Gagal menambah note
+web-failed-delete = Gagal menghapus note
+web-failed-reveal = Gagal menampilkan note
+web-failed-conflicts = Gagal memuat konflik
+web-failed-resolve = Gagal menyelesaikan konflik
+web-failed-upload = Gagal mengunggah lampiran
+web-error-load = Error memuat note: { $error }
+web-error-add = Error menambah note: { $error }
+web-error-delete = Error menghapus note: { $error }
+web-error-reveal = Error menampilkan note: { $error }
+web-error-conflict = Error memuat konflik: { $error }
+web-error-resolve = Error menyelesaikan konflik: { $error }
+web-error-upload = Error mengunggah lampiran: { $error }
+web-merge-title = Perubahan bentrok di note #{ $id }
+web-merge-base = Dasar
+web-merge-ours = Milik kita
+web-merge-theirs = Milik mereka ({ $date })
+web-merge-unknown = (tidak diketahui)
+web-merge-result = Hasil
+web-merge-result-markers = Hasil — perbaiki penanda konflik
+web-cancel = Batal
+web-use-ours = Pakai milik kita
+web-use-theirs = Pakai milik mereka
+web-save-result = Simpan hasil
+web-login-title = Masuk
+web-username = Username
+web-password = Password
+web-login = Masuk
+web-logout = Keluar
+web-signed-in-as = Masuk sebagai { $name }
+web-failed-login = Gagal masuk
+web-share = Bagikan
+web-share-link = Link publik
+web-share-user-prompt = Bagikan ke user siapa?
+web-share-access-prompt = Akses (read atau write):
+web-shared-with = Dibagikan ke { $name }
+web-shared-by = Dibagikan oleh { $name }
+web-shared-with-you = Dibagikan kepada Anda
+web-link-expiry-prompt = Link kedaluwarsa setelah berapa hari? (kosongkan supaya tidak pernah kedaluwarsa)
+web-link-created = Siapa pun yang punya link ini bisa membaca note. Salin sekarang; link tidak akan ditampilkan lagi:
+web-empty-shared = Belum ada yang dibagikan lewat link ini.
+web-failed-share = Gagal membagikan note
+web-error-share = Error membagikan note: { $error }
+web-edit-together = Edit bersama
+web-live-title = Edit bersama note #{ $id }
+web-live-connecting = Menyambungkan...
+web-live-read-only = Kamu hanya bisa membaca note ini; perubahan dari yang lain muncul di sini saat mereka mengetik.
+web-live-disconnected = Koneksi terputus. Tutup editor lalu buka lagi untuk melanjutkan.
+web-live-out-of-sync = Editor tidak sinkron
+web-live-reset = Note diubah di tempat lain; editor sekarang menampilkan isi yang tersimpan dan editan yang belum terkirim dibuang.
+web-live-alone = Tidak ada orang lain yang sedang mengedit
+web-live-peer = { $name } (baris { $line }, kolom { $col })
+web-close = Tutup
+
+## GUI desktop (main_egui.rs, main_linux.rs)
+
+gui-window-title = Pengelola Note - Rust GUI
+gui-title = 📝 Pengelola Note
+gui-new-note = Note Baru:
+gui-placeholder = Tulis note baru di sini...
+gui-add = ➕ Tambah
+gui-empty = Belum ada note. Tambahkan di atas! 👆
+gui-your-notes = Note Kamu:
+gui-created = Dibuat: { $date }
+gui-delete = 🗑️ Hapus
+
+## Deteksi objek (rust_object_detection)
+
+detect-usage = Cara pakai:
+detect-example = Contoh:
+detect-image-required = Error: Masukkan path gambar
+detect-unknown-mode = Error: Mode '{ $mode }' tidak dikenal
+detect-modes = Mode yang tersedia: image, webcam
+detect-error = Error: { $error }
+detect-none = Tidak ada objek terdeteksi.
+detect-count = { $count } objek terdeteksi:
+detect-column-class = Kelas
+detect-column-confidence = Keyakinan
+detect-column-bbox = Bounding Box
+detect-loading-model = Memuat model: { $path }
+detect-image-not-found = Gambar tidak ditemukan: { $path }
+detect-detecting = Mendeteksi objek di: { $path }
+detect-image-load-failed = Gagal memuat gambar: { $error }
+detect-image-loaded = Gambar dimuat: { $width }x{ $height }
+detect-inference-placeholder = Menjalankan inferensi (placeholder - implementasikan dengan ONNX Runtime)
+detect-save-failed = Gagal menyimpan hasil: { $error }
+detect-saved = Hasil disimpan ke: { $path }
+detect-webcam-starting = Memulai deteksi webcam...
+detect-webcam-requires = Catatan: Butuh feature opencv aktif di Cargo.toml
+detect-webcam-done = Deteksi webcam selesai.
+detect-webcam-checklist =
+    Pastikan:
+      1. Webcam terhubung
+      2. Feature opencv aktif di Cargo.toml
+      3. Library sistem yang dibutuhkan sudah terpasang:
+detect-webcam-disabled = Error: Feature webcam tidak aktif!
+detect-webcam-enable = Untuk mengaktifkan webcam:
+detect-webcam-enable-step1 = 1. Aktifkan dependency opencv di Cargo.toml:
+detect-webcam-enable-step2 = 2. Jalankan dengan feature flag:
+detect-webcam-opening = Membuka webcam...
+detect-webcam-open-failed = Gagal membuka webcam: { $error }
+detect-webcam-check-failed = Gagal memeriksa webcam: { $error }
+detect-webcam-opened = Webcam berhasil dibuka!
+detect-webcam-keys = Tekan 'q' untuk keluar, 's' untuk menyimpan frame saat ini
+detect-frame-read-failed = Gagal membaca frame: { $error }
+detect-frame-header = Frame { $frame } - Deteksi:
+detect-quitting = Keluar...
+detect-frame-save-failed = Gagal menyimpan frame: { $error }
+detect-frame-saved = Frame disimpan ke: { $path }
+detect-no-window = Peringatan: Tidak bisa menampilkan jendela: { $error }. Memproses frame...
+detect-webcam-stopped = Deteksi webcam berhenti. { $count } frame diproses.
+detect-frame-size-failed = Gagal membaca ukuran frame: { $error }
+detect-draw-failed = Gagal menggambar deteksi: { $error }
@@ -0,0 +1,421 @@
+// Akun user untuk web server: password, session cookie browser dan API token
+// Disimpan di <data dir>/users.json. Password disimpan sebagai hash Argon2 (PHC string)
+// dan token sebagai SHA-256, jadi isi file tidak bisa dipakai untuk login.
+// Session hanya ada di memory: setelah server restart semua user login ulang.
+//
+// Isolasi note: note web punya `owner`; note tanpa owner (dibuat lewat CLI/TUI
+// atau sebelum ada akun) adalah milik pemilik notebook, yaitu user admin.
+
+#![allow(dead_code)]
+
+use crate::notes::{data_path, new_uid, now_timestamp, write_atomic, Note};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+pub const USERS_FILE: &str = "users.json";
+/// Password untuk `rust_app_web user add/passwd` tanpa prompt (script)
+pub const PASSWORD_ENV: &str = "RUST_APP_PASSWORD";
+pub const SESSION_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+pub const MIN_PASSWORD_CHARS: usize = 8;
+const MAX_USERNAME_CHARS: usize = 32;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct User {
+    pub name: String,
+    password_hash: String,
+    /// Admin memiliki note lokal (tanpa owner) dan boleh sync seluruh notebook
+    #[serde(default)]
+    pub admin: bool,
+    pub created_at: String,
+}
+
+impl User {
+    pub fn owns(&self, note: &Note) -> bool {
+        match &note.owner {
+            Some(owner) => *owner == self.name,
+            None => self.admin,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiToken {
+    pub id: String,
+    pub user: String,
+    pub name: String,
+    /// SHA-256 dari token; token aslinya hanya ditampilkan sekali saat dibuat
+    hash: String,
+    pub created_at: String,
+}
+
+struct Session {
+    user: String,
+    expires: Instant,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Accounts {
+    pub users: Vec<User>,
+    #[serde(default)]
+    pub tokens: Vec<ApiToken>,
+    /// Hash token session -> session
+    #[serde(skip)]
+    sessions: HashMap<String, Session>,
+}
+
+/// Secret acak 256-bit, aman dipakai di URL dan cookie
+pub fn random_secret() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn hash_secret(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+fn hash_password(password: &str) -> Result<String, String> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let salt = SaltString::encode_b64(&salt).map_err(|e| e.to_string())?;
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| e.to_string())
+}
+
+fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+}
+
+/// Hash pembanding untuk username yang tidak ada (lihat `check_password`)
+fn dummy_hash() -> &'static str {
+    static DUMMY: OnceLock<String> = OnceLock::new();
+    DUMMY.get_or_init(|| hash_password(&random_secret()).unwrap_or_default())
+}
+
+pub fn validate_username(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.chars().count() <= MAX_USERNAME_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(t!("account-invalid-name", name = name, limit = MAX_USERNAME_CHARS))
+    }
+}
+
+pub fn validate_password(password: &str) -> Result<(), String> {
+    if password.chars().count() < MIN_PASSWORD_CHARS {
+        return Err(t!("account-password-too-short", limit = MIN_PASSWORD_CHARS));
+    }
+    Ok(())
+}
+
+/// Cek password login. Tetap menjalankan Argon2 untuk username yang tidak ada,
+/// supaya waktu respons tidak membocorkan username mana yang terdaftar.
+pub fn check_password(user: Option<&User>, password: &str) -> bool {
+    match user {
+        Some(user) => verify_password(password, &user.password_hash),
+        None => {
+            verify_password(password, dummy_hash());
+            false
+        }
+    }
+}
+
+/// Password baru dari env RUST_APP_PASSWORD, atau tanya dua kali di terminal
+pub fn read_new_password() -> io::Result<String> {
+    if let Ok(password) = std::env::var(PASSWORD_ENV) {
+        return Ok(password);
+    }
+    let first = rpassword::prompt_password(t!("prompt-new-password"))?;
+    let second = rpassword::prompt_password(t!("prompt-repeat-password"))?;
+    if first != second {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, t!("password-mismatch")));
+    }
+    Ok(first)
+}
+
+impl Accounts {
+    pub fn load() -> io::Result<Accounts> {
+        match fs::read_to_string(data_path(USERS_FILE)) {
+            Ok(json) => serde_json::from_str(&json).map_err(io::Error::other),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Accounts::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Salinan yang disimpan ke file; session hanya hidup di memori
+    pub fn persisted(&self) -> Accounts {
+        Accounts {
+            users: self.users.clone(),
+            tokens: self.tokens.clone(),
+            sessions: HashMap::new(),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        let path = data_path(USERS_FILE);
+        write_atomic(&path, json.as_bytes())?;
+        // Hanya pemilik file yang boleh membaca hash password
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+
+    pub fn user(&self, name: &str) -> Option<&User> {
+        self.users.iter().find(|user| user.name == name)
+    }
+
+    /// User pertama selalu jadi admin, karena dia yang memiliki note yang sudah ada
+    pub fn add_user(&mut self, name: &str, password: &str, admin: bool) -> Result<&User, String> {
+        validate_username(name)?;
+        validate_password(password)?;
+        if self.user(name).is_some() {
+            return Err(t!("account-exists", name = name));
+        }
+        let admin = admin || self.users.is_empty();
+        self.users.push(User {
+            name: name.to_string(),
+            password_hash: hash_password(password)?,
+            admin,
+            created_at: now_timestamp(),
+        });
+        Ok(self.users.last().unwrap())
+    }
+
+    /// Hapus user beserta token dan session-nya. Note miliknya tetap ada di notebook.
+    pub fn remove_user(&mut self, name: &str) -> bool {
+        let before = self.users.len();
+        self.users.retain(|user| user.name != name);
+        self.tokens.retain(|token| token.user != name);
+        self.sessions.retain(|_, session| session.user != name);
+        self.users.len() != before
+    }
+
+    /// Ganti password dan keluarkan semua session user tersebut
+    pub fn set_password(&mut self, name: &str, password: &str) -> Result<(), String> {
+        validate_password(password)?;
+        let hash = hash_password(password)?;
+        let user = self
+            .users
+            .iter_mut()
+            .find(|user| user.name == name)
+            .ok_or_else(|| t!("account-not-found", name = name))?;
+        user.password_hash = hash;
+        self.sessions.retain(|_, session| session.user != name);
+        Ok(())
+    }
+
+    pub fn authenticate(&self, name: &str, password: &str) -> Option<&User> {
+        let user = self.user(name);
+        check_password(user, password).then_some(user).flatten()
+    }
+
+    /// Buat session baru; yang dikembalikan adalah nilai cookie
+    pub fn create_session(&mut self, name: &str) -> String {
+        let now = Instant::now();
+        self.sessions.retain(|_, session| session.expires > now);
+        let secret = random_secret();
+        self.sessions.insert(
+            hash_secret(&secret),
+            Session {
+                user: name.to_string(),
+                expires: now + SESSION_TTL,
+            },
+        );
+        secret
+    }
+
+    pub fn session_user(&self, secret: &str) -> Option<&User> {
+        let session = self.sessions.get(&hash_secret(secret))?;
+        if session.expires <= Instant::now() {
+            return None;
+        }
+        self.user(&session.user)
+    }
+
+    pub fn end_session(&mut self, secret: &str) {
+        self.sessions.remove(&hash_secret(secret));
+    }
+
+    /// Buat API token; secret-nya hanya bisa dilihat sekali, dari nilai kembalian ini
+    pub fn create_token(&mut self, user: &str, name: &str) -> Result<(ApiToken, String), String> {
+        if self.user(user).is_none() {
+            return Err(t!("account-not-found", name = user));
+        }
+        let secret = random_secret();
+        let token = ApiToken {
+            id: new_uid()[..12].to_string(),
+            user: user.to_string(),
+            name: name.trim().to_string(),
+            hash: hash_secret(&secret),
+            created_at: now_timestamp(),
+        };
+        self.tokens.push(token.clone());
+        Ok((token, secret))
+    }
+
+    pub fn token_user(&self, secret: &str) -> Option<&User> {
+        let hash = hash_secret(secret);
+        let token = self.tokens.iter().find(|token| token.hash == hash)?;
+        self.user(&token.user)
+    }
+
+    pub fn tokens_of(&self, user: &str) -> Vec<&ApiToken> {
+        self.tokens.iter().filter(|token| token.user == user).collect()
+    }
+
+    /// Cabut token `id`; kalau `user` diberikan, hanya token milik user itu
+    pub fn revoke_token(&mut self, id: &str, user: Option<&str>) -> bool {
+        let before = self.tokens.len();
+        self.tokens
+            .retain(|token| token.id != id || user.is_some_and(|user| token.user != user));
+        self.tokens.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn note(owner: Option<&str>) -> Note {
+        serde_json::from_value(json!({"id": 1, "content": "", "created_at": "", "owner": owner})).unwrap()
+    }
+
+    /// User tanpa hash Argon2, untuk test yang tidak butuh password
+    fn user(name: &str, admin: bool) -> User {
+        serde_json::from_value(json!({"name": name, "password_hash": "", "admin": admin, "created_at": ""})).unwrap()
+    }
+
+    fn two_users() -> Accounts {
+        Accounts {
+            users: vec![user("alice", true), user("bob", false)],
+            ..Accounts::default()
+        }
+    }
+
+    #[test]
+    fn passwords_are_checked_against_the_hash() {
+        let mut accounts = Accounts::default();
+        accounts.add_user("alice", "correct horse", false).unwrap();
+        let alice = accounts.user("alice");
+        assert_ne!(alice.unwrap().password_hash, "correct horse");
+        assert!(check_password(alice, "correct horse"));
+        assert!(!check_password(alice, "wrong horse"));
+        // Username yang tidak ada tetap diverifikasi (dengan hash dummy) dan selalu gagal
+        assert!(!check_password(None, "correct horse"));
+        assert!(!check_password(None, ""));
+        assert!(accounts.authenticate("alice", "correct horse").is_some());
+        assert!(accounts.authenticate("bob", "correct horse").is_none());
+    }
+
+    #[test]
+    fn first_user_is_admin() {
+        let mut accounts = Accounts::default();
+        accounts.users.push(user("taken", false));
+        assert!(accounts.add_user("taken", "long enough", false).is_err());
+        assert!(accounts.add_user("bad name", "long enough", false).is_err());
+        assert!(accounts.add_user("short", "short", false).is_err());
+
+        let mut accounts = Accounts::default();
+        assert!(accounts.add_user("alice", "long enough", false).unwrap().admin);
+        assert!(!accounts.add_user("bob", "long enough", false).unwrap().admin);
+    }
+
+    #[test]
+    fn sessions_expire_and_end() {
+        let mut accounts = two_users();
+        let alice = accounts.create_session("alice");
+        let bob = accounts.create_session("bob");
+        assert_eq!(accounts.session_user(&alice).unwrap().name, "alice");
+        // Yang disimpan hanya hash cookie-nya
+        assert!(!accounts.sessions.contains_key(&alice));
+        assert!(accounts.session_user("guess").is_none());
+
+        accounts.sessions.get_mut(&hash_secret(&alice)).unwrap().expires = Instant::now();
+        assert!(accounts.session_user(&alice).is_none());
+        // Session kedaluwarsa dibuang saat session baru dibuat
+        accounts.create_session("alice");
+        assert!(!accounts.sessions.contains_key(&hash_secret(&alice)));
+
+        accounts.end_session(&bob);
+        assert!(accounts.session_user(&bob).is_none());
+    }
+
+    #[test]
+    fn changing_password_ends_sessions() {
+        let mut accounts = two_users();
+        let alice = accounts.create_session("alice");
+        let bob = accounts.create_session("bob");
+        assert!(accounts.set_password("alice", "short").is_err());
+        assert!(accounts.session_user(&alice).is_some());
+
+        accounts.set_password("alice", "new password").unwrap();
+        assert!(accounts.session_user(&alice).is_none());
+        assert!(accounts.session_user(&bob).is_some());
+        assert!(accounts.set_password("nobody", "new password").is_err());
+    }
+
+    #[test]
+    fn tokens_are_hashed_and_revoked_per_user() {
+        let mut accounts = two_users();
+        let (token, secret) = accounts.create_token("alice", " laptop ").unwrap();
+        assert_eq!(token.name, "laptop");
+        assert_eq!(token.hash, hash_secret(&secret));
+        assert!(!serde_json::to_string(&accounts).unwrap().contains(&secret));
+        assert_eq!(accounts.token_user(&secret).unwrap().name, "alice");
+        assert!(accounts.token_user(&token.hash).is_none());
+        assert!(accounts.create_token("nobody", "x").is_err());
+
+        // User lain tidak bisa mencabut token alice, admin (user None) bisa
+        assert!(!accounts.revoke_token(&token.id, Some("bob")));
+        assert!(accounts.token_user(&secret).is_some());
+        assert!(accounts.revoke_token(&token.id, Some("alice")));
+        assert!(accounts.token_user(&secret).is_none());
+        let (token, secret) = accounts.create_token("bob", "phone").unwrap();
+        assert!(accounts.revoke_token(&token.id, None));
+        assert!(accounts.token_user(&secret).is_none());
+        assert!(!accounts.revoke_token(&token.id, None));
+    }
+
+    #[test]
+    fn removing_a_user_drops_tokens_and_sessions() {
+        let mut accounts = two_users();
+        let session = accounts.create_session("bob");
+        let (_, secret) = accounts.create_token("bob", "phone").unwrap();
+        assert!(accounts.remove_user("bob"));
+        assert!(accounts.session_user(&session).is_none());
+        assert!(accounts.token_user(&secret).is_none());
+        assert!(accounts.tokens_of("bob").is_empty());
+        assert!(!accounts.remove_user("bob"));
+    }
+
+    #[test]
+    fn notes_without_owner_belong_to_admins() {
+        let (admin, bob) = (user("alice", true), user("bob", false));
+        assert!(admin.owns(&note(None)));
+        assert!(!bob.owns(&note(None)));
+        assert!(bob.owns(&note(Some("bob"))));
+        assert!(!admin.owns(&note(Some("bob"))));
+    }
+}
@@ -0,0 +1,321 @@
+// File attachments untuk note
+// Blob disimpan content-addressed: <data dir>/attachments/<2 char pertama hash>/<sha256>
+// Note hanya menyimpan metadata (nama, hash, ukuran), jadi file yang sama cukup disimpan sekali
+// Kalau notebook terenkripsi, blob disimpan sebagai <sha256>.enc (Sealed JSON dengan
+// passphrase notebook); hash tetap dari isi aslinya.
+
+#![allow(dead_code)]
+
+use crate::crypto::{self, Sealed};
+use crate::notes::{current_passphrase, data_path, is_encrypted, now_timestamp, try_load_notes, Attachment, Notes};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+pub const ATTACHMENTS_DIR: &str = "attachments";
+/// Blob yang lebih muda dari ini tidak dihapus gc(): bisa jadi upload-nya masih
+/// berjalan dan note yang mereferensikannya belum tersimpan
+const GC_GRACE: Duration = Duration::from_secs(60 * 60);
+
+pub fn is_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn blob_path(hash: &str) -> PathBuf {
+    data_path(ATTACHMENTS_DIR).join(&hash[..2]).join(hash)
+}
+
+fn sealed_path(hash: &str) -> PathBuf {
+    blob_path(hash).with_extension("enc")
+}
+
+/// Hash blob dari nama file di folder attachments (`<hash>` atau `<hash>.enc`)
+fn blob_hash(file_name: &str) -> Option<&str> {
+    let hash = file_name.strip_suffix(".enc").unwrap_or(file_name);
+    is_hash(hash).then_some(hash)
+}
+
+/// Tulis lewat file sementara supaya blob tidak pernah setengah jadi
+fn write_blob_file(path: &PathBuf, content: &[u8]) -> io::Result<()> {
+    fs::create_dir_all(path.parent().unwrap())?;
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)
+}
+
+fn seal_blob(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let sealed = crypto::seal(&current_passphrase()?, bytes).map_err(|e| io::Error::other(e.to_string()))?;
+    Ok(serde_json::to_vec(&sealed)?)
+}
+
+fn open_blob(content: &[u8]) -> io::Result<Vec<u8>> {
+    let sealed: Sealed = serde_json::from_slice(content)?;
+    crypto::open(&current_passphrase()?, &sealed).map_err(|e| io::Error::other(e.to_string()))
+}
+
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Blob ada di store ini (plaintext atau terenkripsi)
+pub fn has_blob(hash: &str) -> bool {
+    is_hash(hash) && (sealed_path(hash).exists() || blob_path(hash).exists())
+}
+
+/// Simpan isi file sebagai blob (terenkripsi kalau notebook terenkripsi), return hash-nya
+pub fn store_blob(bytes: &[u8]) -> io::Result<String> {
+    let hash = hash_bytes(bytes);
+    let path = if is_encrypted() { sealed_path(&hash) } else { blob_path(&hash) };
+    if path.exists() {
+        // Blob lama dipakai lagi: perbarui waktunya supaya gc() yang berjalan
+        // bersamaan tidak menghapusnya sebelum note-nya tersimpan
+        fs::File::options().write(true).open(&path)?.set_modified(SystemTime::now())?;
+    } else if is_encrypted() {
+        write_blob_file(&path, &seal_blob(bytes)?)?;
+    } else {
+        write_blob_file(&path, bytes)?;
+    }
+    Ok(hash)
+}
+
+pub fn read_blob(hash: &str) -> io::Result<Vec<u8>> {
+    if !is_hash(hash) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            t!("attachment-invalid-hash", hash = hash),
+        ));
+    }
+    match fs::read(sealed_path(hash)) {
+        Ok(content) => open_blob(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => fs::read(blob_path(hash)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Enkripsi semua blob plaintext (setelah `encrypt_notebook`) atau kebalikannya
+/// (sebelum `decrypt_notebook`, selagi passphrase masih diketahui).
+/// Return jumlah blob yang diubah.
+pub fn convert_blobs(encrypt: bool) -> io::Result<usize> {
+    let root = data_path(ATTACHMENTS_DIR);
+    let shards = match fs::read_dir(&root) {
+        Ok(shards) => shards,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let mut converted = 0;
+    for shard in shards {
+        let shard = shard?.path();
+        if !shard.is_dir() {
+            continue;
+        }
+        for blob in fs::read_dir(&shard)? {
+            let name = blob?.file_name().to_string_lossy().to_string();
+            let Some(hash) = blob_hash(&name) else {
+                continue;
+            };
+            let sealed = name.ends_with(".enc");
+            if sealed == encrypt {
+                continue;
+            }
+            let (from, to) = if encrypt {
+                (blob_path(hash), sealed_path(hash))
+            } else {
+                (sealed_path(hash), blob_path(hash))
+            };
+            let content = fs::read(&from)?;
+            let content = if encrypt { seal_blob(&content)? } else { open_blob(&content)? };
+            write_blob_file(&to, &content)?;
+            fs::remove_file(&from)?;
+            converted += 1;
+        }
+    }
+    Ok(converted)
+}
+
+/// Ambil nama file saja, tanpa path dari client/shell dan tanpa karakter kontrol
+/// (tidak boleh ada di header HTTP maupun di output terminal)
+pub fn sanitize_name(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or("");
+    let name: String = name.chars().filter(|c| !c.is_control()).collect();
+    let name = name.trim();
+    if name.is_empty() {
+        "attachment".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Simpan blob dan tambahkan metadata ke note. Return None kalau note tidak ada.
+pub fn attach(
+    notes: &mut Notes,
+    id: usize,
+    name: &str,
+    bytes: &[u8],
+) -> io::Result<Option<Attachment>> {
+    if notes.get(id).is_none() {
+        return Ok(None);
+    }
+    let hash = store_blob(bytes)?;
+    Ok(add_attachment(notes, id, name, hash, bytes.len() as u64))
+}
+
+/// Tambahkan metadata blob yang sudah disimpan ke note. Return None kalau note tidak ada.
+pub fn add_attachment(notes: &mut Notes, id: usize, name: &str, hash: String, size: u64) -> Option<Attachment> {
+    let note = notes.notes.iter_mut().find(|note| note.id == id)?;
+    let attachment = Attachment {
+        name: sanitize_name(name),
+        hash,
+        size,
+        added_at: now_timestamp(),
+    };
+    note.attachments.push(attachment.clone());
+    note.touch();
+    Some(attachment)
+}
+
+/// Hapus attachment (berdasarkan nama atau hash) dari note. Blob dibersihkan oleh gc().
+pub fn detach(notes: &mut Notes, id: usize, name_or_hash: &str) -> Option<Attachment> {
+    let note = notes.notes.iter_mut().find(|note| note.id == id)?;
+    let index = note
+        .attachments
+        .iter()
+        .position(|a| a.hash == name_or_hash || a.name == name_or_hash)?;
+    let attachment = note.attachments.remove(index);
+    note.touch();
+    Some(attachment)
+}
+
+/// Hapus blob yang tidak lagi direferensikan oleh note manapun.
+/// Note dibaca ulang dari disk juga, karena proses lain (web server, TUI) mungkin sudah
+/// menyimpan note baru yang mereferensikan blob. File sementara dan blob yang baru
+/// ditulis dilewati. Return (jumlah blob dihapus, total byte yang dibebaskan).
+pub fn gc(notes: &Notes) -> io::Result<(usize, u64)> {
+    let stored = try_load_notes().map_err(|e| io::Error::other(e.to_string()))?;
+    let referenced: HashSet<&str> = notes
+        .notes
+        .iter()
+        .chain(&stored.notes)
+        .flat_map(|note| note.attachments.iter().map(|a| a.hash.as_str()))
+        .collect();
+    let now = SystemTime::now();
+
+    let root = data_path(ATTACHMENTS_DIR);
+    let shards = match fs::read_dir(&root) {
+        Ok(shards) => shards,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(e) => return Err(e),
+    };
+
+    let mut removed = 0;
+    let mut freed = 0;
+    for shard in shards {
+        let shard = shard?.path();
+        if !shard.is_dir() {
+            continue;
+        }
+        for blob in fs::read_dir(&shard)? {
+            let blob = blob?;
+            let name = blob.file_name().to_string_lossy().to_string();
+            // File sementara dan file asing dilewati
+            let Some(hash) = blob_hash(&name) else {
+                continue;
+            };
+            if referenced.contains(hash) {
+                continue;
+            }
+            let metadata = blob.metadata()?;
+            let age = metadata.modified().ok().and_then(|modified| now.duration_since(modified).ok());
+            if age.is_none_or(|age| age < GC_GRACE) {
+                continue;
+            }
+            freed += metadata.len();
+            fs::remove_file(blob.path())?;
+            removed += 1;
+        }
+        // Folder shard kosong ikut dihapus; gagal berarti masih ada isinya
+        let _ = fs::remove_dir(&shard);
+    }
+    Ok((removed, freed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::test_data_dir;
+
+    #[test]
+    fn sanitize_name_keeps_only_the_file_name() {
+        assert_eq!(sanitize_name("report.pdf"), "report.pdf");
+        assert_eq!(sanitize_name("/home/me/report.pdf"), "report.pdf");
+        assert_eq!(sanitize_name("C:\\Users\\me\\report.pdf"), "report.pdf");
+        assert_eq!(sanitize_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_name("  bad\r\nname\u{7}.txt "), "badname.txt");
+        assert_eq!(sanitize_name("dir/"), "attachment");
+        assert_eq!(sanitize_name("\n\t"), "attachment");
+    }
+
+    #[test]
+    fn is_hash_accepts_only_sha256_hex() {
+        let hash = hash_bytes(b"hello");
+        assert_eq!(hash, "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+        assert!(is_hash(&hash));
+        assert!(is_hash(&hash.to_uppercase()));
+        assert!(!is_hash(&hash[..63]));
+        assert!(!is_hash(&format!("{}0", hash)));
+        assert!(!is_hash(&format!("../{}", &hash[3..])));
+        assert!(!is_hash(""));
+        assert_eq!(blob_hash(&format!("{}.enc", hash)), Some(hash.as_str()));
+        assert_eq!(blob_hash(&format!("{}.tmp", hash)), None);
+    }
+
+    #[test]
+    fn detach_by_name_or_hash() {
+        test_data_dir();
+        let mut notes = Notes::new();
+        notes.add("note".to_string());
+        let first = add_attachment(&mut notes, 1, "a.txt", "1".repeat(64), 1).unwrap();
+        let second = add_attachment(&mut notes, 1, "b.txt", "2".repeat(64), 2).unwrap();
+        assert!(add_attachment(&mut notes, 9, "c.txt", "3".repeat(64), 3).is_none());
+
+        assert_eq!(detach(&mut notes, 1, &second.hash).unwrap().name, "b.txt");
+        assert!(detach(&mut notes, 1, "b.txt").is_none());
+        assert!(detach(&mut notes, 2, "a.txt").is_none());
+        assert_eq!(detach(&mut notes, 1, "a.txt").unwrap().hash, first.hash);
+        assert!(notes.get(1).unwrap().attachments.is_empty());
+    }
+
+    /// Tulis blob dengan waktu ubah `age` yang lalu, return path-nya
+    fn blob_aged(content: &[u8], age: Duration) -> PathBuf {
+        let path = blob_path(&hash_bytes(content));
+        write_blob_file(&path, content).unwrap();
+        let modified = SystemTime::now() - age;
+        fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+        path
+    }
+
+    #[test]
+    fn gc_removes_only_old_unreferenced_blobs() {
+        test_data_dir();
+        let old = GC_GRACE + Duration::from_secs(60);
+        let orphan = blob_aged(b"gc orphan", old);
+        let recent = blob_aged(b"gc recent upload", GC_GRACE / 2);
+        let used = blob_aged(b"gc still used", old);
+        let tmp = used.with_extension("tmp");
+        fs::write(&tmp, b"partial").unwrap();
+
+        let mut notes = Notes::new();
+        notes.add("with attachment".to_string());
+        add_attachment(&mut notes, 1, "used.txt", hash_bytes(b"gc still used"), 13);
+
+        let (removed, freed) = gc(&notes).unwrap();
+        assert_eq!((removed, freed), (1, b"gc orphan".len() as u64));
+        assert!(!orphan.exists());
+        // Blob yang baru ditulis bisa jadi upload yang note-nya belum tersimpan
+        assert!(recent.exists());
+        assert!(used.exists());
+        assert!(tmp.exists());
+    }
+}
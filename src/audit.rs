@@ -0,0 +1,112 @@
+// Audit log web server: siapa membuat, mengubah atau menghapus note mana, dan kapan
+// Disimpan sebagai JSON lines di <data dir>/audit.jsonl. File hanya pernah ditambah di
+// akhir, tidak pernah ditulis ulang; isi note tidak ikut dicatat (bisa terenkripsi).
+// Perubahan lewat CLI/TUI tidak tercatat, hanya yang lewat API.
+
+use crate::notes::{data_path, now_timestamp, Note};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use utoipa::ToSchema;
+
+pub const AUDIT_FILE: &str = "audit.jsonl";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Created,
+    Updated,
+    Deleted,
+    Attached,
+    Detached,
+    Resolved,
+    /// Isi dari sesi edit bersama disimpan ke note
+    LiveEdited,
+    /// Sync mengubah banyak note sekaligus; `note` kosong, ringkasan di `detail`
+    Synced,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct Entry {
+    /// Waktu lokal server, format sama dengan `created_at` note
+    pub at: String,
+    pub user: String,
+    pub action: Action,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<usize>,
+    /// UID note; tetap bisa dicari setelah note dihapus atau pindah id karena sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    /// Nama file attachment, ringkasan sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl Entry {
+    pub fn new(user: &str, action: Action, note: &Note) -> Entry {
+        Entry {
+            at: now_timestamp(),
+            user: user.to_string(),
+            action,
+            note: Some(note.id),
+            uid: Some(note.uid.clone()),
+            detail: None,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Entry {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Tambahkan entry di akhir file (dibuat kalau belum ada)
+pub fn append(entries: &[Entry]) -> io::Result<()> {
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry)?);
+        lines.push('\n');
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(data_path(AUDIT_FILE))?;
+    // Satu write per batch supaya baris tidak terpotong di tengah
+    file.write_all(lines.as_bytes())
+}
+
+/// Filter untuk `read`; field kosong berarti tidak difilter
+#[derive(Debug, Default)]
+pub struct Filter {
+    pub user: Option<String>,
+    pub note: Option<usize>,
+    pub uid: Option<String>,
+    pub action: Option<Action>,
+    /// Entry pada atau setelah waktu ini (`YYYY-MM-DD` atau `YYYY-MM-DD HH:MM:SS`)
+    pub since: Option<String>,
+}
+
+impl Filter {
+    fn matches(&self, entry: &Entry) -> bool {
+        self.user.as_ref().is_none_or(|user| entry.user == *user)
+            && self.note.is_none_or(|id| entry.note == Some(id))
+            && self.uid.as_ref().is_none_or(|uid| entry.uid.as_ref() == Some(uid))
+            && self.action.is_none_or(|action| entry.action == action)
+            // Format timestamp bisa dibandingkan sebagai string
+            && self.since.as_ref().is_none_or(|since| entry.at >= *since)
+    }
+}
+
+/// Entry yang cocok dengan `filter`, terbaru dulu, paling banyak `limit`.
+/// Baris yang rusak (misalnya tulisan terakhir yang terpotong) dilewati.
+pub fn read(filter: &Filter, limit: usize) -> io::Result<Vec<Entry>> {
+    let text = match fs::read_to_string(data_path(AUDIT_FILE)) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(text
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<Entry>(line).ok())
+        .filter(|entry| filter.matches(entry))
+        .take(limit)
+        .collect())
+}
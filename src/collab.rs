@@ -0,0 +1,724 @@
+// Edit bersama satu note secara real-time dari web (operational transform)
+// Setiap note yang sedang dibuka punya satu Session di server: isi dokumen, nomor
+// revisi, riwayat operasi, dan daftar peer (user + posisi kursor). Client mengirim
+// operasi berdasarkan revisi terakhir yang dia tahu; server men-transform operasi itu
+// terhadap operasi lain yang masuk duluan, menerapkannya, lalu menyiarkan hasilnya.
+//
+// Format operasi sama dengan ot.js: array berisi angka positif (retain n karakter),
+// string (insert) dan angka negatif (delete n karakter). Posisi dihitung dalam
+// karakter Unicode (code point), bukan byte atau unit UTF-16.
+
+#![allow(dead_code)]
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::sync::broadcast;
+
+/// Riwayat yang disimpan per session; client yang tertinggal lebih jauh harus sinkron ulang
+const MAX_HISTORY: usize = 500;
+const CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Component {
+    Retain(usize),
+    Insert(String),
+    Delete(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OtError {
+    /// Panjang dokumen tidak cocok dengan panjang yang diharapkan operasi
+    LengthMismatch { expected: usize, actual: usize },
+    /// Komponen kosong atau angka 0
+    InvalidComponent,
+}
+
+impl fmt::Display for OtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OtError::LengthMismatch { expected, actual } => write!(
+                f,
+                "{}",
+                t!("collab-length-mismatch", expected = *expected, actual = *actual)
+            ),
+            OtError::InvalidComponent => write!(f, "{}", t!("collab-invalid-operation")),
+        }
+    }
+}
+
+impl std::error::Error for OtError {}
+
+/// Urutan komponen yang mengubah dokumen sepanjang `base_len` jadi `target_len`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Operation {
+    ops: Vec<Component>,
+    base_len: usize,
+    target_len: usize,
+}
+
+fn char_len(text: &str) -> usize {
+    text.chars().count()
+}
+
+impl Operation {
+    pub fn base_len(&self) -> usize {
+        self.base_len
+    }
+
+    pub fn target_len(&self) -> usize {
+        self.target_len
+    }
+
+    /// Operasi yang tidak mengubah apa pun
+    pub fn is_noop(&self) -> bool {
+        self.ops.iter().all(|op| matches!(op, Component::Retain(_)))
+    }
+
+    pub fn retain(&mut self, n: usize) -> &mut Self {
+        if n == 0 {
+            return self;
+        }
+        self.base_len += n;
+        self.target_len += n;
+        match self.ops.last_mut() {
+            Some(Component::Retain(last)) => *last += n,
+            _ => self.ops.push(Component::Retain(n)),
+        }
+        self
+    }
+
+    /// Insert selalu diletakkan sebelum delete di posisi yang sama, supaya dua operasi
+    /// dengan efek sama punya bentuk yang sama
+    pub fn insert(&mut self, text: &str) -> &mut Self {
+        if text.is_empty() {
+            return self;
+        }
+        self.target_len += char_len(text);
+        let len = self.ops.len();
+        match self.ops.as_mut_slice() {
+            [.., Component::Insert(last)] => last.push_str(text),
+            [.., Component::Insert(before), Component::Delete(_)] => before.push_str(text),
+            [.., Component::Delete(_)] => self.ops.insert(len - 1, Component::Insert(text.to_string())),
+            _ => self.ops.push(Component::Insert(text.to_string())),
+        }
+        self
+    }
+
+    pub fn delete(&mut self, n: usize) -> &mut Self {
+        if n == 0 {
+            return self;
+        }
+        self.base_len += n;
+        match self.ops.last_mut() {
+            Some(Component::Delete(last)) => *last += n,
+            _ => self.ops.push(Component::Delete(n)),
+        }
+        self
+    }
+
+    pub fn apply(&self, doc: &str) -> Result<String, OtError> {
+        let chars: Vec<char> = doc.chars().collect();
+        if chars.len() != self.base_len {
+            return Err(OtError::LengthMismatch {
+                expected: self.base_len,
+                actual: chars.len(),
+            });
+        }
+        let mut result = String::with_capacity(doc.len());
+        let mut index = 0;
+        for op in &self.ops {
+            match op {
+                Component::Retain(n) => {
+                    result.extend(&chars[index..index + n]);
+                    index += n;
+                }
+                Component::Insert(text) => result.push_str(text),
+                Component::Delete(n) => index += n,
+            }
+        }
+        Ok(result)
+    }
+
+    /// Transform dua operasi konkuren `a` dan `b` (dari dokumen yang sama) menjadi
+    /// `(a', b')` sehingga `apply(apply(doc, a), b') == apply(apply(doc, b), a')`.
+    /// Insert di posisi yang sama: milik `a` diletakkan lebih dulu.
+    pub fn transform(a: &Operation, b: &Operation) -> Result<(Operation, Operation), OtError> {
+        if a.base_len != b.base_len {
+            return Err(OtError::LengthMismatch {
+                expected: a.base_len,
+                actual: b.base_len,
+            });
+        }
+        let mut a_prime = Operation::default();
+        let mut b_prime = Operation::default();
+        let mut ops_a = a.ops.iter().cloned();
+        let mut ops_b = b.ops.iter().cloned();
+        let mut op_a = ops_a.next();
+        let mut op_b = ops_b.next();
+        loop {
+            match (op_a.take(), op_b.take()) {
+                (None, None) => break,
+                (Some(Component::Insert(text)), other) => {
+                    b_prime.retain(char_len(&text));
+                    a_prime.insert(&text);
+                    op_a = ops_a.next();
+                    op_b = other;
+                }
+                (other, Some(Component::Insert(text))) => {
+                    a_prime.retain(char_len(&text));
+                    b_prime.insert(&text);
+                    op_a = other;
+                    op_b = ops_b.next();
+                }
+                (Some(first), Some(second)) => {
+                    let (len_a, len_b) = (component_len(&first), component_len(&second));
+                    let n = len_a.min(len_b);
+                    match (&first, &second) {
+                        (Component::Retain(_), Component::Retain(_)) => {
+                            a_prime.retain(n);
+                            b_prime.retain(n);
+                        }
+                        // Keduanya menghapus teks yang sama: tidak ada yang tersisa untuk dihapus
+                        (Component::Delete(_), Component::Delete(_)) => {}
+                        (Component::Delete(_), Component::Retain(_)) => {
+                            a_prime.delete(n);
+                        }
+                        (Component::Retain(_), Component::Delete(_)) => {
+                            b_prime.delete(n);
+                        }
+                        _ => unreachable!("insert ditangani di atas"),
+                    }
+                    op_a = shrink(first, n).or_else(|| ops_a.next());
+                    op_b = shrink(second, n).or_else(|| ops_b.next());
+                }
+                // Panjang sama tapi komponen habis duluan: operasi tidak valid
+                _ => return Err(OtError::InvalidComponent),
+            }
+        }
+        Ok((a_prime, b_prime))
+    }
+
+    /// Posisi `index` (kursor) setelah operasi ini diterapkan
+    pub fn transform_index(&self, index: usize) -> usize {
+        let mut position = 0;
+        let mut new_index = index;
+        for op in &self.ops {
+            if position > index {
+                break;
+            }
+            match op {
+                Component::Retain(n) => position += n,
+                Component::Insert(text) => new_index += char_len(text),
+                Component::Delete(n) => {
+                    new_index -= (*n).min(index - position);
+                    position += n;
+                }
+            }
+        }
+        new_index
+    }
+}
+
+fn component_len(op: &Component) -> usize {
+    match op {
+        Component::Retain(n) | Component::Delete(n) => *n,
+        Component::Insert(text) => char_len(text),
+    }
+}
+
+/// Sisa komponen retain/delete setelah `n` karakter dipakai
+fn shrink(op: Component, n: usize) -> Option<Component> {
+    match op {
+        Component::Retain(len) if len > n => Some(Component::Retain(len - n)),
+        Component::Delete(len) if len > n => Some(Component::Delete(len - n)),
+        _ => None,
+    }
+}
+
+/// Bentuk JSON satu komponen: angka positif, string, atau angka negatif
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawComponent {
+    Count(i64),
+    Text(String),
+}
+
+impl Serialize for Operation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let raw: Vec<RawComponent> = self
+            .ops
+            .iter()
+            .map(|op| match op {
+                Component::Retain(n) => RawComponent::Count(*n as i64),
+                Component::Insert(text) => RawComponent::Text(text.clone()),
+                Component::Delete(n) => RawComponent::Count(-(*n as i64)),
+            })
+            .collect();
+        raw.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Operation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Vec::<RawComponent>::deserialize(deserializer)?;
+        let mut operation = Operation::default();
+        for component in raw {
+            match component {
+                RawComponent::Count(n) if n > 0 => operation.retain(n as usize),
+                RawComponent::Count(n) if n < 0 => operation.delete(n.unsigned_abs() as usize),
+                RawComponent::Text(text) if !text.is_empty() => operation.insert(&text),
+                _ => return Err(serde::de::Error::custom(OtError::InvalidComponent)),
+            };
+        }
+        Ok(operation)
+    }
+}
+
+/// Seleksi teks; `anchor == head` berarti kursor biasa
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Cursor {
+    pub anchor: usize,
+    pub head: usize,
+}
+
+impl Cursor {
+    fn transform(self, op: &Operation) -> Cursor {
+        Cursor {
+            anchor: op.transform_index(self.anchor),
+            head: op.transform_index(self.head),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Peer {
+    pub client: u64,
+    pub user: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<Cursor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ClientMessage {
+    Op { revision: usize, op: Operation },
+    Cursor { anchor: usize, head: usize },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ServerMessage {
+    /// Pesan pertama untuk client baru: isi dokumen dan peer yang sudah ada
+    Init {
+        client: u64,
+        revision: usize,
+        content: String,
+        peers: Vec<Peer>,
+        read_only: bool,
+    },
+    /// Operasi client ini sudah diterapkan sebagai `revision`
+    Ack { revision: usize },
+    Op {
+        client: u64,
+        revision: usize,
+        op: Operation,
+    },
+    Cursor {
+        client: u64,
+        user: String,
+        anchor: usize,
+        head: usize,
+    },
+    Join { client: u64, user: String },
+    Leave { client: u64 },
+    /// Note diubah di luar session: dokumen diganti isi note, operasi yang belum di-ack dibuang
+    Reset { revision: usize, content: String },
+    Error { detail: String },
+}
+
+pub struct Session {
+    pub content: String,
+    /// Isi note saat session dibuka atau terakhir disimpan. Kalau isi note sudah berbeda
+    /// dari ini, note diubah di luar session.
+    pub saved: String,
+    /// User yang operasinya terakhir diterapkan (untuk audit log dan cek akses saat simpan)
+    pub editor: Option<String>,
+    revision: usize,
+    /// Operasi untuk revisi `history_start + 1` dan seterusnya
+    history: VecDeque<Operation>,
+    history_start: usize,
+    peers: BTreeMap<u64, Peer>,
+    /// Penyimpanan ke notes sudah dijadwalkan (ditunda supaya tidak menulis per ketukan)
+    pub save_scheduled: bool,
+    tx: broadcast::Sender<ServerMessage>,
+}
+
+fn next_client_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+impl Session {
+    fn new(content: String) -> Session {
+        Session {
+            saved: content.clone(),
+            content,
+            editor: None,
+            revision: 0,
+            history: VecDeque::new(),
+            history_start: 0,
+            peers: BTreeMap::new(),
+            save_scheduled: false,
+            tx: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Daftarkan peer baru. Receiver dibuat di bawah lock yang sama dengan snapshot
+    /// `Init`, jadi client menerima semua operasi setelah revisi di snapshot itu.
+    pub fn join(&mut self, user: &str, read_only: bool) -> (u64, ServerMessage, broadcast::Receiver<ServerMessage>) {
+        let client = next_client_id();
+        let init = ServerMessage::Init {
+            client,
+            revision: self.revision,
+            content: self.content.clone(),
+            peers: self.peers.values().cloned().collect(),
+            read_only,
+        };
+        let rx = self.tx.subscribe();
+        self.peers.insert(
+            client,
+            Peer {
+                client,
+                user: user.to_string(),
+                cursor: None,
+            },
+        );
+        let _ = self.tx.send(ServerMessage::Join {
+            client,
+            user: user.to_string(),
+        });
+        (client, init, rx)
+    }
+
+    /// Hapus peer; mengembalikan jumlah peer yang tersisa
+    pub fn leave(&mut self, client: u64) -> usize {
+        if self.peers.remove(&client).is_some() {
+            let _ = self.tx.send(ServerMessage::Leave { client });
+        }
+        self.peers.len()
+    }
+
+    /// Terapkan operasi dari `client` yang dibuat berdasarkan `revision`.
+    /// `max_bytes` membatasi ukuran dokumen hasilnya.
+    pub fn receive(&mut self, client: u64, revision: usize, op: Operation, max_bytes: usize) -> Result<(), String> {
+        if revision > self.revision || revision < self.history_start {
+            return Err(t!("collab-out-of-sync"));
+        }
+        let mut op = op;
+        for concurrent in self.history.iter().skip(revision - self.history_start) {
+            op = Operation::transform(&op, concurrent).map_err(|e| e.to_string())?.0;
+        }
+        let content = op.apply(&self.content).map_err(|e| e.to_string())?;
+        if content.len() > max_bytes {
+            return Err(t!("api-content-too-long", size = t!("bytes", count = max_bytes)));
+        }
+        self.content = content;
+        self.revision += 1;
+        self.editor = self.peers.get(&client).map(|peer| peer.user.clone());
+        for peer in self.peers.values_mut() {
+            peer.cursor = peer.cursor.map(|cursor| cursor.transform(&op));
+        }
+        self.history.push_back(op.clone());
+        if self.history.len() > MAX_HISTORY {
+            self.history.pop_front();
+            self.history_start += 1;
+        }
+        let _ = self.tx.send(ServerMessage::Op {
+            client,
+            revision: self.revision,
+            op,
+        });
+        Ok(())
+    }
+
+    pub fn revision(&self) -> usize {
+        self.revision
+    }
+
+    /// Ganti dokumen dengan `content` (isi note yang diubah di luar session). Riwayat
+    /// dikosongkan, jadi operasi yang dibuat dari revisi sebelumnya ditolak.
+    pub fn reset(&mut self, content: String) {
+        self.content = content.clone();
+        self.saved = content.clone();
+        self.editor = None;
+        self.revision += 1;
+        self.history.clear();
+        self.history_start = self.revision;
+        for peer in self.peers.values_mut() {
+            peer.cursor = None;
+        }
+        let _ = self.tx.send(ServerMessage::Reset {
+            revision: self.revision,
+            content,
+        });
+    }
+
+    pub fn set_cursor(&mut self, client: u64, anchor: usize, head: usize) {
+        let len = char_len(&self.content);
+        let cursor = Cursor {
+            anchor: anchor.min(len),
+            head: head.min(len),
+        };
+        let Some(peer) = self.peers.get_mut(&client) else {
+            return;
+        };
+        peer.cursor = Some(cursor);
+        let _ = self.tx.send(ServerMessage::Cursor {
+            client,
+            user: peer.user.clone(),
+            anchor: cursor.anchor,
+            head: cursor.head,
+        });
+    }
+}
+
+/// Session yang sedang aktif, per id note
+#[derive(Clone, Default)]
+pub struct Collab(Arc<Mutex<HashMap<usize, Arc<Mutex<Session>>>>>);
+
+/// Session hanya state sementara; kalau ada handler yang panic, lanjutkan dengan isinya
+pub fn lock_session(session: &Mutex<Session>) -> MutexGuard<'_, Session> {
+    session.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Collab {
+    fn sessions(&self) -> MutexGuard<'_, HashMap<usize, Arc<Mutex<Session>>>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Session untuk note `id`; kalau belum ada, dibuat dari `content()`.
+    /// `content` dipanggil di bawah lock registry supaya tidak balapan dengan `leave`.
+    pub fn session<E>(&self, id: usize, content: impl FnOnce() -> Result<String, E>) -> Result<Arc<Mutex<Session>>, E> {
+        let mut sessions = self.sessions();
+        if let Some(session) = sessions.get(&id) {
+            return Ok(Arc::clone(session));
+        }
+        let session = Arc::new(Mutex::new(Session::new(content()?)));
+        sessions.insert(id, Arc::clone(&session));
+        Ok(session)
+    }
+
+    pub fn is_live(&self, id: usize) -> bool {
+        self.sessions().contains_key(&id)
+    }
+
+    /// Semua session yang aktif, urut id note. Kunci session-nya dalam urutan ini
+    /// kalau perlu memegang beberapa sekaligus.
+    pub fn live(&self) -> Vec<(usize, Arc<Mutex<Session>>)> {
+        let mut live: Vec<_> = self.sessions().iter().map(|(id, session)| (*id, Arc::clone(session))).collect();
+        live.sort_by_key(|(id, _)| *id);
+        live
+    }
+
+    /// Keluarkan `client` dari session note `id`. Kalau dia peer terakhir, session
+    /// ditutup dan `persist` dipanggil dengan session itu (masih di bawah lock
+    /// registry, jadi client yang masuk sesudahnya membaca isi yang sudah tersimpan).
+    pub fn leave(&self, id: usize, client: u64, persist: impl FnOnce(&mut Session)) {
+        let mut sessions = self.sessions();
+        let Some(session) = sessions.get(&id).cloned() else {
+            return;
+        };
+        let mut guard = lock_session(&session);
+        if guard.leave(client) == 0 {
+            sessions.remove(&id);
+            persist(&mut guard);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn op(value: serde_json::Value) -> Operation {
+        serde_json::from_value(value).unwrap()
+    }
+
+    /// Operasi untuk `doc`: hapus `delete` karakter mulai `at`, lalu sisipkan `insert`
+    fn edit(doc: &str, at: usize, delete: usize, insert: &str) -> Operation {
+        let mut operation = Operation::default();
+        operation
+            .retain(at)
+            .insert(insert)
+            .delete(delete)
+            .retain(char_len(doc) - at - delete);
+        operation
+    }
+
+    /// Semua edit kecil yang mungkin di `doc`: insert di setiap posisi, hapus setiap
+    /// range, dan ganti setiap range
+    fn edits(doc: &str) -> Vec<Operation> {
+        let len = char_len(doc);
+        let mut edits = Vec::new();
+        for at in 0..=len {
+            for delete in 0..=len - at {
+                for insert in ["", "x", "é😀"] {
+                    if delete > 0 || !insert.is_empty() {
+                        edits.push(edit(doc, at, delete, insert));
+                    }
+                }
+            }
+        }
+        edits
+    }
+
+    fn assert_converges(doc: &str, a: &Operation, b: &Operation) -> String {
+        let (a_prime, b_prime) = Operation::transform(a, b).unwrap();
+        let left = b_prime.apply(&a.apply(doc).unwrap()).unwrap();
+        let right = a_prime.apply(&b.apply(doc).unwrap()).unwrap();
+        assert_eq!(left, right, "doc {:?}, a {:?}, b {:?}", doc, a, b);
+        left
+    }
+
+    #[test]
+    fn json_format_matches_ot_js() {
+        let operation = op(json!([2, "ab", -1, 3]));
+        assert_eq!((operation.base_len(), operation.target_len()), (6, 7));
+        assert_eq!(serde_json::to_value(&operation).unwrap(), json!([2, "ab", -1, 3]));
+        assert_eq!(operation.apply("12x456").unwrap(), "12ab456");
+        for invalid in [json!([0]), json!([""]), json!([1.5])] {
+            assert!(serde_json::from_value::<Operation>(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn apply_counts_characters_not_bytes() {
+        let doc = "añ😀b";
+        assert_eq!(op(json!([2, -1, "🎉", 1])).apply(doc).unwrap(), "añ🎉b");
+        assert_eq!(
+            op(json!([3])).apply(doc),
+            Err(OtError::LengthMismatch { expected: 3, actual: 4 })
+        );
+    }
+
+    #[test]
+    fn concurrent_inserts_at_same_spot() {
+        let a = edit("ac", 1, 0, "X");
+        let b = edit("ac", 1, 0, "Y");
+        // Insert milik operasi pertama diletakkan lebih dulu, di kedua urutan
+        assert_eq!(assert_converges("ac", &a, &b), "aXYc");
+        assert_eq!(assert_converges("ac", &b, &a), "aYXc");
+    }
+
+    #[test]
+    fn overlapping_deletes() {
+        let doc = "abcdef";
+        let a = edit(doc, 1, 3, "");
+        let b = edit(doc, 2, 3, "");
+        assert_eq!(assert_converges(doc, &a, &b), "af");
+        let same = edit(doc, 1, 2, "");
+        assert_eq!(assert_converges(doc, &same, &same), "adef");
+        let inner = edit(doc, 2, 1, "Z");
+        assert_eq!(assert_converges(doc, &a, &inner), "aZef");
+    }
+
+    #[test]
+    fn transform_converges_for_all_small_edits() {
+        for doc in ["", "ab", "é😀c"] {
+            let edits = edits(doc);
+            for a in &edits {
+                for b in &edits {
+                    assert_converges(doc, a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transform_rejects_different_base() {
+        let result = Operation::transform(&op(json!([2])), &op(json!([3])));
+        assert_eq!(result, Err(OtError::LengthMismatch { expected: 2, actual: 3 }));
+    }
+
+    #[test]
+    fn transform_index_follows_edits() {
+        // "hello world" -> "hey world": "llo" diganti "y"
+        let operation = edit("hello world", 2, 3, "y");
+        let cases = [(0, 0), (2, 3), (3, 3), (4, 3), (5, 3), (6, 4), (11, 9)];
+        for (before, after) in cases {
+            assert_eq!(operation.transform_index(before), after, "index {}", before);
+        }
+        // Insert tepat di posisi kursor mendorong kursor ke belakang teks baru
+        assert_eq!(edit("ab", 1, 0, "😀😀").transform_index(1), 3);
+        assert_eq!(edit("ab", 1, 0, "😀😀").transform_index(0), 0);
+    }
+
+    #[test]
+    fn receive_transforms_against_concurrent_ops() {
+        let mut session = Session::new("ac".to_string());
+        let (first, _, _rx) = session.join("alice", false);
+        let (second, _, _rx2) = session.join("bob", false);
+        session.set_cursor(second, 2, 2);
+
+        session.receive(first, 0, edit("ac", 1, 0, "b"), 100).unwrap();
+        // Dibuat dari revisi 0, sebelum insert "b" diketahui
+        session.receive(second, 0, edit("ac", 2, 0, "d"), 100).unwrap();
+        assert_eq!(session.content, "abcd");
+        assert_eq!(session.revision, 2);
+        let cursor = session.peers[&second].cursor.unwrap();
+        assert_eq!((cursor.anchor, cursor.head), (4, 4));
+    }
+
+    #[test]
+    fn receive_rejects_unknown_or_forgotten_revision() {
+        let mut session = Session::new(String::new());
+        assert!(session.receive(1, 1, op(json!(["x"])), 100).is_err());
+
+        for i in 0..=MAX_HISTORY {
+            session.receive(1, i, edit(&"x".repeat(i), i, 0, "x"), 10_000).unwrap();
+        }
+        assert_eq!(session.history.len(), MAX_HISTORY);
+        assert_eq!(session.history_start, 1);
+        // Riwayat untuk revisi 0 sudah dibuang: client harus sinkron ulang
+        assert!(session.receive(2, 0, op(json!(["y"])), 10_000).is_err());
+        // Revisi 1 masih ada: dokumennya saat itu "x"
+        session.receive(2, 1, edit("x", 0, 0, "y"), 10_000).unwrap();
+        assert_eq!(session.content, format!("y{}", "x".repeat(MAX_HISTORY + 1)));
+    }
+
+    #[test]
+    fn reset_replaces_the_document_and_drops_older_ops() {
+        let mut session = Session::new("draft".to_string());
+        let (alice, _, mut rx) = session.join("alice", false);
+        session.set_cursor(alice, 5, 5);
+        session.receive(alice, 0, edit("draft", 5, 0, "!"), 100).unwrap();
+        assert_eq!(session.editor.as_deref(), Some("alice"));
+        assert_eq!(session.saved, "draft");
+
+        session.reset("from sync".to_string());
+        assert_eq!((session.content.as_str(), session.saved.as_str()), ("from sync", "from sync"));
+        assert_eq!((session.revision, session.editor.as_deref()), (2, None));
+        assert!(session.peers[&alice].cursor.is_none());
+        // Operasi yang dibuat sebelum reset tidak bisa diterapkan ke dokumen baru
+        assert!(session.receive(alice, 1, edit("draft!", 0, 0, "x"), 100).is_err());
+        session.receive(alice, 2, edit("from sync", 0, 0, "x"), 100).unwrap();
+        assert_eq!(session.content, "xfrom sync");
+
+        let messages: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert!(matches!(
+            messages.as_slice(),
+            [ServerMessage::Join { .. }, ServerMessage::Cursor { .. }, ServerMessage::Op { .. }, ServerMessage::Reset { revision: 2, .. }, ServerMessage::Op { .. }]
+        ));
+    }
+
+    #[test]
+    fn receive_enforces_size_limit() {
+        let mut session = Session::new("ab".to_string());
+        assert!(session.receive(1, 0, edit("ab", 2, 0, "😀"), 5).is_err());
+        assert_eq!((session.content.as_str(), session.revision), ("ab", 0));
+    }
+}
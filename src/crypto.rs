@@ -0,0 +1,186 @@
+// Enkripsi untuk notebook terenkripsi dan locked notes
+// Key diturunkan dari passphrase dengan Argon2id, data dienkripsi dengan XChaCha20-Poly1305
+// (authenticated encryption: passphrase salah atau file yang diubah akan gagal dibuka)
+
+#![allow(dead_code)]
+
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const PASSPHRASE_ENV: &str = "RUST_APP_PASSPHRASE";
+
+const SALT_LEN: usize = 16;
+
+#[derive(Debug)]
+pub enum CryptoError {
+    /// Passphrase salah atau data sudah diubah
+    Decrypt,
+    Format(String),
+}
+
+impl std::fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptoError::Decrypt => write!(f, "{}", t!("crypto-decrypt-failed")),
+            CryptoError::Format(msg) => write!(f, "{}", t!("crypto-invalid-data", error = msg)),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+/// Data terenkripsi beserta parameter yang dibutuhkan untuk membukanya
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct Sealed {
+    pub kdf: String,
+    pub cipher: String,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], CryptoError> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| CryptoError::Format(e.to_string()))?;
+    Ok(key)
+}
+
+pub fn seal(passphrase: &str, plaintext: &[u8]) -> Result<Sealed, CryptoError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = XChaCha20Poly1305::new(&key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| CryptoError::Format("encryption failed".to_string()))?;
+
+    Ok(Sealed {
+        kdf: "argon2id".to_string(),
+        cipher: "xchacha20poly1305".to_string(),
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+pub fn open(passphrase: &str, sealed: &Sealed) -> Result<Vec<u8>, CryptoError> {
+    if sealed.kdf != "argon2id" || sealed.cipher != "xchacha20poly1305" {
+        return Err(CryptoError::Format(format!(
+            "unsupported algorithms {}/{}",
+            sealed.kdf, sealed.cipher
+        )));
+    }
+    let decode = |field: &str| {
+        BASE64
+            .decode(field)
+            .map_err(|e| CryptoError::Format(e.to_string()))
+    };
+    let salt = decode(&sealed.salt)?;
+    let nonce = decode(&sealed.nonce)?;
+    let ciphertext = decode(&sealed.ciphertext)?;
+    if nonce.len() != 24 {
+        return Err(CryptoError::Format("bad nonce length".to_string()));
+    }
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = XChaCha20Poly1305::new(&key.into());
+    cipher
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| CryptoError::Decrypt)
+}
+
+/// Passphrase dari env RUST_APP_PASSPHRASE, atau tanya di terminal (tanpa echo)
+pub fn read_passphrase(prompt: &str) -> std::io::Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    rpassword::prompt_password(prompt)
+}
+
+/// Minta passphrase baru dua kali supaya typo tidak mengunci data selamanya
+pub fn read_new_passphrase() -> std::io::Result<String> {
+    let passphrase = match std::env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) => passphrase,
+        Err(_) => {
+            let first = rpassword::prompt_password(t!("prompt-new-passphrase"))?;
+            let second = rpassword::prompt_password(t!("prompt-repeat-passphrase"))?;
+            if first != second {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    t!("passphrase-mismatch"),
+                ));
+            }
+            first
+        }
+    };
+    // Env var yang kosong juga ditolak, sama seperti input kosong di terminal
+    if passphrase.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            t!("passphrase-empty"),
+        ));
+    }
+    Ok(passphrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_and_open_round_trip() {
+        let plaintext = "catatan rahasia 🔐".as_bytes();
+        let sealed = seal("correct horse", plaintext).unwrap();
+        assert_eq!(open("correct horse", &sealed).unwrap(), plaintext);
+        assert_eq!(open("correct horse", &seal("correct horse", b"").unwrap()).unwrap(), b"");
+        // Salt dan nonce baru setiap kali
+        let again = seal("correct horse", plaintext).unwrap();
+        assert_ne!(again.salt, sealed.salt);
+        assert_ne!(again.nonce, sealed.nonce);
+        assert_ne!(again.ciphertext, sealed.ciphertext);
+    }
+
+    #[test]
+    fn wrong_passphrase_or_tampering_fails() {
+        let sealed = seal("correct horse", b"secret").unwrap();
+        assert!(matches!(open("wrong horse", &sealed), Err(CryptoError::Decrypt)));
+
+        let mut ciphertext = BASE64.decode(&sealed.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        let tampered = Sealed {
+            ciphertext: BASE64.encode(ciphertext),
+            ..sealed.clone()
+        };
+        assert!(matches!(open("correct horse", &tampered), Err(CryptoError::Decrypt)));
+    }
+
+    #[test]
+    fn malformed_sealed_data_is_a_format_error() {
+        let sealed = seal("pw", b"secret").unwrap();
+        let cases = [
+            Sealed {
+                cipher: "aes".to_string(),
+                ..sealed.clone()
+            },
+            Sealed {
+                nonce: BASE64.encode([0u8; 12]),
+                ..sealed.clone()
+            },
+            Sealed {
+                salt: "not base64!".to_string(),
+                ..sealed.clone()
+            },
+        ];
+        for case in &cases {
+            assert!(matches!(open("pw", case), Err(CryptoError::Format(_))), "{:?}", case);
+        }
+    }
+}
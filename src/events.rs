@@ -0,0 +1,70 @@
+// Event perubahan note untuk halaman web yang sedang terbuka (Server-Sent Events)
+// Handler di main_web.rs mengirim event lewat broadcast channel; setiap koneksi
+// /api/events memfilter sendiri event mana yang boleh dilihat user-nya.
+
+use crate::notes::Note;
+use serde_json::json;
+use tokio::sync::broadcast;
+
+/// Koneksi yang tertinggal lebih dari ini menerima satu event `reload` sebagai gantinya
+const CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone)]
+pub enum NoteEvent {
+    Created(Note),
+    Updated(Note),
+    /// Isi note sebelum dihapus, supaya masih bisa dicek siapa yang boleh melihatnya
+    Deleted(Note),
+    /// Banyak note berubah sekaligus (sync, jadwal, share); `None` berarti semua user
+    Reload(Option<Vec<String>>),
+}
+
+impl NoteEvent {
+    /// Nama event SSE (`event:` di stream, `addEventListener` di browser)
+    pub fn name(&self) -> &'static str {
+        match self {
+            NoteEvent::Created(_) => "note-created",
+            NoteEvent::Updated(_) => "note-updated",
+            NoteEvent::Deleted(_) => "note-deleted",
+            NoteEvent::Reload(_) => "reload",
+        }
+    }
+
+    pub fn note(&self) -> Option<&Note> {
+        match self {
+            NoteEvent::Created(note) | NoteEvent::Updated(note) | NoteEvent::Deleted(note) => Some(note),
+            NoteEvent::Reload(_) => None,
+        }
+    }
+
+    /// Isi `data:`: note lengkap untuk created/updated, cukup id untuk deleted
+    pub fn data(&self) -> String {
+        match self {
+            NoteEvent::Created(note) | NoteEvent::Updated(note) => {
+                serde_json::to_string(note).unwrap_or_default()
+            }
+            NoteEvent::Deleted(note) => json!({ "id": note.id }).to_string(),
+            NoteEvent::Reload(_) => "{}".to_string(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Events(broadcast::Sender<NoteEvent>);
+
+impl Default for Events {
+    fn default() -> Self {
+        Events(broadcast::channel(CHANNEL_CAPACITY).0)
+    }
+}
+
+impl Events {
+    /// Kirim event ke semua koneksi; tidak apa-apa kalau belum ada yang mendengarkan
+    pub fn send(&self, event: NoteEvent) {
+        let _ = self.0.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<NoteEvent> {
+        self.0.subscribe()
+    }
+}
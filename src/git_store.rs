@@ -0,0 +1,277 @@
+// Storage backend berbasis git (lokal saja, tanpa remote)
+// Setiap note disimpan sebagai <data dir>/notes_git/<id>.md dengan front matter,
+// dan setiap save membuat satu commit dengan pesan yang menjelaskan perubahannya.
+//
+// Front matter berisi semua field Note selain content, satu field per baris
+// dalam format `key: <json>` (JSON value juga valid YAML).
+
+#![allow(dead_code)]
+
+use crate::notes::{data_path, Note, Notes, Tombstone};
+use git2::{Delta, IndexAddOption, Repository, Signature, Sort};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const GIT_STORE_DIR: &str = "notes_git";
+const META_FILE: &str = "notebook.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Meta {
+    next_id: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tombstones: Vec<Tombstone>,
+}
+
+pub struct LogEntry {
+    pub id: String,
+    pub time: String,
+    pub author: String,
+    pub message: String,
+}
+
+pub fn repo_dir() -> PathBuf {
+    data_path(GIT_STORE_DIR)
+}
+
+pub fn is_git_backed() -> bool {
+    repo_dir().join(".git").exists()
+}
+
+/// Tempat memindahkan repo saat kembali ke notes.json: `notes_git.old-<waktu>`, supaya
+/// backup dari perpindahan sebelumnya tidak menghalangi (atau tertimpa)
+pub fn backup_dir() -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let base = format!("{}.old-{}", GIT_STORE_DIR, stamp);
+    let mut path = data_path(&base);
+    let mut n = 1;
+    while path.exists() {
+        n += 1;
+        path = data_path(&format!("{}-{}", base, n));
+    }
+    path
+}
+
+fn note_file(id: usize) -> String {
+    format!("{}.md", id)
+}
+
+fn to_markdown(note: &Note) -> Result<String, Box<dyn Error>> {
+    let value = serde_json::to_value(note)?;
+    let mut out = String::from("---\n");
+    if let serde_json::Value::Object(fields) = value {
+        for (key, value) in fields {
+            if key != "content" {
+                out.push_str(&format!("{}: {}\n", key, value));
+            }
+        }
+    }
+    out.push_str("---\n");
+    out.push_str(&note.content);
+    out.push('\n');
+    Ok(out)
+}
+
+fn from_markdown(text: &str) -> Result<Note, Box<dyn Error>> {
+    let rest = text
+        .strip_prefix("---\n")
+        .ok_or("missing front matter")?;
+    let end = rest.find("\n---\n").ok_or("unterminated front matter")?;
+    let mut fields = serde_json::Map::new();
+    for line in rest[..end].lines() {
+        if let Some((key, value)) = line.split_once(": ") {
+            let value = serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+            fields.insert(key.to_string(), value);
+        }
+    }
+    let content = &rest[end + 5..];
+    let content = content.strip_suffix('\n').unwrap_or(content);
+    fields.insert("content".to_string(), content.into());
+    Ok(serde_json::from_value(serde_json::Value::Object(fields))?)
+}
+
+pub fn load() -> Result<Notes, Box<dyn Error>> {
+    let dir = repo_dir();
+    let mut notes = Notes::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "md") {
+            let note = from_markdown(&fs::read_to_string(&path)?)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            notes.notes.push(note);
+        }
+    }
+    notes.notes.sort_by_key(|note| note.id);
+
+    let max_id = notes.notes.iter().map(|note| note.id).max().unwrap_or(0);
+    notes.next_id = max_id + 1;
+    if let Ok(meta) = fs::read_to_string(dir.join(META_FILE)) {
+        let meta: Meta = serde_json::from_str(&meta)?;
+        notes.next_id = notes.next_id.max(meta.next_id);
+        notes.tombstones = meta.tombstones;
+    }
+    Ok(notes)
+}
+
+fn write_if_changed(path: &Path, content: &str) -> std::io::Result<()> {
+    if fs::read_to_string(path).ok().as_deref() != Some(content) {
+        fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+fn signature(repo: &Repository) -> Result<Signature<'static>, git2::Error> {
+    // Pakai user.name/user.email dari git config kalau ada
+    repo.signature()
+        .map(|sig| sig.to_owned())
+        .or_else(|_| Signature::now("rust_app", "rust_app@localhost"))
+}
+
+/// Buat pesan commit dari perubahan yang di-stage, misalnya "Add note 3: Standup"
+fn commit_message(notes: &Notes, changes: &[(Delta, String)]) -> String {
+    let describe = |delta: &Delta, path: &str| -> Option<String> {
+        let id: usize = path.strip_suffix(".md")?.parse().ok()?;
+        let title = notes.get(id).map(|note| note.title()).unwrap_or("");
+        let verb = match delta {
+            Delta::Added => "Add",
+            Delta::Deleted => "Delete",
+            _ => "Update",
+        };
+        if title.is_empty() {
+            Some(format!("{} note {}", verb, id))
+        } else {
+            Some(format!("{} note {}: {}", verb, id, title))
+        }
+    };
+    let lines: Vec<String> = changes
+        .iter()
+        .filter_map(|(delta, path)| describe(delta, path))
+        .collect();
+    match lines.len() {
+        0 => "Update notebook metadata".to_string(),
+        1 => lines[0].clone(),
+        n => format!("Update {} notes\n\n{}", n, lines.join("\n")),
+    }
+}
+
+pub fn init(notes: &Notes) -> Result<(), Box<dyn Error>> {
+    let dir = repo_dir();
+    fs::create_dir_all(&dir)?;
+    Repository::init(&dir)?;
+    save(notes)?;
+    Ok(())
+}
+
+/// Tulis semua note ke working tree repo lalu commit kalau ada perubahan
+pub fn save(notes: &Notes) -> Result<(), Box<dyn Error>> {
+    let dir = repo_dir();
+    let repo = Repository::open(&dir)?;
+
+    let mut wanted: BTreeMap<String, String> = BTreeMap::new();
+    for note in &notes.notes {
+        wanted.insert(note_file(note.id), to_markdown(note)?);
+    }
+    for (name, content) in &wanted {
+        write_if_changed(&dir.join(name), content)?;
+    }
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        if name.ends_with(".md") && !wanted.contains_key(&name) {
+            fs::remove_file(&path)?;
+        }
+    }
+    let meta = serde_json::to_string_pretty(&Meta {
+        next_id: notes.next_id,
+        tombstones: notes.tombstones.clone(),
+    })?;
+    write_if_changed(&dir.join(META_FILE), &(meta + "\n"))?;
+
+    let mut index = repo.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"], None)?;
+    index.write()?;
+
+    let head_tree = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        Err(_) => None,
+    };
+    let diff = repo.diff_tree_to_index(head_tree.as_ref(), Some(&index), None)?;
+    let changes: Vec<(Delta, String)> = diff
+        .deltas()
+        .filter_map(|delta| {
+            let path = delta.new_file().path().or_else(|| delta.old_file().path())?;
+            Some((delta.status(), path.to_string_lossy().to_string()))
+        })
+        .collect();
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let sig = signature(&repo)?;
+    let parent = match repo.head() {
+        Ok(head) => Some(head.peel_to_commit()?),
+        Err(_) => None,
+    };
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &sig,
+        &sig,
+        &commit_message(notes, &changes),
+        &tree,
+        &parents,
+    )?;
+    Ok(())
+}
+
+/// History commit, terbaru dulu. Kalau `id` diisi, hanya commit yang mengubah note tersebut.
+pub fn log(id: Option<usize>, limit: usize) -> Result<Vec<LogEntry>, Box<dyn Error>> {
+    let repo = Repository::open(repo_dir())?;
+    let mut walk = repo.revwalk()?;
+    if walk.push_head().is_err() {
+        return Ok(Vec::new());
+    }
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+    let path = id.map(note_file);
+    let mut entries = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        if let Some(path) = &path {
+            let tree = commit.tree()?;
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let mut opts = git2::DiffOptions::new();
+            opts.pathspec(path);
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))?;
+            if diff.deltas().len() == 0 {
+                continue;
+            }
+        }
+
+        let time = chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        entries.push(LogEntry {
+            id: commit.id().to_string()[..8].to_string(),
+            time,
+            author: commit.author().name().unwrap_or("").to_string(),
+            message: commit.message().unwrap_or("").trim_end().to_string(),
+        });
+        if entries.len() >= limit {
+            break;
+        }
+    }
+    Ok(entries)
+}
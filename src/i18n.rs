@@ -0,0 +1,198 @@
+// Terjemahan string untuk user (CLI, TUI, web, GUI) dengan Fluent.
+// Katalog ada di locales/<bahasa>.ftl dan di-embed saat compile, jadi binary
+// tidak butuh file tambahan. Pesan yang belum diterjemahkan jatuh ke English.
+//
+// Yang sengaja tidak diterjemahkan: help clap, status code dan nama field di
+// output machine (json/jsonl/tsv), isi note dan pesan commit git storage.
+
+#![allow(dead_code)]
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use clap::ValueEnum;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::sync::OnceLock;
+
+pub const LANG_ENV: &str = "RUST_APP_LANG";
+
+/// Format timestamp di notes.json (lihat notes::now_timestamp)
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Pesan dalam bahasa aktif: `t!("note-not-found", id = 3)`
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::message($key, None)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), fluent_bundle::FluentValue::from($value));)+
+        $crate::i18n::message($key, Some(&args))
+    }};
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Lang {
+    /// English
+    En,
+    /// Bahasa Indonesia
+    Id,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Id];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Id => "id",
+        }
+    }
+
+    /// Bahasa dari tag locale/BCP 47: "id_ID.UTF-8", "id-ID", "en"
+    pub fn parse(tag: &str) -> Option<Lang> {
+        let language = tag.split(['_', '-', '.', '@']).next()?.trim().to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Lang::En),
+            "id" => Some(Lang::Id),
+            _ => None,
+        }
+    }
+
+    fn catalog(self) -> &'static str {
+        match self {
+            Lang::En => include_str!("../locales/en.ftl"),
+            Lang::Id => include_str!("../locales/id.ftl"),
+        }
+    }
+}
+
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+/// Bahasa dari environment: RUST_APP_LANG, lalu seperti gettext
+/// (LANGUAGE sebagai daftar prioritas, kemudian LC_ALL, LC_MESSAGES, LANG).
+/// Locale C/POSIX atau bahasa yang tidak dikenal berarti English.
+pub fn detect() -> Lang {
+    if let Some(lang) = env(LANG_ENV).and_then(|value| Lang::parse(&value)) {
+        return lang;
+    }
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter().find_map(env);
+    if locale
+        .as_deref()
+        .is_some_and(|locale| locale == "C" || locale == "POSIX" || locale.starts_with("C."))
+    {
+        return Lang::En;
+    }
+    let languages = env("LANGUAGE").unwrap_or_default();
+    languages
+        .split(':')
+        .chain(locale.as_deref())
+        .find_map(Lang::parse)
+        .unwrap_or(Lang::En)
+}
+
+/// Bahasa dari header Accept-Language browser, mengikuti urutan q-value
+pub fn negotiate(accept_language: &str) -> Option<Lang> {
+    let mut ranges: Vec<(&str, f32)> = accept_language
+        .split(',')
+        .map(|range| {
+            let mut parts = range.split(';');
+            let tag = parts.next().unwrap_or("").trim();
+            let q = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+            (tag, q)
+        })
+        .filter(|(_, q)| *q > 0.0)
+        .collect();
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranges.into_iter().find_map(|(tag, _)| Lang::parse(tag))
+}
+
+static CURRENT: OnceLock<Lang> = OnceLock::new();
+
+/// Pilih bahasa dari --lang; harus dipanggil sebelum pesan pertama diterjemahkan
+pub fn set_lang(lang: Lang) {
+    let _ = CURRENT.set(lang);
+}
+
+pub fn lang() -> Lang {
+    *CURRENT.get_or_init(detect)
+}
+
+fn bundle(lang: Lang) -> &'static FluentBundle<FluentResource> {
+    static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+    let bundles = BUNDLES.get_or_init(|| {
+        Lang::ALL
+            .iter()
+            .map(|lang| {
+                let resource = FluentResource::try_new(lang.catalog().to_string())
+                    .unwrap_or_else(|(_, errors)| panic!("invalid {}.ftl: {:?}", lang.code(), errors));
+                let mut bundle = FluentBundle::new_concurrent(vec![lang.code().parse().unwrap()]);
+                // Tanpa tanda Unicode isolation di sekitar argumen, supaya output terminal tetap bersih
+                bundle.set_use_isolating(false);
+                bundle
+                    .add_resource(resource)
+                    .unwrap_or_else(|errors| panic!("duplicate message in {}.ftl: {:?}", lang.code(), errors));
+                bundle
+            })
+            .collect()
+    });
+    &bundles[lang as usize]
+}
+
+/// Pesan `key` dalam bahasa `lang`. Kalau belum diterjemahkan pakai English;
+/// kalau key tidak ada sama sekali, key itu sendiri yang dikembalikan.
+pub fn message_in(lang: Lang, key: &str, args: Option<&FluentArgs>) -> String {
+    for lang in [lang, Lang::En] {
+        let bundle = bundle(lang);
+        if let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) {
+            let mut errors = Vec::new();
+            return bundle.format_pattern(pattern, args, &mut errors).into_owned();
+        }
+    }
+    key.to_string()
+}
+
+pub fn message(key: &str, args: Option<&FluentArgs>) -> String {
+    message_in(lang(), key, args)
+}
+
+/// Semua pesan dengan awalan `prefix` (misalnya "web-") untuk front-end di browser.
+/// Argumen dibiarkan sebagai placeholder `{$name}` dan diisi di sisi JavaScript.
+pub fn messages_in(lang: Lang, prefix: &str) -> Vec<(String, String)> {
+    Lang::En
+        .catalog()
+        .lines()
+        .filter_map(|line| line.split_once(" ="))
+        .map(|(key, _)| key)
+        .filter(|key| key.starts_with(prefix))
+        .map(|key| (key.to_string(), message_in(lang, key, None)))
+        .collect()
+}
+
+/// Tanggal sesuai bahasa aktif: "Oct 18, 2026" / "18 Okt 2026"
+pub fn format_date(date: NaiveDate) -> String {
+    t!(
+        "date",
+        day = date.day().to_string(),
+        month = message(&format!("month-{}", date.month()), None),
+        year = date.year().to_string(),
+    )
+}
+
+/// Timestamp note sesuai bahasa aktif: "Oct 18, 2026, 14:39" / "18 Okt 2026 14.39".
+/// Teks yang bukan timestamp dikembalikan apa adanya.
+pub fn format_timestamp(timestamp: &str) -> String {
+    match NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT) {
+        Ok(time) => t!(
+            "datetime",
+            date = format_date(time.date()),
+            hour = format!("{:02}", time.hour()),
+            minute = format!("{:02}", time.minute()),
+        ),
+        Err(_) => timestamp.to_string(),
+    }
+}
@@ -0,0 +1,112 @@
+// Wiki-style links antar note
+// Syntax: [[judul note]] atau [[#id]]
+
+#![allow(dead_code)]
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum LinkTarget {
+    Id(usize),
+    Title(String),
+}
+
+impl std::fmt::Display for LinkTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinkTarget::Id(id) => write!(f, "[[#{}]]", id),
+            LinkTarget::Title(title) => write!(f, "[[{}]]", title),
+        }
+    }
+}
+
+/// Ambil semua link [[...]] dari content, sesuai urutan kemunculan
+pub fn parse_links(content: &str) -> Vec<LinkTarget> {
+    let mut links = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        let inner = after[..end].trim();
+        if let Some(id) = inner.strip_prefix('#') {
+            if let Ok(id) = id.trim().parse::<usize>() {
+                links.push(LinkTarget::Id(id));
+            }
+        } else if !inner.is_empty() && !inner.contains('\n') {
+            links.push(LinkTarget::Title(inner.to_string()));
+        }
+        rest = &after[end + 2..];
+    }
+    links
+}
+
+/// Judul note = baris pertama yang tidak kosong, tanpa prefix heading Markdown
+pub fn title_of(content: &str) -> &str {
+    content
+        .lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::{test_data_dir, Notes};
+
+    fn title(text: &str) -> LinkTarget {
+        LinkTarget::Title(text.to_string())
+    }
+
+    #[test]
+    fn parses_id_and_title_links_in_order() {
+        let content = "See [[#12]], then [[ Shopping list ]] and [[# 3 ]].";
+        assert_eq!(parse_links(content), [LinkTarget::Id(12), title("Shopping list"), LinkTarget::Id(3)]);
+    }
+
+    #[test]
+    fn skips_malformed_links() {
+        assert!(parse_links("[[]] [[  ]] [[#]] [[#abc]] [[#-1]]").is_empty());
+        assert!(parse_links("[[two\nlines]]").is_empty());
+        // Tanpa penutup: sisa teks bukan link
+        assert_eq!(parse_links("[[a]] [[unclosed"), [title("a")]);
+        assert_eq!(parse_links("[[a]]]] [[b]]"), [title("a"), title("b")]);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for target in [LinkTarget::Id(7), title("Meeting notes")] {
+            assert_eq!(parse_links(&target.to_string()), [target]);
+        }
+    }
+
+    #[test]
+    fn title_is_first_non_empty_line_without_heading() {
+        assert_eq!(title_of("\n  \n## Weekly plan\nbody"), "Weekly plan");
+        assert_eq!(title_of("plain first line\nsecond"), "plain first line");
+        assert_eq!(title_of("#\n# Real title"), "Real title");
+        assert_eq!(title_of(""), "");
+    }
+
+    #[test]
+    fn resolves_ids_titles_and_broken_links() {
+        test_data_dir();
+        let mut notes = Notes::new();
+        notes.add("# Shopping List\nmilk".to_string());
+        notes.add("Plan: [[shopping list]], [[#1]], [[#99]], [[Nowhere]], [[#2]]".to_string());
+
+        let links = notes.links_from(2).unwrap();
+        let resolved: Vec<_> = links.iter().map(|(_, to)| *to).collect();
+        // Judul dicocokkan tanpa membedakan huruf besar/kecil
+        assert_eq!(resolved, [Some(1), Some(1), None, None, Some(2)]);
+        assert_eq!(notes.broken_links(), [(2, LinkTarget::Id(99)), (2, title("Nowhere"))]);
+        // Link ke diri sendiri tidak dihitung sebagai backlink
+        assert_eq!(notes.backlinks(1), [2]);
+        assert!(notes.backlinks(2).is_empty());
+        assert_eq!(notes.links_from(3), None);
+    }
+}
@@ -1,140 +1,1397 @@
-use std::fs;
-use std::io;
-use serde::{Deserialize, Serialize};
+// Harus pertama supaya macro t! bisa dipakai di semua module
+#[macro_use]
+mod i18n;
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Note {
-    id: usize,
-    content: String,
-    created_at: String,
+mod attachments;
+mod crypto;
+mod git_store;
+mod links;
+mod merge;
+mod notes;
+mod output;
+mod picker;
+mod select;
+mod sync;
+mod templates;
+mod text;
+
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use notes::{load_notes, save_notes, Note, Notes};
+use output::{
+    Format, GcRecord, LinkRecord, LogRecord, NoteRecord, Output, ScheduleRecord, StatusRecord,
+    TemplateRecord,
+};
+use select::Query;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use templates::{Recurrence, Schedule};
+
+/// 📝 Note Manager - A simple CLI note-taking app
+#[derive(Debug, Parser)]
+#[command(name = "rust_app", version, about, arg_required_else_help = true)]
+#[command(
+    after_help = "Commands taking a note ID open a fuzzy picker when the ID is left out.\n\n\
+Exit codes: 0 success, 1 failure (e.g. saving), 2 invalid usage, 3 not found, 130 cancelled"
+)]
+struct Cli {
+    /// Directory holding notes.json, templates and attachments
+    #[arg(long, global = true, env = notes::DATA_DIR_ENV, value_name = "DIR")]
+    data_dir: Option<PathBuf>,
+
+    /// Output format; json, jsonl and tsv follow the schema in the README
+    #[arg(short, long, global = true, value_enum, default_value_t = Format::Text, alias = "format")]
+    output: Format,
+
+    /// Only print errors and requested data
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Language of messages and dates (default: from LANG / LC_ALL)
+    #[arg(long, global = true, value_enum, env = i18n::LANG_ENV)]
+    lang: Option<i18n::Lang>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Add a new note
+    Add {
+        /// Note content (multiple words are joined with spaces)
+        #[arg(required = true, num_args = 1..)]
+        content: Vec<String>,
+    },
+    /// Add a note from a template
+    New {
+        #[arg(long, value_name = "NAME")]
+        template: String,
+    },
+    /// List notes (archived notes only with --archived)
+    List {
+        #[command(flatten)]
+        query: Query,
+    },
+    /// Show a note
+    Show { id: Option<usize> },
+    /// Edit a note in $EDITOR
+    Edit { id: Option<usize> },
+    /// Delete notes
+    Delete {
+        #[command(flatten)]
+        target: Target,
+    },
+    /// Add or remove tags
+    Tag {
+        #[command(flatten)]
+        target: Target,
+        /// Tag to add (repeatable)
+        #[arg(long = "add", value_name = "TAG", required_unless_present = "remove")]
+        add: Vec<String>,
+        /// Tag to remove (repeatable)
+        #[arg(long = "remove", value_name = "TAG")]
+        remove: Vec<String>,
+    },
+    /// Move notes to a notebook
+    Move {
+        #[command(flatten)]
+        target: Target,
+        /// Notebook name; an empty name takes notes out of their notebook
+        #[arg(long, value_name = "NAME")]
+        to: String,
+    },
+    /// Archive notes (hidden from list unless --archived)
+    Archive {
+        #[command(flatten)]
+        target: Target,
+    },
+    /// Bring archived notes back
+    Unarchive {
+        #[command(flatten)]
+        target: Target,
+    },
+    /// Pin a note to the top of the list
+    Pin { id: Option<usize> },
+    /// Unpin a note
+    Unpin { id: Option<usize> },
+    /// Show [[links]] from a note, or all broken links
+    Links {
+        id: Option<usize>,
+        /// Show links that point nowhere
+        #[arg(long, conflicts_with = "id")]
+        broken: bool,
+    },
+    /// Show notes linking to a note
+    Backlinks { id: Option<usize> },
+    /// Attach a file to a note
+    Attach { id: usize, file: PathBuf },
+    /// List attachments of a note
+    Attachments { id: Option<usize> },
+    /// Remove an attachment from a note
+    Detach {
+        id: usize,
+        /// Attachment name or hash
+        name: String,
+    },
+    /// Delete orphaned attachment files
+    Gc,
+    /// Encrypt the notebook file and its attachments
+    Encrypt,
+    /// Decrypt the notebook file and its attachments
+    Decrypt,
+    /// Lock a note with its own passphrase
+    Lock { id: Option<usize> },
+    /// Permanently unlock a locked note
+    Unlock { id: Option<usize> },
+    /// Show a locked note without unlocking it
+    Reveal { id: Option<usize> },
+    /// Sync notes with a rust_app_web server
+    Sync {
+        #[arg(env = sync::SYNC_URL_ENV)]
+        url: String,
+    },
+    /// List notes with conflicting edits
+    Conflicts,
+    /// Three-way merge a conflicting note
+    Resolve {
+        id: Option<usize>,
+        #[command(flatten)]
+        choice: ResolveChoice,
+    },
+    /// Show or switch the storage backend
+    Storage {
+        #[arg(value_enum)]
+        backend: Option<Backend>,
+    },
+    /// Show history (git storage only)
+    Log { id: Option<usize> },
+    /// Manage note templates
+    #[command(subcommand)]
+    Template(TemplateCommand),
+    /// Manage recurring notes
+    #[command(subcommand)]
+    Schedule(ScheduleCommand),
+    /// Print shell completions (bash, zsh, fish, ...)
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Print the man page (roff)
+    Man,
+}
+
+/// Tanpa flag, konflik ditampilkan dan user ditanya secara interaktif
+#[derive(Debug, Args)]
+#[group(multiple = false)]
+struct ResolveChoice {
+    /// Keep the local version
+    #[arg(long)]
+    ours: bool,
+    /// Keep the other device's version
+    #[arg(long)]
+    theirs: bool,
+    /// Keep the automatic merge (only if no lines conflict)
+    #[arg(long)]
+    merged: bool,
+    /// Fix the merge by hand in $EDITOR
+    #[arg(long)]
+    edit: bool,
+}
+
+/// Note untuk operasi bulk: id, daftar/range id, query, atau picker kalau semuanya kosong
+#[derive(Debug, Args)]
+struct Target {
+    /// Note ID, or a list of IDs and ranges such as 3-7,10
+    ids: Option<String>,
+    #[command(flatten)]
+    query: Query,
+    /// Don't ask before changing several notes
+    #[arg(short, long)]
+    yes: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Notes {
-    notes: Vec<Note>,
-    next_id: usize,
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Backend {
+    Json,
+    Git,
 }
 
-impl Notes {
-    fn new() -> Self {
-        Notes {
-            notes: Vec::new(),
-            next_id: 1,
+#[derive(Debug, Subcommand)]
+enum TemplateCommand {
+    /// Save a template ("\n" in the content becomes a newline)
+    Add {
+        name: String,
+        #[arg(required = true, num_args = 1..)]
+        content: Vec<String>,
+    },
+    /// List templates
+    List,
+    /// Show a template
+    Show { name: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum ScheduleCommand {
+    /// Create a note from a template every day or week
+    Add {
+        template: String,
+        /// daily or weekly
+        every: String,
+        /// Day for weekly schedules (e.g. mon)
+        weekday: Option<String>,
+    },
+    /// List schedules
+    List,
+    /// Remove a schedule
+    Remove { number: usize },
+}
+
+/// Error CLI, masing-masing punya exit code sendiri
+#[derive(Debug)]
+enum CliError {
+    /// Gagal menjalankan perintah (misalnya save gagal) - exit 1
+    Failed(String),
+    /// Input valid secara sintaks tapi tidak bisa dipakai - exit 2
+    Invalid(String),
+    /// Note/template/attachment tidak ada - exit 3
+    NotFound(String),
+    /// User membatalkan picker - exit 130 (seperti Ctrl-C)
+    Cancelled,
+}
+
+impl CliError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Failed(_) => 1,
+            CliError::Invalid(_) => 2,
+            CliError::NotFound(_) => 3,
+            CliError::Cancelled => 130,
         }
     }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Failed(msg) | CliError::Invalid(msg) | CliError::NotFound(msg) => {
+                write!(f, "{}", msg)
+            }
+            CliError::Cancelled => write!(f, "{}", t!("cancelled")),
+        }
+    }
+}
+
+type CliResult = Result<(), CliError>;
 
-    fn add(&mut self, content: String) {
-        let note = Note {
-            id: self.next_id,
-            content,
-            created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+fn note_not_found(id: usize) -> CliError {
+    CliError::NotFound(t!("note-not-found", id = id))
+}
+
+/// Id dari argumen, atau pilih lewat fuzzy picker kalau tidak diberikan.
+/// `candidates` membatasi note yang bisa dipilih (misalnya hanya yang locked).
+fn note_id(
+    id: Option<usize>,
+    notes: &Notes,
+    out: &Output,
+    prompt: &str,
+    candidates: impl Fn(&Note) -> bool,
+) -> Result<usize, CliError> {
+    if let Some(id) = id {
+        return Ok(id);
+    }
+    if out.machine() || !picker::available() {
+        return Err(CliError::Invalid(t!("cli-id-required")));
+    }
+    let choices: Vec<&Note> = notes.notes.iter().filter(|note| candidates(note)).collect();
+    if choices.is_empty() {
+        return Err(CliError::NotFound(t!("cli-nothing-to-pick")));
+    }
+    picker::pick(prompt, choices)
+        .map_err(|e| CliError::Failed(t!("cli-picker-failed", error = e.to_string())))?
+        .ok_or(CliError::Cancelled)
+}
+
+/// Id note dari Target. Tanpa id dan filter, pakai picker (satu note).
+fn select_notes(
+    target: &Target,
+    notes: &Notes,
+    out: &Output,
+    prompt: &str,
+    candidates: impl Fn(&Note) -> bool,
+) -> Result<Vec<usize>, CliError> {
+    if target.ids.is_none() && target.query.is_empty() {
+        let id = note_id(None, notes, out, prompt, |note| {
+            !note.archived && candidates(note)
+        })?;
+        return Ok(vec![id]);
+    }
+    let ids = match &target.ids {
+        Some(spec) => Some(select::parse_ids(spec).map_err(CliError::Invalid)?),
+        None => None,
+    };
+    let selected = select::select(notes, ids.as_ref(), &target.query);
+    if !selected.missing.is_empty() {
+        let missing: Vec<String> = selected.missing.iter().map(|id| id.to_string()).collect();
+        if selected.ids.is_empty() && selected.missing.len() == 1 {
+            return Err(note_not_found(selected.missing[0]));
+        }
+        eprintln!("{}", t!("cli-skipping-missing", ids = missing.join(", ")));
+    }
+    if selected.ids.is_empty() {
+        return Err(CliError::NotFound(t!("cli-no-match")));
+    }
+    Ok(selected.ids)
+}
+
+/// Minta konfirmasi sebelum mengubah lebih dari satu note atau note hasil query.
+/// Prompt ditulis ke stderr supaya stdout tetap bersih untuk --output json.
+fn confirm(target: &Target, ids: &[usize], notes: &Notes, action: &str) -> CliResult {
+    let bulk = ids.len() > 1 || !target.query.is_empty();
+    if !bulk || target.yes {
+        return Ok(());
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(CliError::Invalid(t!(
+            "cli-confirm-needs-yes",
+            action = action,
+            count = ids.len()
+        )));
+    }
+    eprintln!(
+        "{}",
+        t!("cli-confirm-list", action = action, count = ids.len())
+    );
+    for note in ids.iter().filter_map(|id| notes.get(*id)) {
+        eprintln!("  [{}] {}", note.id, note.title());
+    }
+    eprint!("{} ", t!("cli-confirm-prompt"));
+    let mut answer = String::new();
+    std::io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| CliError::Failed(e.to_string()))?;
+    // "y"/"yes" selalu diterima, ditambah jawaban "ya" dalam bahasa aktif
+    let answer = answer.trim().to_lowercase();
+    match answer.as_str() {
+        "y" | "yes" => Ok(()),
+        _ if answer == t!("cli-confirm-yes").to_lowercase() => Ok(()),
+        _ => Err(CliError::Cancelled),
+    }
+}
+
+/// Hasil operasi bulk: satu status record per note di mode machine, ringkasan di mode text
+fn report(out: &Output, status: &'static str, ids: &[usize], text: String) {
+    if out.machine() {
+        let records: Vec<_> = ids
+            .iter()
+            .map(|id| StatusRecord {
+                status,
+                id: Some(*id),
+                message: t!(&format!("status-{}", status), id = *id),
+            })
+            .collect();
+        out.records(&records);
+    } else {
+        out.info(text);
+    }
+}
+
+fn set_archived(notes: &mut Notes, target: &Target, archived: bool, out: &Output) -> CliResult {
+    let (prompt, status) = if archived {
+        (t!("cli-prompt-archive"), "archived")
+    } else {
+        (t!("cli-prompt-unarchive"), "unarchived")
+    };
+    // Unarchive hanya masuk akal untuk note yang diarsipkan (juga di picker)
+    let ids = if !archived && target.ids.is_none() && target.query.is_empty() {
+        vec![note_id(None, notes, out, &prompt, |note| note.archived)?]
+    } else if !archived && target.ids.is_none() {
+        let query = Query {
+            archived: true,
+            ..target.query.clone()
         };
-        self.notes.push(note);
-        self.next_id += 1;
+        select::select(notes, None, &query).ids
+    } else {
+        select_notes(target, notes, out, &prompt, |_| true)?
+    };
+    if ids.is_empty() {
+        return Err(CliError::NotFound(t!("cli-no-match")));
+    }
+    confirm(target, &ids, notes, &prompt)?;
+    let mut changed = Vec::new();
+    for id in &ids {
+        let note = notes.get_mut(*id).unwrap();
+        if note.archived != archived {
+            note.archived = archived;
+            note.touch();
+            changed.push(*id);
+        }
+    }
+    save(notes)?;
+    let text = if archived {
+        t!("cli-archived", count = changed.len())
+    } else {
+        t!("cli-unarchived", count = changed.len())
+    };
+    report(out, status, &changed, text);
+    Ok(())
+}
+
+fn save(notes: &Notes) -> CliResult {
+    save_notes(notes).map_err(|e| CliError::Failed(t!("error-saving", error = e.to_string())))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Some(lang) = cli.lang {
+        i18n::set_lang(lang);
+    }
+    if let Some(dir) = &cli.data_dir {
+        std::env::set_var(notes::DATA_DIR_ENV, dir);
     }
+    let out = Output::new(cli.output, cli.quiet);
 
-    fn list(&self) {
-        if self.notes.is_empty() {
-            println!("No notes found.");
-            return;
+    match run(cli.command, &out) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) if std::io::stderr().is_terminal() => {
+            eprintln!("❌ {}", e);
+            ExitCode::from(e.exit_code())
         }
-        println!("\n📝 Your Notes:");
-        println!("{}", "=".repeat(50));
-        for note in &self.notes {
-            println!("[{}] {}", note.id, note.content);
-            println!("    Created: {}", note.created_at);
-            println!();
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(e.exit_code())
         }
     }
+}
+
+fn run(command: Command, out: &Output) -> CliResult {
+    // Perintah yang tidak butuh notebook dijalankan tanpa load (dan tanpa prompt passphrase)
+    let command = match command {
+        Command::Completions { shell } => {
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut Cli::command(), "rust_app", &mut script);
+            return std::io::Write::write_all(&mut std::io::stdout(), &script)
+                .map_err(|e| CliError::Failed(t!("cli-error-completions", error = e.to_string())));
+        }
+        Command::Man => {
+            return clap_mangen::Man::new(Cli::command())
+                .render(&mut std::io::stdout())
+                .map_err(|e| CliError::Failed(t!("cli-error-man", error = e.to_string())));
+        }
+        Command::Template(command) => return handle_template(command, out),
+        Command::Schedule(command) => return handle_schedule(command, out),
+        command => command,
+    };
+
+    let mut notes = load_notes();
+    match templates::run_due_schedules(&mut notes) {
+        Ok(0) => {}
+        Ok(count) => out.info(t!("schedules-ran", count = count)),
+        Err(e) => eprintln!("{}", t!("schedules-error", error = e.to_string())),
+    }
 
-    fn delete(&mut self, id: usize) -> bool {
-        let initial_len = self.notes.len();
-        self.notes.retain(|note| note.id != id);
-        self.notes.len() < initial_len
+    match command {
+        Command::Add { content } => {
+            let note = notes.add(content.join(" ")).clone();
+            save(&notes)?;
+            out.record(&NoteRecord::from(&note));
+            out.status("added", Some(note.id), t!("cli-added"));
+        }
+        Command::New { template } => {
+            let content = templates::load_template(&template).map_err(|e| {
+                CliError::NotFound(t!(
+                    "cli-template-not-found",
+                    name = &template,
+                    error = e.to_string()
+                ))
+            })?;
+            let today = chrono::Local::now().date_naive();
+            let note = notes.add(templates::render(&content, today)).clone();
+            save(&notes)?;
+            out.record(&NoteRecord::from(&note));
+            out.status(
+                "added",
+                Some(note.id),
+                t!("cli-added-from-template", id = note.id, name = &template),
+            );
+        }
+        Command::List { query } => print_list(&notes, &query, out),
+        Command::Show { id } => {
+            let id = note_id(id, &notes, out, &t!("cli-prompt-show"), |_| true)?;
+            let note = notes.get(id).ok_or_else(|| note_not_found(id))?;
+            if out.machine() {
+                out.record(&NoteRecord::from(note));
+                return Ok(());
+            }
+            out.text(format!("[{}] {}", note.id, out.content(note)));
+            out.line(format!(
+                "    {}",
+                t!(
+                    "label-created",
+                    date = i18n::format_timestamp(&note.created_at)
+                )
+            ));
+            out.line(format!(
+                "    {}",
+                t!(
+                    "label-updated",
+                    date = i18n::format_timestamp(&note.updated_at)
+                )
+            ));
+            if !note.attachments.is_empty() {
+                out.line(format!(
+                    "    {}",
+                    t!("label-attachments", count = note.attachments.len())
+                ));
+            }
+        }
+        Command::Edit { id } => {
+            let id = note_id(id, &notes, out, &t!("cli-prompt-edit"), |note| {
+                !note.is_locked()
+            })?;
+            let note = notes.get(id).ok_or_else(|| note_not_found(id))?;
+            if note.is_locked() {
+                return Err(CliError::Invalid(t!("cli-edit-locked", id = id)));
+            }
+            let text = merge::edit_in_editor(&note.content)
+                .map_err(|e| CliError::Failed(t!("error-editor", error = e.to_string())))?;
+            let content = text.trim_end_matches('\n').to_string();
+            if content.trim().is_empty() {
+                return Err(CliError::Invalid(t!("cli-edit-empty")));
+            }
+            if !notes.get_mut(id).unwrap().edit(content) {
+                out.status("unchanged", Some(id), t!("cli-unchanged", id = id));
+                return Ok(());
+            }
+            notes.rebuild_links();
+            save(&notes)?;
+            out.record(&NoteRecord::from(notes.get(id).unwrap()));
+            out.status("edited", Some(id), t!("cli-edited", id = id));
+        }
+        Command::Pin { id } => set_pinned(&mut notes, id, true, out)?,
+        Command::Unpin { id } => set_pinned(&mut notes, id, false, out)?,
+        Command::Delete { target } => {
+            let prompt = t!("cli-prompt-delete");
+            let ids = select_notes(&target, &notes, out, &prompt, |_| true)?;
+            confirm(&target, &ids, &notes, &prompt)?;
+            for id in &ids {
+                notes.delete(*id);
+            }
+            save(&notes)?;
+            match ids.as_slice() {
+                [id] => out.status("deleted", Some(*id), t!("cli-deleted", id = *id)),
+                _ => report(
+                    out,
+                    "deleted",
+                    &ids,
+                    t!("cli-deleted-many", count = ids.len()),
+                ),
+            }
+        }
+        Command::Tag {
+            target,
+            add,
+            remove,
+        } => {
+            let normalize = |tags: Vec<String>| -> Result<Vec<String>, CliError> {
+                tags.iter()
+                    .map(|tag| select::normalize_tag(tag).map_err(CliError::Invalid))
+                    .collect()
+            };
+            let (add, remove) = (normalize(add)?, normalize(remove)?);
+            let prompt = t!("cli-prompt-tag");
+            let ids = select_notes(&target, &notes, out, &prompt, |_| true)?;
+            confirm(&target, &ids, &notes, &prompt)?;
+            let mut changed = Vec::new();
+            for id in &ids {
+                let note = notes.get_mut(*id).unwrap();
+                let before = note.tags.clone();
+                note.tags
+                    .retain(|tag| !remove.iter().any(|r| r.eq_ignore_ascii_case(tag)));
+                for tag in &add {
+                    if !note.has_tag(tag) {
+                        note.tags.push(tag.clone());
+                    }
+                }
+                if note.tags != before {
+                    note.touch();
+                    changed.push(*id);
+                }
+            }
+            save(&notes)?;
+            report(
+                out,
+                "tagged",
+                &changed,
+                t!("cli-tagged", count = changed.len()),
+            );
+        }
+        Command::Move { target, to } => {
+            let notebook = Some(to.trim().to_string()).filter(|name| !name.is_empty());
+            let prompt = t!("cli-prompt-move");
+            let ids = select_notes(&target, &notes, out, &prompt, |note| {
+                note.notebook != notebook
+            })?;
+            confirm(&target, &ids, &notes, &prompt)?;
+            let mut changed = Vec::new();
+            for id in &ids {
+                let note = notes.get_mut(*id).unwrap();
+                if note.notebook != notebook {
+                    note.notebook = notebook.clone();
+                    note.touch();
+                    changed.push(*id);
+                }
+            }
+            save(&notes)?;
+            let text = match &notebook {
+                Some(name) => t!("cli-moved", count = changed.len(), notebook = name),
+                None => t!("cli-moved-out", count = changed.len()),
+            };
+            report(out, "moved", &changed, text);
+        }
+        Command::Archive { target } => set_archived(&mut notes, &target, true, out)?,
+        Command::Unarchive { target } => set_archived(&mut notes, &target, false, out)?,
+        Command::Links { broken: true, .. } => print_broken_links(&notes, out),
+        Command::Links { id, .. } => {
+            let id = note_id(id, &notes, out, &t!("cli-prompt-links"), |_| true)?;
+            let links = notes.links_from(id).ok_or_else(|| note_not_found(id))?;
+            if out.machine() {
+                let records: Vec<_> = links
+                    .iter()
+                    .map(|(target, to)| LinkRecord::new(id, target, *to))
+                    .collect();
+                out.records(&records);
+                return Ok(());
+            }
+            if links.is_empty() {
+                out.line(t!("cli-no-links", id = id));
+                return Ok(());
+            }
+            out.line(format!("\n{}", t!("cli-links-header", id = id)));
+            for (target, resolved) in links {
+                match resolved.and_then(|to| notes.get(to)) {
+                    Some(to) => out.text(format!("  {} -> [{}] {}", target, to.id, to.title())),
+                    None => {
+                        let icon = if out.plain { "" } else { "❌ " };
+                        out.text(format!("  {} -> {}{}", target, icon, t!("cli-link-broken")))
+                    }
+                }
+            }
+        }
+        Command::Backlinks { id } => {
+            let id = note_id(id, &notes, out, &t!("cli-prompt-backlinks"), |_| true)?;
+            notes.get(id).ok_or_else(|| note_not_found(id))?;
+            let backlinks: Vec<&Note> = notes
+                .backlinks(id)
+                .iter()
+                .filter_map(|from| notes.get(*from))
+                .collect();
+            if out.machine() {
+                let records: Vec<_> = backlinks
+                    .iter()
+                    .map(|note| NoteRecord::from(*note))
+                    .collect();
+                out.records(&records);
+                return Ok(());
+            }
+            if backlinks.is_empty() {
+                out.line(t!("cli-no-backlinks", id = id));
+                return Ok(());
+            }
+            out.line(format!("\n{}", t!("cli-backlinks-header", id = id)));
+            for from in backlinks {
+                out.text(format!("  [{}] {}", from.id, from.title()));
+            }
+        }
+        Command::Attach { id, file } => {
+            let bytes = std::fs::read(&file).map_err(|e| {
+                CliError::Failed(t!(
+                    "cli-read-failed",
+                    path = file.display().to_string(),
+                    error = e.to_string()
+                ))
+            })?;
+            let attachment = attachments::attach(&mut notes, id, &file.to_string_lossy(), &bytes)
+                .map_err(|e| CliError::Failed(t!("cli-attach-failed", error = e.to_string())))?
+                .ok_or_else(|| note_not_found(id))?;
+            save(&notes)?;
+            out.record(&attachment);
+            out.status(
+                "attached",
+                Some(id),
+                t!(
+                    "cli-attached",
+                    name = &attachment.name,
+                    size = t!("bytes", count = attachment.size),
+                    id = id,
+                ),
+            );
+        }
+        Command::Attachments { id } => {
+            let id = note_id(id, &notes, out, &t!("cli-prompt-attachments"), |note| {
+                !note.attachments.is_empty()
+            })?;
+            let note = notes.get(id).ok_or_else(|| note_not_found(id))?;
+            if out.machine() {
+                out.records(&note.attachments);
+                return Ok(());
+            }
+            if note.attachments.is_empty() {
+                out.line(t!("cli-no-attachments", id = id));
+                return Ok(());
+            }
+            out.line(format!("\n{}", t!("cli-attachments-header", id = id)));
+            for attachment in &note.attachments {
+                out.text(format!(
+                    "  {} ({}) {}",
+                    attachment.name,
+                    t!("bytes", count = attachment.size),
+                    attachment.hash.get(..12).unwrap_or(&attachment.hash)
+                ));
+            }
+        }
+        Command::Detach { id, name } => {
+            notes.get(id).ok_or_else(|| note_not_found(id))?;
+            let attachment = attachments::detach(&mut notes, id, &name).ok_or_else(|| {
+                CliError::NotFound(t!("cli-attachment-not-found", name = &name, id = id))
+            })?;
+            save(&notes)?;
+            out.record(&attachment);
+            out.status(
+                "detached",
+                Some(id),
+                t!("cli-detached", name = &attachment.name, id = id),
+            );
+        }
+        Command::Gc => {
+            let (removed, freed_bytes) = attachments::gc(&notes)
+                .map_err(|e| CliError::Failed(t!("cli-gc-failed", error = e.to_string())))?;
+            out.record(&GcRecord {
+                removed,
+                freed_bytes,
+            });
+            if removed == 0 {
+                out.info(t!("cli-gc-nothing"));
+            } else {
+                out.info(t!(
+                    "cli-gc-done",
+                    count = removed,
+                    size = t!("bytes", count = freed_bytes)
+                ));
+            }
+        }
+        Command::Encrypt => {
+            let encrypt_failed = |e: std::io::Error| {
+                CliError::Failed(t!("cli-encrypt-failed", error = e.to_string()))
+            };
+            // Isi note ada di notes_git/*.md dan di history git; enkripsi hanya untuk storage json
+            if git_store::is_git_backed() {
+                return Err(CliError::Invalid(t!("cli-encrypt-git")));
+            }
+            if notes::is_encrypted() {
+                // Attachment yang tertinggal dari `encrypt` sebelumnya yang gagal di tengah
+                let converted = attachments::convert_blobs(true).map_err(encrypt_failed)?;
+                if converted == 0 {
+                    return Err(CliError::Invalid(t!("cli-already-encrypted")));
+                }
+                out.info(t!("cli-attachments-encrypted", count = converted));
+                return Ok(());
+            }
+            crypto::read_new_passphrase()
+                .and_then(|passphrase| notes::encrypt_notebook(&notes, passphrase))
+                .map_err(encrypt_failed)?;
+            let converted = attachments::convert_blobs(true).map_err(encrypt_failed)?;
+            if converted > 0 {
+                out.info(t!("cli-attachments-encrypted", count = converted));
+            }
+            out.status(
+                "encrypted",
+                None,
+                t!("cli-encrypted", file = notes::ENCRYPTED_NOTES_FILE),
+            );
+        }
+        Command::Decrypt => {
+            if !notes::is_encrypted() {
+                return Err(CliError::Invalid(t!("cli-not-encrypted")));
+            }
+            let decrypt_failed = |e: std::io::Error| {
+                CliError::Failed(t!("cli-decrypt-failed", error = e.to_string()))
+            };
+            // Attachment dulu, selagi passphrase notebook masih dipakai
+            let converted = attachments::convert_blobs(false).map_err(decrypt_failed)?;
+            if converted > 0 {
+                out.info(t!("cli-attachments-decrypted", count = converted));
+            }
+            notes::decrypt_notebook(&notes).map_err(decrypt_failed)?;
+            out.status(
+                "decrypted",
+                None,
+                t!("cli-decrypted", file = notes::NOTES_FILE),
+            );
+        }
+        Command::Lock { id } => {
+            let id = note_id(id, &notes, out, &t!("cli-prompt-lock"), |note| {
+                !note.is_locked()
+            })?;
+            handle_locked_note(&mut notes, "lock", id, out)?
+        }
+        Command::Unlock { id } => {
+            let id = note_id(id, &notes, out, &t!("cli-prompt-unlock"), Note::is_locked)?;
+            handle_locked_note(&mut notes, "unlock", id, out)?
+        }
+        Command::Reveal { id } => {
+            let id = note_id(id, &notes, out, &t!("cli-prompt-reveal"), Note::is_locked)?;
+            handle_locked_note(&mut notes, "reveal", id, out)?
+        }
+        Command::Sync { url } => {
+            let report = sync::sync_with(&mut notes, &url)
+                .map_err(|e| CliError::Failed(t!("sync-failed", error = e.to_string())))?;
+            save(&notes)?;
+            out.record(&report);
+            out.status(
+                "synced",
+                None,
+                t!("sync-done", url = &url, report = report.to_string()),
+            );
+        }
+        Command::Conflicts => {
+            let conflicts = notes.conflicts();
+            if out.machine() {
+                let records: Vec<_> = conflicts
+                    .iter()
+                    .map(|note| NoteRecord::from(*note))
+                    .collect();
+                out.records(&records);
+                return Ok(());
+            }
+            if conflicts.is_empty() {
+                out.info(t!("cli-no-conflicts"));
+                return Ok(());
+            }
+            out.line(format!("\n{}", t!("cli-conflicts-header")));
+            for note in conflicts {
+                out.text(format!("  [{}] {}", note.id, note.title()));
+            }
+            out.info(format!("\n{}", t!("cli-conflicts-hint")));
+        }
+        Command::Resolve { id, choice } => {
+            let id = note_id(
+                id,
+                &notes,
+                out,
+                &t!("cli-prompt-resolve"),
+                Note::has_conflict,
+            )?;
+            resolve_conflict(&mut notes, id, &choice, out)?
+        }
+        Command::Storage { backend } => handle_storage(backend, &notes, out)?,
+        Command::Log { id } => {
+            if !git_store::is_git_backed() {
+                return Err(CliError::Invalid(t!("cli-log-needs-git")));
+            }
+            let entries = git_store::log(id, 50)
+                .map_err(|e| CliError::Failed(t!("cli-log-failed", error = e.to_string())))?;
+            print_log(&entries, out);
+        }
+        Command::Template(_)
+        | Command::Schedule(_)
+        | Command::Completions { .. }
+        | Command::Man => {
+            unreachable!("handled before the notebook is loaded")
+        }
     }
+    Ok(())
 }
 
-fn load_notes() -> Notes {
-    match fs::read_to_string("notes.json") {
-        Ok(content) => {
-            serde_json::from_str(&content).unwrap_or_else(|_| Notes::new())
+fn print_list(notes: &Notes, query: &Query, out: &Output) {
+    let listed = sorted_for_list(notes, query);
+    if out.machine() {
+        let records: Vec<_> = listed.into_iter().map(NoteRecord::from).collect();
+        out.records(&records);
+        return;
+    }
+    if listed.is_empty() {
+        out.line(t!("cli-list-empty"));
+        return;
+    }
+    out.line(format!("\n{}", t!("cli-list-header")));
+    out.line("=".repeat(50));
+    for note in listed {
+        let mut marker = String::new();
+        if note.pinned {
+            marker.push_str(&if out.plain {
+                format!(" ({})", t!("marker-pinned"))
+            } else {
+                " 📌".to_string()
+            });
+        }
+        if note.has_conflict() {
+            marker.push_str(&if out.plain {
+                format!(" ({})", t!("marker-conflict"))
+            } else {
+                format!(" ⚠️ {}", t!("marker-conflict"))
+            });
+        }
+        out.text(format!("[{}]{} {}", note.id, marker, out.content(note)));
+        out.line(format!(
+            "    {}",
+            t!(
+                "label-created",
+                date = i18n::format_timestamp(&note.created_at)
+            )
+        ));
+        let mut meta = Vec::new();
+        if let Some(notebook) = &note.notebook {
+            meta.push(t!("label-notebook", notebook = notebook));
+        }
+        if !note.tags.is_empty() {
+            let tags: Vec<String> = note.tags.iter().map(|tag| format!("#{}", tag)).collect();
+            meta.push(t!("label-tags", tags = tags.join(" ")));
+        }
+        if note.archived {
+            meta.push(t!("marker-archived"));
+        }
+        if !meta.is_empty() {
+            out.text(format!("    {}", meta.join("  ")));
         }
-        Err(_) => Notes::new(),
+        out.line("");
     }
 }
 
-fn save_notes(notes: &Notes) -> io::Result<()> {
-    let json = serde_json::to_string_pretty(notes)?;
-    fs::write("notes.json", json)?;
-    Ok(())
+/// Note yang cocok dengan query; yang di-pin duluan, sisanya tetap urut id
+fn sorted_for_list<'a>(notes: &'a Notes, query: &Query) -> Vec<&'a Note> {
+    let mut sorted: Vec<&Note> = notes
+        .notes
+        .iter()
+        .filter(|note| query.matches(note, true))
+        .collect();
+    sorted.sort_by_key(|note| !note.pinned);
+    sorted
 }
 
-fn main() {
-    let mut notes = load_notes();
-    let args: Vec<String> = std::env::args().collect();
+fn set_pinned(notes: &mut Notes, id: Option<usize>, pinned: bool, out: &Output) -> CliResult {
+    let (prompt, action) = if pinned {
+        (t!("cli-prompt-pin"), "pinned")
+    } else {
+        (t!("cli-prompt-unpin"), "unpinned")
+    };
+    let id = note_id(id, notes, out, &prompt, |note| note.pinned != pinned)?;
+    let note = notes.get_mut(id).ok_or_else(|| note_not_found(id))?;
+    if note.pinned == pinned {
+        out.status(
+            "unchanged",
+            Some(id),
+            t!(&format!("cli-already-{}", action), id = id),
+        );
+        return Ok(());
+    }
+    note.pinned = pinned;
+    note.touch();
+    save(notes)?;
+    out.status(action, Some(id), t!(&format!("cli-{}", action), id = id));
+    Ok(())
+}
 
-    if args.len() < 2 {
-        print_usage();
+fn print_log(entries: &[git_store::LogEntry], out: &Output) {
+    if out.machine() {
+        let records: Vec<_> = entries.iter().map(LogRecord::from).collect();
+        out.records(&records);
         return;
     }
+    if entries.is_empty() {
+        out.line(t!("cli-log-empty"));
+        return;
+    }
+    out.line(format!("\n{}", t!("cli-log-header")));
+    for entry in entries {
+        let mut lines = entry.message.lines();
+        out.text(format!(
+            "{} {} ({}) {}",
+            entry.id,
+            i18n::format_timestamp(&entry.time),
+            entry.author,
+            lines.next().unwrap_or("")
+        ));
+        for line in lines.filter(|line| !line.is_empty()) {
+            out.text(format!("         {}", line));
+        }
+    }
+}
+
+fn handle_locked_note(notes: &mut Notes, command: &str, id: usize, out: &Output) -> CliResult {
+    let note = notes.get_mut(id).ok_or_else(|| note_not_found(id))?;
+    if command == "lock" && note.is_locked() {
+        return Err(CliError::Invalid(t!("cli-already-locked", id = id)));
+    }
+    if command != "lock" && !note.is_locked() {
+        return Err(CliError::Invalid(t!("cli-not-locked", id = id)));
+    }
 
-    match args[1].as_str() {
-        "add" => {
-            if args.len() < 3 {
-                println!("Error: Please provide note content");
-                println!("Usage: {} add \"Your note here\"", args[0]);
-                return;
+    // Locked note punya passphrase sendiri, terpisah dari passphrase notebook
+    let passphrase = if command == "lock" {
+        rpassword::prompt_password(t!("prompt-note-passphrase")).and_then(|first| {
+            let second = rpassword::prompt_password(t!("prompt-repeat-passphrase"))?;
+            if first == second && !first.is_empty() {
+                Ok(first)
+            } else {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    t!("cli-passphrase-mismatch"),
+                ))
             }
-            let content = args[2..].join(" ");
-            notes.add(content);
-            if save_notes(&notes).is_ok() {
-                println!("✅ Note added successfully!");
+        })
+    } else {
+        rpassword::prompt_password(t!("prompt-note-passphrase"))
+    };
+    let passphrase = passphrase.map_err(|e| CliError::Invalid(e.to_string()))?;
+
+    let result = match command {
+        "lock" => note.lock(&passphrase),
+        "unlock" => note.unlock(&passphrase),
+        _ => {
+            let content = note.reveal(&passphrase).map_err(|e| {
+                CliError::Failed(t!("cli-reveal-failed", id = id, error = e.to_string()))
+            })?;
+            if out.machine() {
+                let mut record = NoteRecord::from(&*note);
+                record.content = Some(content);
+                out.record(&record);
             } else {
-                println!("❌ Error saving note");
-            }
-        }
-        "list" => {
-            notes.list();
-        }
-        "delete" => {
-            if args.len() < 3 {
-                println!("Error: Please provide note ID");
-                println!("Usage: {} delete <id>", args[0]);
-                return;
-            }
-            match args[2].parse::<usize>() {
-                Ok(id) => {
-                    if notes.delete(id) {
-                        if save_notes(&notes).is_ok() {
-                            println!("✅ Note {} deleted successfully!", id);
-                        } else {
-                            println!("❌ Error saving changes");
-                        }
-                    } else {
-                        println!("❌ Note with ID {} not found", id);
-                    }
-                }
-                Err(_) => {
-                    println!("Error: Invalid ID. Please provide a number");
-                }
+                out.text(format!("[{}] {}", id, content));
             }
+            return Ok(());
         }
-        "help" => {
-            print_usage();
+    };
+    result.map_err(|e| {
+        CliError::Failed(t!(
+            &format!("cli-{}-failed", command),
+            id = id,
+            error = e.to_string()
+        ))
+    })?;
+    notes.rebuild_links();
+    save(notes)?;
+    let status = if command == "lock" {
+        "locked"
+    } else {
+        "unlocked"
+    };
+    out.status(status, Some(id), t!(&format!("cli-{}", status), id = id));
+    Ok(())
+}
+
+fn print_conflict(note: &Note, out: &Output) {
+    let Some(conflict) = &note.conflict else {
+        return;
+    };
+    let section = |title: &str, text: &str| {
+        out.line(format!(
+            "\n── {} {}",
+            title,
+            "─".repeat(50usize.saturating_sub(title.chars().count()))
+        ));
+        out.text(text);
+    };
+    let unknown = t!("conflict-unknown");
+    section(
+        &t!("conflict-base"),
+        conflict.base.as_deref().unwrap_or(&unknown),
+    );
+    section(&t!("conflict-ours"), &conflict.ours);
+    let theirs = t!(
+        "conflict-theirs",
+        date = i18n::format_timestamp(&conflict.theirs_updated_at)
+    );
+    section(&theirs, &conflict.theirs);
+    if let Some(preview) = note.merge_preview() {
+        let title = if preview.clean {
+            t!("conflict-merged-clean")
+        } else {
+            t!("conflict-merged-markers")
+        };
+        section(&title, &preview.text);
+    }
+    out.line("");
+}
+
+fn resolve_conflict(
+    notes: &mut Notes,
+    id: usize,
+    choice: &ResolveChoice,
+    out: &Output,
+) -> CliResult {
+    let note = notes.get(id).ok_or_else(|| note_not_found(id))?;
+    let Some(conflict) = note.conflict.clone() else {
+        out.status("unchanged", Some(id), t!("cli-no-conflict", id = id));
+        return Ok(());
+    };
+    let preview = note.merge_preview().unwrap();
+
+    let choice = if choice.ours {
+        "o".to_string()
+    } else if choice.theirs {
+        "t".to_string()
+    } else if choice.merged {
+        "m".to_string()
+    } else if choice.edit {
+        "e".to_string()
+    } else if out.machine() {
+        return Err(CliError::Invalid(t!("cli-resolve-needs-choice")));
+    } else {
+        print_conflict(note, out);
+        // Prompt ke stderr seperti confirm(), supaya stdout tetap berisi data saja
+        eprint!("{} ", t!("cli-resolve-prompt"));
+        let mut input = String::new();
+        std::io::stdin()
+            .read_line(&mut input)
+            .map_err(|e| CliError::Failed(e.to_string()))?;
+        input.trim().to_string()
+    };
+
+    let content = match choice.as_str() {
+        "o" => conflict.ours,
+        "t" => conflict.theirs,
+        "m" if preview.clean => preview.text,
+        "m" => return Err(CliError::Invalid(t!("cli-resolve-not-clean"))),
+        "e" => {
+            let text = merge::edit_in_editor(&preview.text)
+                .map_err(|e| CliError::Failed(t!("error-editor", error = e.to_string())))?;
+            if merge::has_markers(&text) {
+                return Err(CliError::Invalid(t!("conflict-markers-left")));
+            }
+            text.trim_end_matches('\n').to_string()
         }
         _ => {
-            println!("Unknown command: {}", args[1]);
-            print_usage();
+            out.info(t!("cancelled"));
+            return Ok(());
         }
+    };
+
+    let note = notes.get_mut(id).unwrap();
+    note.resolve_conflict(content);
+    let record = NoteRecord::from(&*note);
+    notes.rebuild_links();
+    save(notes)?;
+    out.record(&record);
+    out.status("resolved", Some(id), t!("cli-resolved", id = id));
+    Ok(())
+}
+
+fn print_broken_links(notes: &Notes, out: &Output) {
+    let broken = notes.broken_links();
+    if out.machine() {
+        let records: Vec<_> = broken
+            .iter()
+            .map(|(from, target)| LinkRecord::new(*from, target, None))
+            .collect();
+        out.records(&records);
+        return;
+    }
+    if broken.is_empty() {
+        out.info(t!("cli-no-broken-links"));
+        return;
+    }
+    out.line(format!("\n{}", t!("cli-broken-links-header")));
+    for (from, target) in broken {
+        out.line(format!("  [{}] {}", from, target));
     }
 }
 
-fn print_usage() {
-    println!("📝 Note Manager - A simple CLI note-taking app");
-    println!("\nUsage:");
-    println!("  {} add \"Your note\"     - Add a new note", std::env::args().next().unwrap());
-    println!("  {} list                - List all notes", std::env::args().next().unwrap());
-    println!("  {} delete <id>         - Delete a note by ID", std::env::args().next().unwrap());
-    println!("  {} help                - Show this help message", std::env::args().next().unwrap());
+fn handle_storage(backend: Option<Backend>, notes: &Notes, out: &Output) -> CliResult {
+    let current = if git_store::is_git_backed() {
+        Backend::Git
+    } else {
+        Backend::Json
+    };
+    let name = |backend: Backend| backend.to_possible_value().unwrap().get_name().to_string();
+    match backend {
+        None if out.machine() => out.status("ok", None, name(current)),
+        None => out.line(t!("cli-storage-current", backend = name(current))),
+        Some(target) if target == current => out.status(
+            "unchanged",
+            None,
+            t!("cli-storage-unchanged", backend = name(current)),
+        ),
+        Some(Backend::Git) => {
+            if notes::is_encrypted() {
+                return Err(CliError::Invalid(t!("cli-storage-encrypted")));
+            }
+            git_store::init(notes).map_err(|e| {
+                CliError::Failed(t!("cli-storage-git-failed", error = e.to_string()))
+            })?;
+            // Simpan file lama sebagai backup, git storage yang dipakai mulai sekarang
+            let json = notes::data_path(notes::NOTES_FILE);
+            if json.exists() {
+                let _ = std::fs::rename(&json, json.with_extension("json.bak"));
+            }
+            out.status(
+                "switched",
+                None,
+                t!(
+                    "cli-storage-to-git",
+                    path = git_store::repo_dir().display().to_string()
+                ),
+            );
+        }
+        Some(Backend::Json) => {
+            let backup = git_store::backup_dir();
+            std::fs::rename(git_store::repo_dir(), &backup)
+                .and_then(|_| save_notes(notes))
+                .map_err(|e| {
+                    CliError::Failed(t!("cli-storage-switch-failed", error = e.to_string()))
+                })?;
+            out.status(
+                "switched",
+                None,
+                t!(
+                    "cli-storage-to-json",
+                    file = notes::NOTES_FILE,
+                    path = backup.display().to_string(),
+                ),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn handle_template(command: TemplateCommand, out: &Output) -> CliResult {
+    match command {
+        TemplateCommand::Add { name, content } => {
+            // Izinkan "\n" di argumen supaya template multi-baris bisa dibuat dari shell
+            let content = content.join(" ").replace("\\n", "\n");
+            templates::save_template(&name, &content).map_err(|e| {
+                CliError::Failed(t!("cli-template-save-failed", error = e.to_string()))
+            })?;
+            out.status("saved", None, t!("cli-template-saved", name = &name));
+        }
+        TemplateCommand::List => {
+            let names = templates::list_templates();
+            if out.machine() {
+                let records: Vec<_> = names
+                    .into_iter()
+                    .map(|name| TemplateRecord {
+                        name,
+                        content: None,
+                    })
+                    .collect();
+                out.records(&records);
+                return Ok(());
+            }
+            if names.is_empty() {
+                out.line(t!("cli-templates-empty"));
+                return Ok(());
+            }
+            out.line(format!("\n{}", t!("cli-templates-header")));
+            for name in names {
+                out.text(format!("  - {}", name));
+            }
+        }
+        TemplateCommand::Show { name } => {
+            let content = templates::load_template(&name).map_err(|e| {
+                CliError::NotFound(t!(
+                    "cli-template-not-found",
+                    name = &name,
+                    error = e.to_string()
+                ))
+            })?;
+            if out.machine() {
+                out.record(&TemplateRecord {
+                    name,
+                    content: Some(content),
+                });
+            } else {
+                // Isi template dicetak apa adanya, termasuk emoji yang ditulis user
+                out.text(content);
+            }
+        }
+    }
+    Ok(())
 }
 
+fn handle_schedule(command: ScheduleCommand, out: &Output) -> CliResult {
+    match command {
+        ScheduleCommand::Add {
+            template,
+            every,
+            weekday,
+        } => {
+            if templates::load_template(&template).is_err() {
+                return Err(CliError::NotFound(t!(
+                    "cli-schedule-no-template",
+                    name = &template
+                )));
+            }
+            let every = Recurrence::parse(&every, weekday.as_deref()).map_err(CliError::Invalid)?;
+            // Mulai dari kemarin supaya jadwal baru tidak membuat note untuk minggu lalu
+            let yesterday = chrono::Local::now().date_naive() - chrono::Duration::days(1);
+            let mut schedules =
+                templates::load_schedules().map_err(|e| CliError::Failed(e.to_string()))?;
+            schedules.push(Schedule {
+                template,
+                every,
+                last_run: Some(yesterday),
+            });
+            templates::save_schedules(&schedules).map_err(|e| {
+                CliError::Failed(t!("cli-schedule-save-failed", error = e.to_string()))
+            })?;
+            out.record(&ScheduleRecord::new(
+                schedules.len(),
+                schedules.last().unwrap(),
+            ));
+            out.status("added", None, t!("cli-schedule-added"));
+        }
+        ScheduleCommand::List => {
+            let schedules =
+                templates::load_schedules().map_err(|e| CliError::Failed(e.to_string()))?;
+            if out.machine() {
+                let records: Vec<_> = schedules
+                    .iter()
+                    .enumerate()
+                    .map(|(i, schedule)| ScheduleRecord::new(i + 1, schedule))
+                    .collect();
+                out.records(&records);
+                return Ok(());
+            }
+            if schedules.is_empty() {
+                out.line(t!("cli-schedules-empty"));
+                return Ok(());
+            }
+            out.line(format!("\n{}", t!("cli-schedules-header")));
+            for (i, schedule) in schedules.iter().enumerate() {
+                let last_run = schedule
+                    .last_run
+                    .map(i18n::format_date)
+                    .unwrap_or_else(|| t!("cli-schedule-never"));
+                let every = match schedule.every {
+                    Recurrence::Daily => t!("schedule-daily"),
+                    Recurrence::Weekly(day) => {
+                        t!(&format!("schedule-weekly-{}", day.num_days_from_monday()))
+                    }
+                };
+                out.text(format!(
+                    "[{}] {} ({}) - {}",
+                    i + 1,
+                    schedule.template,
+                    every,
+                    t!("cli-schedule-last-run", date = last_run)
+                ));
+            }
+        }
+        ScheduleCommand::Remove { number } => {
+            let mut schedules =
+                templates::load_schedules().map_err(|e| CliError::Failed(e.to_string()))?;
+            if number == 0 || number > schedules.len() {
+                return Err(CliError::NotFound(t!(
+                    "cli-schedule-not-found",
+                    number = number
+                )));
+            }
+            schedules.remove(number - 1);
+            templates::save_schedules(&schedules).map_err(|e| {
+                CliError::Failed(t!("cli-schedule-save-failed", error = e.to_string()))
+            })?;
+            out.status("removed", None, t!("cli-schedule-removed", number = number));
+        }
+    }
+    Ok(())
+}
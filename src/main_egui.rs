@@ -1,5 +1,9 @@
 // Contoh Note Manager dengan GUI menggunakan egui
 // Untuk menjalankan: cargo run --bin rust_app_egui
+// Bahasa UI dari RUST_APP_LANG atau LANG (lihat i18n.rs)
+
+#[macro_use]
+mod i18n;
 
 use eframe::egui;
 use serde::{Deserialize, Serialize};
@@ -76,7 +80,7 @@ impl eframe::App for NoteApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Top panel dengan title
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            ui.heading("📝 Note Manager");
+            ui.heading(t!("gui-title"));
             ui.separator();
         });
 
@@ -87,9 +91,9 @@ impl eframe::App for NoteApp {
 
                 // Input area untuk note baru
                 ui.horizontal(|ui| {
-                    ui.label("New Note:");
-                    ui.text_edit_singleline(&mut self.new_note_text);
-                    if ui.button("➕ Add").clicked() {
+                    ui.label(t!("gui-new-note"));
+                    ui.add(egui::TextEdit::singleline(&mut self.new_note_text).hint_text(t!("gui-placeholder")));
+                    if ui.button(t!("gui-add")).clicked() {
                         if !self.new_note_text.trim().is_empty() {
                             self.notes.add(self.new_note_text.clone());
                             self.new_note_text.clear();
@@ -104,9 +108,9 @@ impl eframe::App for NoteApp {
 
                 // List notes
                 if self.notes.notes.is_empty() {
-                    ui.label("No notes yet. Add one above! 👆");
+                    ui.label(t!("gui-empty"));
                 } else {
-                    ui.heading("Your Notes:");
+                    ui.heading(t!("gui-your-notes"));
                     ui.add_space(5.0);
 
                     egui::ScrollArea::vertical()
@@ -122,14 +126,17 @@ impl eframe::App for NoteApp {
                                                     .strong(),
                                             );
                                             ui.label(
-                                                egui::RichText::new(&note.created_at)
+                                                egui::RichText::new(t!(
+                                                    "gui-created",
+                                                    date = i18n::format_timestamp(&note.created_at)
+                                                ))
                                                     .size(10.0)
                                                     .weak(),
                                             );
                                         });
 
                                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                            if ui.button("🗑️ Delete").clicked() {
+                                            if ui.button(t!("gui-delete")).clicked() {
                                                 self.notes.delete(note.id);
                                                 let _ = save_notes(&self.notes);
                                             }
@@ -149,7 +156,7 @@ fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([400.0, 600.0])
-            .with_title("Note Manager - Rust GUI"),
+            .with_title(t!("gui-window-title")),
         ..Default::default()
     };
 
@@ -3,6 +3,11 @@
 //
 // Prerequisites:
 // sudo apt-get install libgtk-4-dev libadwaita-1-dev
+//
+// Bahasa UI dari RUST_APP_LANG atau LANG (lihat i18n.rs)
+
+#[macro_use]
+mod i18n;
 
 use gtk::prelude::*;
 use gtk::glib;
@@ -68,7 +73,7 @@ fn build_ui(app: &gtk::Application) {
     // Create main window
     let window = gtk::ApplicationWindow::builder()
         .application(app)
-        .title("📝 Note Manager")
+        .title(t!("gui-title"))
         .default_width(600)
         .default_height(700)
         .build();
@@ -94,12 +99,12 @@ fn build_ui(app: &gtk::Application) {
         .build();
 
     let entry = gtk::Entry::builder()
-        .placeholder_text("Tulis note baru di sini...")
+        .placeholder_text(t!("gui-placeholder"))
         .hexpand(true)
         .build();
 
     let add_button = gtk::Button::builder()
-        .label("➕ Add")
+        .label(t!("gui-add"))
         .css_classes(vec!["suggested-action"])
         .build();
 
@@ -132,7 +137,7 @@ fn build_ui(app: &gtk::Application) {
             let notes_guard = notes.lock().unwrap();
             if notes_guard.notes.is_empty() {
                 let empty_label = gtk::Label::builder()
-                    .text("No notes yet. Add one above! 👆")
+                    .text(t!("gui-empty"))
                     .css_classes(vec!["title-3"])
                     .margin_top(50)
                     .build();
@@ -162,7 +167,7 @@ fn build_ui(app: &gtk::Application) {
                         .build();
 
                     let date_label = gtk::Label::builder()
-                        .text(&format!("Created: {}", note.created_at))
+                        .text(t!("gui-created", date = i18n::format_timestamp(&note.created_at)))
                         .css_classes(vec!["caption"])
                         .halign(gtk::Align::Start)
                         .build();
@@ -173,7 +178,7 @@ fn build_ui(app: &gtk::Application) {
                         .build();
 
                     let delete_button = gtk::Button::builder()
-                        .label("🗑️ Delete")
+                        .label(t!("gui-delete"))
                         .css_classes(vec!["destructive-action"])
                         .build();
 
@@ -1,6 +1,10 @@
 // Object Detection CLI dengan Rust
 // Run: cargo run --bin rust_object_detection -- image photo.jpg
 
+// Harus pertama supaya macro t! bisa dipakai di semua module
+#[macro_use]
+mod i18n;
+
 mod object_detection;
 
 use std::env;
@@ -8,14 +12,19 @@ use object_detection::{ObjectDetector, Detection};
 
 fn print_detections(detections: &[Detection]) {
     if detections.is_empty() {
-        println!("No objects detected.");
+        println!("{}", t!("detect-none"));
         return;
     }
 
     println!("\n{}", "=".repeat(60));
-    println!("Detected {} object(s):", detections.len());
+    println!("{}", t!("detect-count", count = detections.len()));
     println!("{}", "=".repeat(60));
-    println!("{:<20} {:<15} {:<20}", "Class", "Confidence", "Bounding Box");
+    println!(
+        "{:<20} {:<15} {:<20}",
+        t!("detect-column-class"),
+        t!("detect-column-confidence"),
+        t!("detect-column-bbox")
+    );
     println!("{}", "-".repeat(60));
 
     for det in detections {
@@ -37,10 +46,10 @@ fn main() {
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 2 {
-        println!("Usage:");
+        println!("{}", t!("detect-usage"));
         println!("  cargo run --bin rust_object_detection -- image <input_image> [output_json]");
         println!("  cargo run --bin rust_object_detection -- webcam");
-        println!("\nExample:");
+        println!("\n{}", t!("detect-example"));
         println!("  cargo run --bin rust_object_detection -- image photo.jpg results.json");
         println!("  cargo run --bin rust_object_detection -- webcam");
         std::process::exit(1);
@@ -54,8 +63,8 @@ fn main() {
     match mode.as_str() {
         "image" => {
             if args.len() < 3 {
-                println!("Error: Please provide image path");
-                println!("Usage: cargo run --bin rust_object_detection -- image <input_image> [output_json]");
+                println!("{}", t!("detect-image-required"));
+                println!("{} cargo run --bin rust_object_detection -- image <input_image> [output_json]", t!("detect-usage"));
                 std::process::exit(1);
             }
 
@@ -70,12 +79,12 @@ fn main() {
                     // Save results if output path specified
                     if let Some(output) = output_path {
                         if let Err(e) = detector.save_results(&detections, output) {
-                            eprintln!("Error saving results: {}", e);
+                            eprintln!("{}", t!("detect-save-failed", error = e));
                         }
                     }
                 }
                 Err(e) => {
-                    eprintln!("Error: {}", e);
+                    eprintln!("{}", t!("detect-error", error = e));
                     std::process::exit(1);
                 }
             }
@@ -83,16 +92,13 @@ fn main() {
         "webcam" => {
             #[cfg(feature = "webcam")]
             {
-                println!("Starting webcam detection...");
-                println!("Note: Requires opencv feature enabled in Cargo.toml");
+                println!("{}", t!("detect-webcam-starting"));
+                println!("{}", t!("detect-webcam-requires"));
                 match detector.detect_webcam(0.25) {
-                    Ok(_) => println!("Webcam detection completed."),
+                    Ok(_) => println!("{}", t!("detect-webcam-done")),
                     Err(e) => {
-                        eprintln!("Error: {}", e);
-                        eprintln!("\nMake sure:");
-                        eprintln!("  1. Webcam is connected");
-                        eprintln!("  2. opencv feature is enabled in Cargo.toml");
-                        eprintln!("  3. Required system libraries are installed:");
+                        eprintln!("{}", t!("detect-error", error = e));
+                        eprintln!("\n{}", t!("detect-webcam-checklist"));
                         eprintln!("     Ubuntu/Debian: sudo apt-get install libopencv-dev");
                         std::process::exit(1);
                     }
@@ -100,18 +106,18 @@ fn main() {
             }
             #[cfg(not(feature = "webcam"))]
             {
-                eprintln!("Error: Webcam feature not enabled!");
-                eprintln!("\nTo enable webcam support:");
-                eprintln!("1. Uncomment opencv dependency in Cargo.toml:");
+                eprintln!("{}", t!("detect-webcam-disabled"));
+                eprintln!("\n{}", t!("detect-webcam-enable"));
+                eprintln!("{}", t!("detect-webcam-enable-step1"));
                 eprintln!("   opencv = {{ version = \"0.88\", features = [\"opencv-4\", \"videoio\", \"highgui\"] }}");
-                eprintln!("\n2. Run with feature flag:");
+                eprintln!("\n{}", t!("detect-webcam-enable-step2"));
                 eprintln!("   cargo run --bin rust_object_detection --features webcam -- webcam");
                 std::process::exit(1);
             }
         }
         _ => {
-            println!("Error: Unknown mode '{}'", mode);
-            println!("Available modes: image, webcam");
+            println!("{}", t!("detect-unknown-mode", mode = mode));
+            println!("{}", t!("detect-modes"));
             std::process::exit(1);
         }
     }
@@ -2,140 +2,3341 @@
 // Untuk menjalankan: cargo run --bin rust_app_web
 // Buka browser ke: http://localhost:3000
 
+// Harus pertama supaya macro t! bisa dipakai di semua module
+#[macro_use]
+mod i18n;
+
+mod accounts;
+mod attachments;
+mod audit;
+mod collab;
+mod crypto;
+mod events;
+mod git_store;
+mod links;
+mod merge;
+mod notes;
+mod rate_limit;
+mod select;
+mod server_config;
+mod shares;
+mod shutdown;
+mod storage;
+mod sync;
+mod templates;
+
+use accounts::{Accounts, User, SESSION_TTL};
 use axum::{
-    extract::Path,
-    http::StatusCode,
-    response::{Html, Json},
-    routing::{delete, get, post},
+    async_trait,
+    extract::{
+        multipart::{MultipartError, MultipartRejection},
+        rejection::{BytesRejection, JsonRejection, PathRejection, QueryRejection},
+        ws::{rejection::WebSocketUpgradeRejection, Message as WsMessage, WebSocket, WebSocketUpgrade},
+        ConnectInfo, DefaultBodyLimit, FromRef, FromRequest, FromRequestParts, Multipart, Path, Query, Request,
+        State,
+    },
+    http::{header, request::Parts, HeaderMap, Method, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Json, Response,
+    },
+    routing::{get, post, MethodRouter},
     Router,
 };
-use serde::{Deserialize, Serialize};
-use std::fs;
-use std::sync::{Arc, Mutex};
+use audit::Action;
+use clap::{Parser, Subcommand};
+use collab::{ClientMessage, Collab, ServerMessage};
+use events::{Events, NoteEvent};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::service::TowerToHyperService;
+use links::LinkTarget;
+use notes::{save_notes, Attachment, Note, Notes};
+use rate_limit::RateLimiter;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use server_config::{ConfigError, Listen, ServerConfig, TrustedProxies};
+use shares::{Access, Permissions, Shares, Target};
+use shutdown::Shutdown;
+use storage::{Saved, Storage};
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::TlsAcceptor;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
+use tower_http::add_extension::AddExtension;
+use tower_http::catch_panic::CatchPanicLayer;
 use tower_http::services::ServeDir;
-use tower_http::cors::CorsLayer;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{ContentBuilder, Ref, ResponseBuilder};
+use utoipa::{IntoParams, Modify, OpenApi, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
+
+const MAX_ATTACHMENT_BYTES: usize = 25 * 1024 * 1024;
+/// Batas body JSON biasa; upload attachment dan sync punya batas sendiri
+const MAX_JSON_BYTES: usize = 1024 * 1024;
+/// Batas isi satu note (byte UTF-8)
+const MAX_NOTE_BYTES: usize = 256 * 1024;
+const MAX_TAGS: usize = 50;
+const MAX_NOTEBOOK_CHARS: usize = 100;
+const MAX_TOKEN_NAME_CHARS: usize = 64;
+/// Jumlah entry audit per request kalau `limit` tidak diisi, dan batas atasnya
+const DEFAULT_AUDIT_LIMIT: usize = 100;
+const MAX_AUDIT_LIMIT: usize = 1000;
+const SESSION_COOKIE: &str = "session";
+/// Login gagal per menit untuk satu username, terlepas dari alamat client
+const LOGIN_FAILURES_PER_MINUTE: u32 = 5;
+/// Jeda sebelum isi edit bersama disimpan ke notes, supaya tidak menulis per ketukan
+const LIVE_SAVE_DELAY: Duration = Duration::from_secs(2);
+/// Waktu untuk request yang masih berjalan saat shutdown sebelum koneksinya diputus
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Web server and JSON API for the note manager
+#[derive(Parser)]
+#[command(name = "rust_app_web", version)]
+struct Args {
+    /// Language of messages and dates (default: from LANG / LC_ALL)
+    #[arg(long, global = true, value_enum, env = i18n::LANG_ENV)]
+    lang: Option<i18n::Lang>,
+    /// Server config file (TOML) [default: <data dir>/server.toml]
+    #[arg(long, env = server_config::CONFIG_ENV, value_name = "PATH")]
+    config: Option<PathBuf>,
+    #[command(flatten)]
+    server: server_config::Settings,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage user accounts
+    #[command(subcommand)]
+    User(UserCommand),
+    /// Manage API tokens for scripts
+    #[command(subcommand)]
+    Token(TokenCommand),
+}
+
+#[derive(Subcommand)]
+enum UserCommand {
+    /// Create a user (the first user is always an admin); password from RUST_APP_PASSWORD or a prompt
+    Add {
+        name: String,
+        /// Owns the notes created with the CLI/TUI and may sync the whole notebook
+        #[arg(long)]
+        admin: bool,
+    },
+    /// List users
+    List,
+    /// Delete a user and their tokens; their notes stay in the notebook
+    Remove { name: String },
+    /// Change a user's password and sign them out everywhere
+    Passwd { name: String },
+}
+
+#[derive(Subcommand)]
+enum TokenCommand {
+    /// Create an API token and print it (it is shown only once)
+    Create {
+        user: String,
+        /// Label to recognize the token later
+        #[arg(long, default_value = "cli")]
+        name: String,
+    },
+    /// List API tokens
+    List { user: Option<String> },
+    /// Revoke an API token by id
+    Revoke { id: String },
+}
+
+/// State bersama semua handler; handler mengambil bagian yang dibutuhkan lewat FromRef
+#[derive(Clone)]
+struct AppState {
+    notes: Arc<Mutex<Notes>>,
+    accounts: Arc<Mutex<Accounts>>,
+    shares: Arc<Mutex<Shares>>,
+    events: Events,
+    collab: Collab,
+    storage: Storage,
+    shutdown: Shutdown,
+    cookies: Cookies,
+    /// Login gagal per username
+    logins: RateLimiter<String>,
+}
+
+/// Atribut cookie session dari config server
+#[derive(Clone, Copy, Default)]
+struct Cookies {
+    secure: bool,
+}
+
+impl FromRef<AppState> for Arc<Mutex<Notes>> {
+    fn from_ref(state: &AppState) -> Self {
+        Arc::clone(&state.notes)
+    }
+}
+
+impl FromRef<AppState> for Arc<Mutex<Accounts>> {
+    fn from_ref(state: &AppState) -> Self {
+        Arc::clone(&state.accounts)
+    }
+}
+
+impl FromRef<AppState> for Arc<Mutex<Shares>> {
+    fn from_ref(state: &AppState) -> Self {
+        Arc::clone(&state.shares)
+    }
+}
+
+impl FromRef<AppState> for Events {
+    fn from_ref(state: &AppState) -> Self {
+        state.events.clone()
+    }
+}
+
+impl FromRef<AppState> for Collab {
+    fn from_ref(state: &AppState) -> Self {
+        state.collab.clone()
+    }
+}
+
+impl FromRef<AppState> for Storage {
+    fn from_ref(state: &AppState) -> Self {
+        state.storage.clone()
+    }
+}
+
+impl FromRef<AppState> for Shutdown {
+    fn from_ref(state: &AppState) -> Self {
+        state.shutdown.clone()
+    }
+}
+
+impl FromRef<AppState> for Cookies {
+    fn from_ref(state: &AppState) -> Self {
+        state.cookies
+    }
+}
+
+impl FromRef<AppState> for RateLimiter<String> {
+    fn from_ref(state: &AppState) -> Self {
+        state.logins.clone()
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct CreateNoteRequest {
+    content: String,
+}
+
+/// Body PUT: semua field yang bisa diubah; yang tidak dikirim kembali ke default
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+struct UpdateNoteRequest {
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    notebook: Option<String>,
+    #[serde(default)]
+    pinned: bool,
+    #[serde(default)]
+    archived: bool,
+}
+
+/// Body PATCH: hanya field yang dikirim yang diubah; `"notebook": null` mengeluarkan note dari notebook
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+struct PatchNoteRequest {
+    content: Option<String>,
+    tags: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    notebook: Option<Option<String>>,
+    pinned: Option<bool>,
+    archived: Option<bool>,
+}
+
+/// Bedakan field yang tidak dikirim (None) dari field yang dikirim sebagai null (Some(None))
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Perubahan yang sudah divalidasi, siap diterapkan ke note
+struct NoteChanges {
+    content: Option<String>,
+    tags: Option<Vec<String>>,
+    notebook: Option<Option<String>>,
+    pinned: Option<bool>,
+    archived: Option<bool>,
+}
+
+impl From<UpdateNoteRequest> for PatchNoteRequest {
+    fn from(request: UpdateNoteRequest) -> Self {
+        PatchNoteRequest {
+            content: Some(request.content),
+            tags: Some(request.tags),
+            notebook: Some(request.notebook),
+            pinned: Some(request.pinned),
+            archived: Some(request.archived),
+        }
+    }
+}
+
+/// Form multipart untuk upload attachment; hanya dipakai di spec OpenAPI
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct AttachmentUpload {
+    /// Satu field `file` per file yang di-upload
+    #[schema(value_type = Vec<String>, format = Binary)]
+    file: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct RevealRequest {
+    passphrase: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct RevealResponse {
+    content: String,
+}
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct Note {
+#[derive(Debug, Serialize, ToSchema)]
+struct ConflictView {
     id: usize,
+    base: Option<String>,
+    ours: String,
+    theirs: String,
+    theirs_updated_at: String,
+    merged: String,
+    clean: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct LoginRequest {
+    username: String,
+    password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct UserInfo {
+    name: String,
+    admin: bool,
+}
+
+impl From<&User> for UserInfo {
+    fn from(user: &User) -> Self {
+        UserInfo {
+            name: user.name.clone(),
+            admin: user.admin,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct CreateTokenRequest {
+    name: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct TokenInfo {
+    id: String,
+    name: String,
+    created_at: String,
+}
+
+impl From<&accounts::ApiToken> for TokenInfo {
+    fn from(token: &accounts::ApiToken) -> Self {
+        TokenInfo {
+            id: token.id.clone(),
+            name: token.name.clone(),
+            created_at: token.created_at.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct NewToken {
+    id: String,
+    name: String,
+    created_at: String,
+    /// Kirim sebagai `Authorization: Bearer <token>`; hanya ditampilkan sekali
+    token: String,
+}
+
+/// Tepat satu dari `note` atau `notebook`
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+struct ShareRequest {
+    note: Option<usize>,
+    notebook: Option<String>,
+    user: String,
+    access: Access,
+}
+
+/// Tepat satu dari `note` atau `notebook`
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+struct ShareLinkRequest {
+    note: Option<usize>,
+    notebook: Option<String>,
+    /// Tanpa nilai ini link berlaku sampai dihapus
+    expires_in_days: Option<u32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct ShareInfo {
+    id: String,
+    owner: String,
+    user: String,
+    /// None kalau note-nya sudah tidak ada di server ini
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notebook: Option<String>,
+    access: Access,
+    created_at: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct ShareLinkInfo {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notebook: Option<String>,
+    created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<String>,
+    expired: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct NewShareLink {
+    #[serde(flatten)]
+    link: ShareLinkInfo,
+    /// Hanya ditampilkan sekali
+    token: String,
+    /// Halaman web read-only untuk link ini
+    url: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct ShareList {
+    /// Grant yang dibuat user ini dan yang diberikan kepadanya
+    shares: Vec<ShareInfo>,
+    links: Vec<ShareLinkInfo>,
+}
+
+/// Note yang dilihat lewat link publik: tanpa data sync, owner, atau lampiran
+#[derive(Debug, Serialize, ToSchema)]
+struct SharedNote {
     content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notebook: Option<String>,
     created_at: String,
+    updated_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Notes {
-    notes: Vec<Note>,
-    next_id: usize,
+impl From<&Note> for SharedNote {
+    fn from(note: &Note) -> Self {
+        SharedNote {
+            content: note.content.clone(),
+            tags: note.tags.clone(),
+            notebook: note.notebook.clone(),
+            created_at: note.created_at.clone(),
+            updated_at: note.updated_at.clone(),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct CreateNoteRequest {
+#[derive(Debug, Deserialize, ToSchema)]
+struct ResolveRequest {
     content: String,
 }
 
-impl Notes {
-    fn new() -> Self {
-        Notes {
-            notes: Vec::new(),
-            next_id: 1,
+#[derive(Debug, Serialize, ToSchema)]
+struct NoteLink {
+    target: LinkTarget,
+    note_id: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct NoteLinks {
+    links: Vec<NoteLink>,
+    backlinks: Vec<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct BrokenLink {
+    from: usize,
+    target: LinkTarget,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CatalogQuery {
+    /// Kode bahasa (`en`, `id`); default dari Accept-Language, lalu bahasa server
+    lang: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct AuditQuery {
+    /// Only changes by this user
+    user: Option<String>,
+    /// Only changes to the note with this ID
+    note: Option<usize>,
+    /// Only changes to the note with this UID (also finds deleted notes)
+    uid: Option<String>,
+    #[param(inline)]
+    action: Option<Action>,
+    /// Only changes at or after this local time, `YYYY-MM-DD` or `YYYY-MM-DD HH:MM:SS`
+    since: Option<String>,
+    /// Maximum number of entries (default 100, at most 1000)
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct Catalog {
+    lang: &'static str,
+    /// Pesan "web-*"; argumen ditulis `{$name}` dan diisi oleh halaman
+    messages: BTreeMap<String, String>,
+}
+
+/// Error response sesuai RFC 7807 (`application/problem+json`).
+/// `title` selalu reason phrase HTTP, `detail` dalam bahasa server.
+#[derive(Debug, Serialize, ToSchema)]
+struct Problem {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    title: &'static str,
+    status: u16,
+    detail: String,
+    /// Field request yang tidak valid (extension member dari contoh RFC 7807)
+    #[serde(rename = "invalid-params", skip_serializing_if = "Vec::is_empty")]
+    invalid_params: Vec<InvalidParam>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct InvalidParam {
+    name: &'static str,
+    reason: String,
+}
+
+impl Problem {
+    fn new(status: StatusCode, detail: impl Into<String>) -> Self {
+        Problem {
+            kind: "about:blank",
+            title: status.canonical_reason().unwrap_or("Error"),
+            status: status.as_u16(),
+            detail: detail.into(),
+            invalid_params: Vec::new(),
+        }
+    }
+
+    fn not_found(id: usize) -> Self {
+        Problem::new(StatusCode::NOT_FOUND, t!("note-not-found", id = id))
+    }
+
+    fn invalid(invalid_params: Vec<InvalidParam>) -> Self {
+        Problem {
+            invalid_params,
+            ..Problem::new(StatusCode::UNPROCESSABLE_ENTITY, t!("api-invalid-request"))
+        }
+    }
+
+    fn saving(error: std::io::Error) -> Self {
+        Problem::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            t!("error-saving", error = error.to_string()),
+        )
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (
+            status,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(self),
+        )
+            .into_response()
+    }
+}
+
+/// Spec OpenAPI 3 untuk semua route di `api_routes`, dibangun dari anotasi handler
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Note Manager API", description = "JSON API of rust_app_web. Errors are application/problem+json (RFC 7807)."),
+    paths(
+        get_notes,
+        create_note,
+        get_note,
+        replace_note,
+        patch_note,
+        delete_note,
+        reveal_note,
+        get_note_links,
+        get_broken_links,
+        upload_attachments,
+        download_attachment,
+        delete_attachment,
+        sync_notes,
+        get_sync_blob,
+        put_sync_blob,
+        get_conflicts,
+        resolve_conflict,
+        get_catalog,
+        login,
+        logout,
+        get_me,
+        list_tokens,
+        create_token,
+        revoke_token,
+        list_shares,
+        create_share,
+        delete_share,
+        create_share_link,
+        delete_share_link,
+        get_shared,
+        get_audit,
+        note_events,
+        collab_note,
+    ),
+    modifiers(&Security),
+    security(("bearer" = []), ("session" = [])),
+    tags(
+        (name = "notes"),
+        (name = "links", description = "[[wiki links]] between notes"),
+        (name = "attachments"),
+        (name = "conflicts", description = "Conflicting edits from sync"),
+        (name = "sync", description = "Multi-device sync"),
+        (name = "i18n", description = "Translations for the web page"),
+        (name = "auth", description = "Sessions for the browser, bearer tokens for scripts"),
+        (name = "sharing", description = "Share notes and notebooks with other users or through public links"),
+        (name = "events", description = "Live note changes as Server-Sent Events"),
+        (name = "collab", description = "Editing one note together over WebSocket"),
+        (name = "audit", description = "Append-only log of note changes made through the API"),
+    )
+)]
+struct ApiDoc;
+
+/// Skema login di spec, plus response 401 untuk setiap operasi yang butuh login
+struct Security;
+
+impl Modify for Security {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+        components.add_security_scheme(
+            "session",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new(SESSION_COOKIE))),
+        );
+        let unauthorized = ResponseBuilder::new()
+            .description("Not signed in")
+            .content(
+                "application/problem+json",
+                ContentBuilder::new().schema(Some(Ref::from_schema_name("Problem"))).build(),
+            )
+            .build();
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [&mut item.get, &mut item.post, &mut item.put, &mut item.patch, &mut item.delete];
+            // Operasi publik menimpa `security` global dengan requirement kosong
+            for operation in operations.into_iter().flatten().filter(|op| op.security.is_none()) {
+                operation.responses.responses.insert("401".to_string(), unauthorized.clone().into());
+            }
+        }
+    }
+}
+
+// Rejection bawaan axum berupa teks biasa; ubah jadi problem+json dengan status yang sama
+macro_rules! problem_from_rejection {
+    ($($rejection:ty),+) => {
+        $(impl From<$rejection> for Problem {
+            fn from(rejection: $rejection) -> Self {
+                Problem::new(rejection.status(), rejection.body_text())
+            }
+        })+
+    };
+}
+
+problem_from_rejection!(
+    BytesRejection,
+    JsonRejection,
+    PathRejection,
+    MultipartRejection,
+    MultipartError,
+    QueryRejection,
+    WebSocketUpgradeRejection
+);
+
+/// `Json<T>` yang menolak body rusak atau terlalu besar dengan problem+json
+struct ApiJson<T>(T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Problem;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+        Ok(ApiJson(value))
+    }
+}
+
+/// `Path<T>` yang menolak id tidak valid (`/api/notes/abc`) dengan problem+json
+struct ApiPath<T>(T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = Problem;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+        Ok(ApiPath(value))
+    }
+}
+
+/// `Query<T>` yang menolak parameter tidak valid (`?limit=abc`) dengan problem+json
+struct ApiQuery<T>(T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Problem;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        Ok(ApiQuery(value))
+    }
+}
+
+/// Kunci state bersama. Mutex jadi poisoned kalau ada handler yang panic di tengah
+/// mutasi; data di memory mungkin setengah jadi, jadi muat ulang dari disk
+/// (versi terakhir yang tersimpan) daripada ikut panic di setiap request berikutnya.
+fn lock_or_reload<T>(
+    state: &Mutex<T>,
+    reload: impl FnOnce() -> Result<T, String>,
+) -> Result<MutexGuard<'_, T>, Problem> {
+    match state.lock() {
+        Ok(guard) => Ok(guard),
+        Err(poisoned) => {
+            let mut guard = poisoned.into_inner();
+            *guard = reload().map_err(|error| {
+                Problem::new(StatusCode::SERVICE_UNAVAILABLE, t!("api-state-unavailable", error = error))
+            })?;
+            state.clear_poison();
+            eprintln!("⚠️  {}", t!("server-state-recovered"));
+            Ok(guard)
         }
     }
+}
+
+fn lock_notes(state: &Mutex<Notes>) -> Result<MutexGuard<'_, Notes>, Problem> {
+    lock_or_reload(state, || notes::try_load_notes().map_err(|e| e.to_string()))
+}
+
+/// Session hanya ada di memory, jadi setelah recovery semua user harus login ulang
+fn lock_accounts(state: &Mutex<Accounts>) -> Result<MutexGuard<'_, Accounts>, Problem> {
+    lock_or_reload(state, || Accounts::load().map_err(|e| e.to_string()))
+}
+
+fn lock_shares(state: &Mutex<Shares>) -> Result<MutexGuard<'_, Shares>, Problem> {
+    lock_or_reload(state, || Shares::load().map_err(|e| e.to_string()))
+}
+
+// Snapshot diantrikan ke thread penulis selagi lock masih dipegang (lihat storage.rs),
+// jadi urutan tulis sama dengan urutan perubahan. Guard harus keluar scope sebelum
+// `Saved` di-await.
+fn queue_notes(storage: &Storage, notes: &Notes) -> Saved {
+    let snapshot = notes.clone();
+    storage.save(move || save_notes(&snapshot))
+}
+
+fn queue_accounts(storage: &Storage, accounts: &Accounts) -> Saved {
+    let snapshot = accounts.persisted();
+    storage.save(move || snapshot.save())
+}
+
+fn queue_shares(storage: &Storage, shares: &Shares) -> Saved {
+    let snapshot = shares.clone();
+    storage.save(move || snapshot.save())
+}
+
+/// Audit log ditulis setelah penyimpanan yang diantrikan sebelumnya. Gagal menulis
+/// audit tidak membatalkan perubahan yang sudah tersimpan, hanya dilaporkan di log server.
+fn queue_audit(storage: &Storage, entries: Vec<audit::Entry>) {
+    storage.run(move || {
+        if let Err(e) = audit::append(&entries) {
+            eprintln!("❌ {}", t!("audit-write-failed", error = e.to_string()));
+        }
+    });
+}
 
-    #[allow(dead_code)]
-    fn add(&mut self, content: String) {
-        let note = Note {
-            id: self.next_id,
-            content,
-            created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+/// Kerjakan I/O file atau hitungan berat (KDF) di thread blocking, bukan di executor
+async fn blocking<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Result<T, Problem> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|_| Problem::new(StatusCode::INTERNAL_SERVER_ERROR, t!("api-internal-error")))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    value.strip_prefix("Bearer ").map(str::trim)
+}
+
+fn session_secret(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|cookie| cookie.trim().strip_prefix(SESSION_COOKIE)?.strip_prefix('='))
+}
+
+/// Cookie session; HttpOnly supaya tidak terbaca JavaScript, SameSite=Strict
+/// supaya tidak ikut terkirim dari situs lain (CSRF), Secure kalau server dipakai lewat HTTPS
+fn session_cookie(secret: &str, max_age: u64, cookies: Cookies) -> String {
+    let secure = if cookies.secure { "; Secure" } else { "" };
+    format!(
+        "{}={}; HttpOnly; SameSite=Strict; Path=/; Max-Age={}{}",
+        SESSION_COOKIE, secret, max_age, secure
+    )
+}
+
+/// User yang sedang login: dari `Authorization: Bearer <token>` (script)
+/// atau dari cookie session (browser)
+struct CurrentUser(User);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CurrentUser
+where
+    Arc<Mutex<Accounts>>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let accounts = Arc::<Mutex<Accounts>>::from_ref(state);
+        let accounts = lock_accounts(&accounts).map_err(IntoResponse::into_response)?;
+        let user = match bearer_token(&parts.headers) {
+            Some(token) => accounts.token_user(token),
+            None => session_secret(&parts.headers).and_then(|secret| accounts.session_user(secret)),
         };
-        self.notes.push(note);
-        self.next_id += 1;
+        user.cloned().map(CurrentUser).ok_or_else(|| {
+            (
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+                Problem::new(StatusCode::UNAUTHORIZED, t!("api-unauthorized")),
+            )
+                .into_response()
+        })
+    }
+}
+
+/// User yang sedang login beserta note yang dibagikan kepadanya
+struct Viewer(Permissions);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Viewer
+where
+    Arc<Mutex<Accounts>>: FromRef<S>,
+    Arc<Mutex<Shares>>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let CurrentUser(user) = CurrentUser::from_request_parts(parts, state).await?;
+        let shares = Arc::<Mutex<Shares>>::from_ref(state);
+        let accounts = Arc::<Mutex<Accounts>>::from_ref(state);
+        let shares = lock_shares(&shares).map_err(IntoResponse::into_response)?;
+        let accounts = lock_accounts(&accounts).map_err(IntoResponse::into_response)?;
+        Ok(Viewer(shares.permissions(&accounts, user)))
     }
+}
 
-    fn delete(&mut self, id: usize) -> bool {
-        let initial_len = self.notes.len();
-        self.notes.retain(|note| note.id != id);
-        self.notes.len() < initial_len
+/// Note `id` kalau `viewer` punya akses `access`. Note yang tidak terlihat dilaporkan
+/// 404, bukan 403, supaya keberadaannya tidak bocor; note read-only dilaporkan 403.
+fn note_for<'a>(notes: &'a Notes, viewer: &Permissions, id: usize, access: Access) -> Result<&'a Note, Problem> {
+    let note = notes.get(id).ok_or_else(|| Problem::not_found(id))?;
+    match viewer.access(note) {
+        None => Err(Problem::not_found(id)),
+        Some(granted) if granted < access => {
+            Err(Problem::new(StatusCode::FORBIDDEN, t!("api-read-only", id = id)))
+        }
+        Some(_) => Ok(note),
     }
 }
 
-fn load_notes() -> Notes {
-    match fs::read_to_string("notes.json") {
-        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|_| Notes::new()),
-        Err(_) => Notes::new(),
+/// Seperti `note_for`, tapi hanya untuk pemilik (hapus note, bagikan note)
+fn own_note<'a>(notes: &'a Notes, viewer: &Permissions, id: usize) -> Result<&'a Note, Problem> {
+    let note = note_for(notes, viewer, id, Access::Read)?;
+    if !viewer.owns(note) {
+        return Err(Problem::new(StatusCode::FORBIDDEN, t!("api-owner-only", id = id)));
     }
+    Ok(note)
 }
 
-fn save_notes(notes: &Notes) -> Result<(), Box<dyn std::error::Error>> {
-    let json = serde_json::to_string_pretty(notes)?;
-    fs::write("notes.json", json)?;
+fn validate_content(content: &str) -> Result<(), String> {
+    if content.trim().is_empty() {
+        return Err(t!("api-content-empty"));
+    }
+    if content.len() > MAX_NOTE_BYTES {
+        return Err(t!("api-content-too-long", size = t!("bytes", count = MAX_NOTE_BYTES)));
+    }
     Ok(())
 }
 
+/// Validasi body PUT/PATCH; semua field yang salah dilaporkan sekaligus
+fn validate(request: PatchNoteRequest) -> Result<NoteChanges, Problem> {
+    let mut invalid = Vec::new();
+
+    if let Some(Err(reason)) = request.content.as_deref().map(validate_content) {
+        invalid.push(InvalidParam { name: "content", reason });
+    }
+
+    let tags = request.tags.map(|tags| {
+        let mut normalized: Vec<String> = Vec::new();
+        for tag in &tags {
+            match select::normalize_tag(tag) {
+                Ok(tag) if !normalized.iter().any(|t| t.eq_ignore_ascii_case(&tag)) => normalized.push(tag),
+                Ok(_) => {}
+                Err(reason) => invalid.push(InvalidParam { name: "tags", reason }),
+            }
+        }
+        if normalized.len() > MAX_TAGS {
+            invalid.push(InvalidParam { name: "tags", reason: t!("api-too-many-tags", limit = MAX_TAGS) });
+        }
+        normalized
+    });
+
+    // Nama notebook kosong sama dengan tanpa notebook, seperti `rust_app move --to ""`
+    let notebook = request.notebook.map(|notebook| {
+        let notebook = notebook.map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+        if notebook.as_ref().is_some_and(|name| name.chars().count() > MAX_NOTEBOOK_CHARS) {
+            invalid.push(InvalidParam {
+                name: "notebook",
+                reason: t!("api-notebook-too-long", limit = MAX_NOTEBOOK_CHARS),
+            });
+        }
+        notebook
+    });
+
+    if !invalid.is_empty() {
+        return Err(Problem::invalid(invalid));
+    }
+    Ok(NoteChanges {
+        content: request.content,
+        tags,
+        notebook,
+        pinned: request.pinned,
+        archived: request.archived,
+    })
+}
+
+/// Terapkan perubahan ke note `id` dan simpan. Note terkunci hanya bisa diubah
+/// metadata-nya; isinya harus dibuka dulu lewat CLI.
+async fn update(
+    state: &Mutex<Notes>,
+    events: &Events,
+    storage: &Storage,
+    collab: &Collab,
+    viewer: &Permissions,
+    id: usize,
+    changes: NoteChanges,
+) -> Result<Json<Note>, Problem> {
+    let (note, saved) = change_note(state, storage, collab, viewer, id, changes)?;
+    if let Some(saved) = saved {
+        saved.await.map_err(Problem::saving)?;
+        events.send(NoteEvent::Updated(note.clone()));
+    }
+    Ok(Json(note))
+}
+
+/// Bagian `update` yang memegang lock notes; return `None` kalau tidak ada yang berubah
+fn change_note(
+    state: &Mutex<Notes>,
+    storage: &Storage,
+    collab: &Collab,
+    viewer: &Permissions,
+    id: usize,
+    changes: NoteChanges,
+) -> Result<(Note, Option<Saved>), Problem> {
+    // Dicek sebelum lock notes: registry collab tidak boleh dikunci sambil memegang notes
+    let live = collab.is_live(id);
+    let mut notes = lock_notes(state)?;
+    note_for(&notes, viewer, id, Access::Write)?;
+    let note = notes.get_mut(id).unwrap();
+    let content = changes.content.filter(|content| *content != note.content);
+    if content.is_some() && note.is_locked() {
+        return Err(Problem::new(StatusCode::CONFLICT, t!("api-note-locked", id = id)));
+    }
+    // Isinya sedang diedit bersama; perubahan dari luar akan tertimpa session itu
+    if content.is_some() && live {
+        return Err(Problem::new(StatusCode::CONFLICT, t!("api-note-live", id = id)));
+    }
+    // Grant notebook berlaku per nama notebook, jadi memindahkan note mengubah siapa yang bisa membacanya
+    let notebook = changes.notebook.filter(|notebook| *notebook != note.notebook);
+    if notebook.is_some() && !viewer.owns(note) {
+        return Err(Problem::new(StatusCode::FORBIDDEN, t!("api-owner-only", id = id)));
+    }
+
+    let mut changed = false;
+    let relink = content.is_some();
+    if let Some(content) = content {
+        note.content = content;
+        changed = true;
+    }
+    if let Some(tags) = changes.tags.filter(|tags| *tags != note.tags) {
+        note.tags = tags;
+        changed = true;
+    }
+    if let Some(notebook) = notebook {
+        note.notebook = notebook;
+        changed = true;
+    }
+    if let Some(pinned) = changes.pinned.filter(|pinned| *pinned != note.pinned) {
+        note.pinned = pinned;
+        changed = true;
+    }
+    if let Some(archived) = changes.archived.filter(|archived| *archived != note.archived) {
+        note.archived = archived;
+        changed = true;
+    }
+    if !changed {
+        return Ok((note.clone(), None));
+    }
+
+    note.touch();
+    let note = note.clone();
+    if relink {
+        notes.rebuild_links();
+    }
+    let saved = queue_notes(storage, &notes);
+    queue_audit(storage, vec![audit::Entry::new(&viewer.user.name, Action::Updated, &note)]);
+    Ok((note, Some(saved)))
+}
+
 // API Handlers
-async fn get_notes(state: axum::extract::State<Arc<Mutex<Notes>>>) -> Json<Vec<Note>> {
-    let notes = state.lock().unwrap();
-    Json(notes.notes.clone())
+#[utoipa::path(
+    get,
+    path = "/api/notes",
+    tag = "notes",
+    summary = "List all notes",
+    responses(
+        (status = 200, body = Vec<Note>),
+    ),
+)]
+async fn get_notes(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    Viewer(viewer): Viewer,
+) -> Result<Json<Vec<Note>>, Problem> {
+    let notes = lock_notes(&state)?;
+    Ok(Json(notes.notes.iter().filter(|note| viewer.can_read(note)).cloned().collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notes/{id}",
+    tag = "notes",
+    summary = "Get a note",
+    params(("id" = usize, Path, description = "Note ID")),
+    responses(
+        (status = 200, body = Note),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn get_note(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    Viewer(viewer): Viewer,
+    ApiPath(id): ApiPath<usize>,
+) -> Result<Json<Note>, Problem> {
+    let notes = lock_notes(&state)?;
+    Ok(Json(note_for(&notes, &viewer, id, Access::Read)?.clone()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/notes",
+    tag = "notes",
+    summary = "Create a note",
+    request_body = CreateNoteRequest,
+    responses(
+        (status = 201, description = "Note created; `Location` points to it", body = Note),
+        (status = 422, description = "Invalid content", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
 async fn create_note(
     state: axum::extract::State<Arc<Mutex<Notes>>>,
-    Json(payload): Json<CreateNoteRequest>,
-) -> Result<Json<Note>, StatusCode> {
-    let mut notes = state.lock().unwrap();
-    let note = Note {
-        id: notes.next_id,
-        content: payload.content,
-        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-    };
-    notes.notes.push(note.clone());
-    notes.next_id += 1;
-    
-    if save_notes(&notes).is_ok() {
-        Ok(Json(note))
-    } else {
-        Err(StatusCode::INTERNAL_SERVER_ERROR)
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    Viewer(viewer): Viewer,
+    ApiJson(payload): ApiJson<CreateNoteRequest>,
+) -> Result<impl IntoResponse, Problem> {
+    if let Err(reason) = validate_content(&payload.content) {
+        return Err(Problem::invalid(vec![InvalidParam { name: "content", reason }]));
     }
+    let (note, saved) = {
+        let mut notes = lock_notes(&state)?;
+        let id = notes.add(payload.content).id;
+        let note = notes.get_mut(id).unwrap();
+        note.owner = Some(viewer.user.name.clone());
+        let note = note.clone();
+        let saved = queue_notes(&storage, &notes);
+        queue_audit(&storage, vec![audit::Entry::new(&viewer.user.name, Action::Created, &note)]);
+        (note, saved)
+    };
+    saved.await.map_err(Problem::saving)?;
+    events.send(NoteEvent::Created(note.clone()));
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, format!("/api/notes/{}", note.id))],
+        Json(note),
+    ))
 }
 
-async fn delete_note(
+#[utoipa::path(
+    put,
+    path = "/api/notes/{id}",
+    tag = "notes",
+    summary = "Replace a note; fields left out are reset",
+    params(("id" = usize, Path, description = "Note ID")),
+    request_body = UpdateNoteRequest,
+    responses(
+        (status = 200, body = Note),
+        (status = 403, description = "Read-only access, or only the owner can move the note to another notebook", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Note is locked or being edited live", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid fields", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn replace_note(
     state: axum::extract::State<Arc<Mutex<Notes>>>,
-    Path(id): Path<usize>,
-) -> Result<StatusCode, StatusCode> {
-    let mut notes = state.lock().unwrap();
-    if notes.delete(id) {
-        if save_notes(&notes).is_ok() {
-            Ok(StatusCode::NO_CONTENT)
-        } else {
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    } else {
-        Err(StatusCode::NOT_FOUND)
-    }
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    State(collab): State<Collab>,
+    Viewer(viewer): Viewer,
+    ApiPath(id): ApiPath<usize>,
+    ApiJson(payload): ApiJson<UpdateNoteRequest>,
+) -> Result<Json<Note>, Problem> {
+    let changes = validate(payload.into())?;
+    update(&state, &events, &storage, &collab, &viewer, id, changes).await
 }
 
-async fn index() -> Html<&'static str> {
-    Html(include_str!("../static/index.html"))
+#[utoipa::path(
+    patch,
+    path = "/api/notes/{id}",
+    tag = "notes",
+    summary = "Change only the fields sent",
+    params(("id" = usize, Path, description = "Note ID")),
+    request_body = PatchNoteRequest,
+    responses(
+        (status = 200, body = Note),
+        (status = 403, description = "Read-only access, or only the owner can move the note to another notebook", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Note is locked or being edited live", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid fields", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn patch_note(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    State(collab): State<Collab>,
+    Viewer(viewer): Viewer,
+    ApiPath(id): ApiPath<usize>,
+    ApiJson(payload): ApiJson<PatchNoteRequest>,
+) -> Result<Json<Note>, Problem> {
+    let changes = validate(payload)?;
+    update(&state, &events, &storage, &collab, &viewer, id, changes).await
 }
 
-#[tokio::main]
-async fn main() {
-    let notes = Arc::new(Mutex::new(load_notes()));
+#[utoipa::path(
+    delete,
+    path = "/api/notes/{id}",
+    tag = "notes",
+    summary = "Delete a note",
+    params(("id" = usize, Path, description = "Note ID")),
+    responses(
+        (status = 204, description = "Note deleted"),
+        (status = 403, description = "Only the owner can delete a note", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn delete_note(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(shares): State<Arc<Mutex<Shares>>>,
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    Viewer(viewer): Viewer,
+    ApiPath(id): ApiPath<usize>,
+) -> Result<StatusCode, Problem> {
+    let (deleted, saved) = {
+        let mut notes = lock_notes(&state)?;
+        let deleted = own_note(&notes, &viewer, id)?.clone();
+        notes.delete(id);
+        let saved = queue_notes(&storage, &notes);
+        queue_audit(&storage, vec![audit::Entry::new(&viewer.user.name, Action::Deleted, &deleted)]);
+        (deleted, saved)
+    };
+    saved.await.map_err(Problem::saving)?;
+    let uid = deleted.uid.clone();
+    events.send(NoteEvent::Deleted(deleted));
+    let saved = {
+        let mut shares = lock_shares(&shares)?;
+        shares.remove_note(&uid).then(|| queue_shares(&storage, &shares))
+    };
+    if let Some(saved) = saved {
+        saved.await.map_err(Problem::saving)?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    // Build router
-    let app = Router::new()
-        .route("/", get(index))
-        .route("/api/notes", get(get_notes))
-        .route("/api/notes", post(create_note))
-        .route("/api/notes/:id", delete(delete_note))
-        .nest_service("/static", ServeDir::new("static"))
-        .layer(CorsLayer::permissive())
-        .with_state(notes);
+// Dekripsi locked note untuk ditampilkan; note di store tetap terkunci
+#[utoipa::path(
+    post,
+    path = "/api/notes/{id}/reveal",
+    tag = "notes",
+    summary = "Decrypt a locked note without unlocking it",
+    params(("id" = usize, Path, description = "Note ID")),
+    request_body = RevealRequest,
+    responses(
+        (status = 200, body = RevealResponse),
+        (status = 400, description = "Note is not locked", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Wrong passphrase", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn reveal_note(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    Viewer(viewer): Viewer,
+    ApiPath(id): ApiPath<usize>,
+    ApiJson(payload): ApiJson<RevealRequest>,
+) -> Result<Json<RevealResponse>, Problem> {
+    let note = {
+        let notes = lock_notes(&state)?;
+        let note = note_for(&notes, &viewer, id, Access::Read)?;
+        if !note.is_locked() {
+            return Err(Problem::new(StatusCode::BAD_REQUEST, t!("cli-not-locked", id = id)));
+        }
+        note.clone()
+    };
+    let content = blocking(move || note.reveal(&payload.passphrase))
+        .await?
+        .map_err(|e| Problem::new(StatusCode::FORBIDDEN, e.to_string()))?;
+    Ok(Json(RevealResponse { content }))
+}
 
-    println!("🚀 Server running on http://localhost:3000");
-    println!("📝 Open http://localhost:3000 in your browser");
+#[utoipa::path(
+    get,
+    path = "/api/notes/{id}/links",
+    tag = "links",
+    summary = "Links from and backlinks to a note",
+    params(("id" = usize, Path, description = "Note ID")),
+    responses(
+        (status = 200, body = NoteLinks),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn get_note_links(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    Viewer(viewer): Viewer,
+    ApiPath(id): ApiPath<usize>,
+) -> Result<Json<NoteLinks>, Problem> {
+    let notes = lock_notes(&state)?;
+    note_for(&notes, &viewer, id, Access::Read)?;
+    // Link ke note yang tidak terlihat diperlakukan seperti link rusak, supaya id-nya tidak bocor
+    let visible = |id: &usize| notes.get(*id).is_some_and(|note| viewer.can_read(note));
+    let links = notes.links_from(id).unwrap_or_default();
+    Ok(Json(NoteLinks {
+        links: links
+            .into_iter()
+            .map(|(target, note_id)| NoteLink { target, note_id: note_id.filter(visible) })
+            .collect(),
+        backlinks: notes.backlinks(id).into_iter().filter(visible).collect(),
+    }))
+}
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+#[utoipa::path(
+    get,
+    path = "/api/links/broken",
+    tag = "links",
+    summary = "Links that point to no note",
+    responses(
+        (status = 200, body = Vec<BrokenLink>),
+    ),
+)]
+async fn get_broken_links(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    Viewer(viewer): Viewer,
+) -> Result<Json<Vec<BrokenLink>>, Problem> {
+    let notes = lock_notes(&state)?;
+    Ok(Json(
+        notes
+            .broken_links()
+            .into_iter()
+            .filter(|(from, _)| notes.get(*from).is_some_and(|note| viewer.can_read(note)))
+            .map(|(from, target)| BrokenLink { from, target })
+            .collect(),
+    ))
 }
 
+// Upload file (multipart) ke note. Semua field yang berisi file akan disimpan.
+#[utoipa::path(
+    post,
+    path = "/api/notes/{id}/attachments",
+    tag = "attachments",
+    summary = "Attach files to a note",
+    params(("id" = usize, Path, description = "Note ID")),
+    request_body(content = AttachmentUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, body = Vec<Attachment>),
+        (status = 400, description = "No files in the upload", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "Read-only access", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn upload_attachments(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    Viewer(viewer): Viewer,
+    ApiPath(id): ApiPath<usize>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<Json<Vec<Attachment>>, Problem> {
+    let mut multipart = multipart?;
+    let mut files = Vec::new();
+    while let Some(field) = multipart.next_field().await? {
+        let Some(name) = field.file_name().map(str::to_string) else {
+            continue;
+        };
+        let bytes = field.bytes().await?;
+        files.push((name, bytes));
+    }
+    if files.is_empty() {
+        return Err(Problem::new(StatusCode::BAD_REQUEST, t!("api-no-files")));
+    }
+
+    // Cek akses dulu supaya file dari user tanpa hak tulis tidak ikut tersimpan
+    note_for(&*lock_notes(&state)?, &viewer, id, Access::Write)?;
+    let stored = blocking(move || {
+        files
+            .into_iter()
+            .map(|(name, bytes)| Ok((name, attachments::store_blob(&bytes)?, bytes.len() as u64)))
+            .collect::<io::Result<Vec<_>>>()
+    })
+    .await?
+    .map_err(Problem::saving)?;
+
+    let (added, note, saved) = {
+        let mut notes = lock_notes(&state)?;
+        note_for(&notes, &viewer, id, Access::Write)?;
+        let added: Vec<Attachment> = stored
+            .into_iter()
+            .filter_map(|(name, hash, size)| attachments::add_attachment(&mut notes, id, &name, hash, size))
+            .collect();
+        let note = notes.get(id).unwrap().clone();
+        let saved = queue_notes(&storage, &notes);
+        let entries = added
+            .iter()
+            .map(|attachment| {
+                audit::Entry::new(&viewer.user.name, Action::Attached, &note).with_detail(&attachment.name)
+            })
+            .collect();
+        queue_audit(&storage, entries);
+        (added, note, saved)
+    };
+    saved.await.map_err(Problem::saving)?;
+    events.send(NoteEvent::Updated(note));
+    Ok(Json(added))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notes/{id}/attachments/{hash}",
+    tag = "attachments",
+    summary = "Download an attachment",
+    params(
+        ("id" = usize, Path, description = "Note ID"),
+        ("hash" = String, Path, description = "SHA-256 of the attachment"),
+    ),
+    responses(
+        (status = 200, description = "File content", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status = 404, description = "Note or attachment not found", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn download_attachment(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    Viewer(viewer): Viewer,
+    ApiPath((id, hash)): ApiPath<(usize, String)>,
+) -> Result<impl IntoResponse, Problem> {
+    let attachment_not_found =
+        || Problem::new(StatusCode::NOT_FOUND, t!("api-attachment-not-found", hash = hash.as_str(), id = id));
+    let name = {
+        let notes = lock_notes(&state)?;
+        let note = note_for(&notes, &viewer, id, Access::Read)?;
+        let attachment = note
+            .attachments
+            .iter()
+            .find(|a| a.hash == hash)
+            .ok_or_else(attachment_not_found)?;
+        content_disposition(&attachment.name)
+    };
+    let blob = hash.clone();
+    let bytes = blocking(move || attachments::read_blob(&blob))
+        .await?
+        .map_err(|_| attachment_not_found())?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (header::CONTENT_DISPOSITION, name),
+        ],
+        bytes,
+    ))
+}
+
+/// `filename` ASCII untuk client lama, `filename*` (RFC 5987) untuk nama aslinya.
+/// Nama lama yang tersimpan sebelum sanitize_name membuang karakter kontrol ikut dibersihkan.
+fn content_disposition(name: &str) -> String {
+    let name = attachments::sanitize_name(name);
+    let fallback: String = name
+        .chars()
+        .map(|c| if c.is_ascii() && c != '"' && c != '\\' { c } else { '_' })
+        .collect();
+    let encoded = percent_encoding::utf8_percent_encode(&name, percent_encoding::NON_ALPHANUMERIC);
+    format!("attachment; filename=\"{}\"; filename*=UTF-8''{}", fallback, encoded)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/notes/{id}/attachments/{hash}",
+    tag = "attachments",
+    summary = "Remove an attachment from a note",
+    params(
+        ("id" = usize, Path, description = "Note ID"),
+        ("hash" = String, Path, description = "SHA-256 of the attachment"),
+    ),
+    responses(
+        (status = 204, description = "Attachment removed"),
+        (status = 403, description = "Read-only access", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Note or attachment not found", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn delete_attachment(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    Viewer(viewer): Viewer,
+    ApiPath((id, hash)): ApiPath<(usize, String)>,
+) -> Result<StatusCode, Problem> {
+    let (note, saved) = {
+        let mut notes = lock_notes(&state)?;
+        note_for(&notes, &viewer, id, Access::Write)?;
+        let Some(attachment) = attachments::detach(&mut notes, id, &hash) else {
+            return Err(Problem::new(
+                StatusCode::NOT_FOUND,
+                t!("api-attachment-not-found", hash = hash.as_str(), id = id),
+            ));
+        };
+        let note = notes.get(id).unwrap().clone();
+        let saved = queue_notes(&storage, &notes);
+        let entry = audit::Entry::new(&viewer.user.name, Action::Detached, &note).with_detail(attachment.name);
+        queue_audit(&storage, vec![entry]);
+        (note, saved)
+    };
+    saved.await.map_err(Problem::saving)?;
+    events.send(NoteEvent::Updated(note));
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Sync server: merge data dari client, lalu kirim balik state gabungan
+#[utoipa::path(
+    post,
+    path = "/api/sync",
+    tag = "sync",
+    summary = "Merge notes from another device and return the merged notebook",
+    request_body = sync::SyncPayload,
+    responses(
+        (status = 200, body = sync::SyncPayload),
+    ),
+)]
+async fn sync_notes(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    State(collab): State<Collab>,
+    Viewer(viewer): Viewer,
+    ApiJson(payload): ApiJson<sync::SyncPayload>,
+) -> Result<Json<sync::SyncPayload>, Problem> {
+    // Sync memindahkan seluruh notebook, termasuk note user lain
+    if !viewer.user.admin {
+        return Err(Problem::new(StatusCode::FORBIDDEN, t!("api-admin-only")));
+    }
+    // Session edit bersama dikunci sebelum notes, urutan yang sama dengan saat menyimpannya
+    let live = collab.live();
+    let (merged, saved) = {
+        let mut sessions: Vec<_> = live.iter().map(|(id, session)| (*id, collab::lock_session(session))).collect();
+        let mut notes = lock_notes(&state)?;
+        // Edit bersama yang belum tersimpan ikut di-merge, bukan tertimpa hasil sync
+        let mut entries: Vec<audit::Entry> = sessions
+            .iter_mut()
+            .filter_map(|(id, session)| {
+                let note = apply_live(&mut notes, *id, session)?;
+                Some(audit::Entry::new(session.editor.as_deref()?, Action::LiveEdited, &note))
+            })
+            .collect();
+        let report = sync::merge(&mut notes, payload);
+        // Editor yang sedang terbuka melanjutkan dari isi hasil merge
+        for (id, session) in &mut sessions {
+            if let Some(note) = notes.get(*id).filter(|note| note.content != session.saved) {
+                session.reset(note.content.clone());
+            }
+        }
+        if !report.is_empty() {
+            println!("🔄 {}", t!("server-sync", report = report.to_string()));
+            entries.push(audit::Entry {
+                at: notes::now_timestamp(),
+                user: viewer.user.name.clone(),
+                action: Action::Synced,
+                note: None,
+                uid: None,
+                detail: Some(report.to_string()),
+            });
+        }
+        let saved = (!entries.is_empty()).then(|| {
+            let saved = queue_notes(&storage, &notes);
+            queue_audit(&storage, entries);
+            saved
+        });
+        (sync::payload(&notes), saved)
+    };
+    if let Some(saved) = saved {
+        saved.await.map_err(Problem::saving)?;
+        events.send(NoteEvent::Reload(None));
+    }
+    // Blob yang belum ada di server dikirim client lewat PUT /api/sync/blobs/<hash>
+    let merged = blocking(move || sync::SyncPayload {
+        missing_blobs: sync::missing_blobs(&merged.notes),
+        ..merged
+    })
+    .await?;
+    Ok(Json(merged))
+}
+
+fn blob_not_found(hash: &str) -> Problem {
+    Problem::new(StatusCode::NOT_FOUND, t!("api-blob-not-found", hash = hash))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/sync/blobs/{hash}",
+    tag = "sync",
+    summary = "Download an attachment blob for sync",
+    params(("hash" = String, Path, description = "SHA-256 of the attachment")),
+    responses(
+        (status = 200, description = "File content", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status = 404, description = "Blob not stored on this server", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn get_sync_blob(Viewer(viewer): Viewer, ApiPath(hash): ApiPath<String>) -> Result<impl IntoResponse, Problem> {
+    if !viewer.user.admin {
+        return Err(Problem::new(StatusCode::FORBIDDEN, t!("api-admin-only")));
+    }
+    let blob = hash.clone();
+    let bytes = blocking(move || attachments::read_blob(&blob))
+        .await?
+        .map_err(|_| blob_not_found(&hash))?;
+    Ok(([(header::CONTENT_TYPE, "application/octet-stream")], bytes))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/sync/blobs/{hash}",
+    tag = "sync",
+    summary = "Upload an attachment blob the server is missing",
+    params(("hash" = String, Path, description = "SHA-256 of the attachment")),
+    request_body(content = Vec<u8>, content_type = "application/octet-stream"),
+    responses(
+        (status = 204, description = "Blob stored"),
+        (status = 400, description = "Content does not match the hash", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn put_sync_blob(
+    Viewer(viewer): Viewer,
+    ApiPath(hash): ApiPath<String>,
+    body: Result<axum::body::Bytes, BytesRejection>,
+) -> Result<StatusCode, Problem> {
+    if !viewer.user.admin {
+        return Err(Problem::new(StatusCode::FORBIDDEN, t!("api-admin-only")));
+    }
+    let bytes = body?;
+    // Blob content-addressed: isi harus cocok dengan nama supaya tidak bisa menimpa blob lain
+    if attachments::hash_bytes(&bytes) != hash {
+        return Err(Problem::new(StatusCode::BAD_REQUEST, t!("api-blob-mismatch", hash = hash)));
+    }
+    blocking(move || attachments::store_blob(&bytes))
+        .await?
+        .map_err(Problem::saving)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/conflicts",
+    tag = "conflicts",
+    summary = "Notes with conflicting edits",
+    responses(
+        (status = 200, body = Vec<ConflictView>),
+    ),
+)]
+async fn get_conflicts(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    Viewer(viewer): Viewer,
+) -> Result<Json<Vec<ConflictView>>, Problem> {
+    let notes = lock_notes(&state)?;
+    Ok(Json(
+        notes
+            .conflicts()
+            .into_iter()
+            .filter(|note| viewer.can_read(note))
+            .map(|note| {
+                let conflict = note.conflict.clone().unwrap();
+                let preview = note.merge_preview().unwrap();
+                ConflictView {
+                    id: note.id,
+                    base: conflict.base,
+                    ours: conflict.ours,
+                    theirs: conflict.theirs,
+                    theirs_updated_at: conflict.theirs_updated_at,
+                    merged: preview.text,
+                    clean: preview.clean,
+                }
+            })
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/notes/{id}/resolve",
+    tag = "conflicts",
+    summary = "Resolve a conflict with the given content",
+    params(("id" = usize, Path, description = "Note ID")),
+    request_body = ResolveRequest,
+    responses(
+        (status = 200, body = Note),
+        (status = 403, description = "Read-only access", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Note has no conflict, or is being edited live", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Content is empty or still has conflict markers", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn resolve_conflict(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    State(collab): State<Collab>,
+    Viewer(viewer): Viewer,
+    ApiPath(id): ApiPath<usize>,
+    ApiJson(payload): ApiJson<ResolveRequest>,
+) -> Result<Json<Note>, Problem> {
+    let reason = if merge::has_markers(&payload.content) {
+        Some(t!("conflict-markers-left"))
+    } else {
+        validate_content(&payload.content).err()
+    };
+    if let Some(reason) = reason {
+        return Err(Problem::invalid(vec![InvalidParam { name: "content", reason }]));
+    }
+    // Sama seperti change_note: session edit bersama akan menimpa hasil resolve
+    let live = collab.is_live(id);
+    let (note, saved) = {
+        let mut notes = lock_notes(&state)?;
+        note_for(&notes, &viewer, id, Access::Write)?;
+        let note = notes.get_mut(id).unwrap();
+        if !note.has_conflict() {
+            return Err(Problem::new(StatusCode::CONFLICT, t!("cli-no-conflict", id = id)));
+        }
+        if live {
+            return Err(Problem::new(StatusCode::CONFLICT, t!("api-note-live", id = id)));
+        }
+        note.resolve_conflict(payload.content);
+        let note = note.clone();
+        notes.rebuild_links();
+        let saved = queue_notes(&storage, &notes);
+        queue_audit(&storage, vec![audit::Entry::new(&viewer.user.name, Action::Resolved, &note)]);
+        (note, saved)
+    };
+    saved.await.map_err(Problem::saving)?;
+    events.send(NoteEvent::Updated(note.clone()));
+    Ok(Json(note))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    tag = "auth",
+    summary = "Sign in and get a session cookie",
+    security(()),
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Signed in; the `session` cookie is set", body = UserInfo),
+        (status = 401, description = "Wrong username or password", body = Problem, content_type = "application/problem+json"),
+        (status = 429, description = "Too many failed sign-ins for this username; see `Retry-After`", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn login(
+    State(accounts): State<Arc<Mutex<Accounts>>>,
+    State(cookies): State<Cookies>,
+    State(logins): State<RateLimiter<String>>,
+    ApiJson(payload): ApiJson<LoginRequest>,
+) -> Result<impl IntoResponse, Response> {
+    // Hanya login yang gagal dihitung, jadi pemilik akun tetap bisa masuk setelah
+    // salah ketik beberapa kali
+    let username = payload.username;
+    logins
+        .peek(username.clone())
+        .map_err(|wait| too_many_requests(wait, |seconds| t!("api-login-limited", seconds = seconds)))?;
+    // Argon2 sengaja lambat: cek password di thread blocking, tanpa memegang lock
+    let user = lock_accounts(&accounts).map_err(IntoResponse::into_response)?.user(&username).cloned();
+    let user = tokio::task::spawn_blocking(move || {
+        accounts::check_password(user.as_ref(), &payload.password).then_some(user).flatten()
+    })
+    .await
+    .ok()
+    .flatten();
+    let Some(user) = user else {
+        let _ = logins.check(username);
+        return Err(Problem::new(StatusCode::UNAUTHORIZED, t!("api-login-failed")).into_response());
+    };
+    let secret = lock_accounts(&accounts).map_err(IntoResponse::into_response)?.create_session(&user.name);
+    Ok((
+        [(header::SET_COOKIE, session_cookie(&secret, SESSION_TTL.as_secs(), cookies))],
+        Json(UserInfo::from(&user)),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "auth",
+    summary = "End the browser session",
+    security(()),
+    responses(
+        (status = 204, description = "Signed out; the `session` cookie is cleared"),
+    ),
+)]
+async fn logout(
+    State(accounts): State<Arc<Mutex<Accounts>>>,
+    State(cookies): State<Cookies>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, Problem> {
+    if let Some(secret) = session_secret(&headers) {
+        lock_accounts(&accounts)?.end_session(secret);
+    }
+    Ok((
+        StatusCode::NO_CONTENT,
+        [(header::SET_COOKIE, session_cookie("", 0, cookies))],
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/me",
+    tag = "auth",
+    summary = "The signed-in user",
+    responses(
+        (status = 200, body = UserInfo),
+    ),
+)]
+async fn get_me(CurrentUser(user): CurrentUser) -> Json<UserInfo> {
+    Json(UserInfo::from(&user))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/tokens",
+    tag = "auth",
+    summary = "API tokens of the signed-in user",
+    responses(
+        (status = 200, body = Vec<TokenInfo>),
+    ),
+)]
+async fn list_tokens(
+    State(accounts): State<Arc<Mutex<Accounts>>>,
+    CurrentUser(user): CurrentUser,
+) -> Result<Json<Vec<TokenInfo>>, Problem> {
+    let accounts = lock_accounts(&accounts)?;
+    Ok(Json(accounts.tokens_of(&user.name).into_iter().map(TokenInfo::from).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/tokens",
+    tag = "auth",
+    summary = "Create an API token; the token is only returned here",
+    request_body = CreateTokenRequest,
+    responses(
+        (status = 201, body = NewToken),
+        (status = 422, description = "Invalid name", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn create_token(
+    State(accounts): State<Arc<Mutex<Accounts>>>,
+    State(storage): State<Storage>,
+    CurrentUser(user): CurrentUser,
+    ApiJson(payload): ApiJson<CreateTokenRequest>,
+) -> Result<impl IntoResponse, Problem> {
+    let name = payload.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_TOKEN_NAME_CHARS {
+        return Err(Problem::invalid(vec![InvalidParam {
+            name: "name",
+            reason: t!("api-token-name-invalid", limit = MAX_TOKEN_NAME_CHARS),
+        }]));
+    }
+    let (token, secret, saved) = {
+        let mut accounts = lock_accounts(&accounts)?;
+        let (token, secret) = accounts
+            .create_token(&user.name, name)
+            .map_err(|e| Problem::new(StatusCode::UNAUTHORIZED, e))?;
+        (token, secret, queue_accounts(&storage, &accounts))
+    };
+    saved.await.map_err(Problem::saving)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(NewToken {
+            id: token.id,
+            name: token.name,
+            created_at: token.created_at,
+            token: secret,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/auth/tokens/{token_id}",
+    tag = "auth",
+    summary = "Revoke an API token",
+    params(("token_id" = String, Path, description = "Token ID")),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 404, description = "No such token", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn revoke_token(
+    State(accounts): State<Arc<Mutex<Accounts>>>,
+    State(storage): State<Storage>,
+    CurrentUser(user): CurrentUser,
+    ApiPath(token_id): ApiPath<String>,
+) -> Result<StatusCode, Problem> {
+    let saved = {
+        let mut accounts = lock_accounts(&accounts)?;
+        if !accounts.revoke_token(&token_id, Some(&user.name)) {
+            return Err(Problem::new(StatusCode::NOT_FOUND, t!("token-not-found", id = token_id)));
+        }
+        queue_accounts(&storage, &accounts)
+    };
+    saved.await.map_err(Problem::saving)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Target share dari request: tepat satu dari `note` (milik viewer) atau `notebook`.
+/// Grant notebook hanya mencakup note milik pemberinya, jadi tidak perlu dicek di sini.
+fn share_target(
+    notes: &Notes,
+    viewer: &Permissions,
+    note: Option<usize>,
+    notebook: Option<String>,
+) -> Result<Target, Problem> {
+    match (note, notebook) {
+        (Some(id), None) => Ok(Target::Note(own_note(notes, viewer, id)?.uid.clone())),
+        (None, Some(name)) if !name.trim().is_empty() => {
+            let name = name.trim();
+            if name.chars().count() > MAX_NOTEBOOK_CHARS {
+                return Err(Problem::invalid(vec![InvalidParam {
+                    name: "notebook",
+                    reason: t!("api-notebook-too-long", limit = MAX_NOTEBOOK_CHARS),
+                }]));
+            }
+            Ok(Target::Notebook(name.to_string()))
+        }
+        _ => Err(Problem::invalid(vec![InvalidParam {
+            name: "note",
+            reason: t!("api-share-target"),
+        }])),
+    }
+}
+
+/// (note, notebook) untuk ditampilkan; uid diterjemahkan ke id lokal
+fn describe_target(notes: &Notes, target: &Target) -> (Option<usize>, Option<String>) {
+    match target {
+        Target::Note(uid) => (notes.notes.iter().find(|note| note.uid == *uid).map(|note| note.id), None),
+        Target::Notebook(name) => (None, Some(name.clone())),
+    }
+}
+
+fn share_info(notes: &Notes, grant: &shares::Grant) -> ShareInfo {
+    let (note, notebook) = describe_target(notes, &grant.target);
+    ShareInfo {
+        id: grant.id.clone(),
+        owner: grant.owner.clone(),
+        user: grant.user.clone(),
+        note,
+        notebook,
+        access: grant.access,
+        created_at: grant.created_at.clone(),
+    }
+}
+
+fn share_link_info(notes: &Notes, link: &shares::Link) -> ShareLinkInfo {
+    let (note, notebook) = describe_target(notes, &link.target);
+    ShareLinkInfo {
+        id: link.id.clone(),
+        note,
+        notebook,
+        created_at: link.created_at.clone(),
+        expires_at: link.expires_at.clone(),
+        expired: link.is_expired(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/shares",
+    tag = "sharing",
+    summary = "Shares made by and with the signed-in user, and their public links",
+    responses(
+        (status = 200, body = ShareList),
+    ),
+)]
+async fn list_shares(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(shares): State<Arc<Mutex<Shares>>>,
+    Viewer(viewer): Viewer,
+) -> Result<Json<ShareList>, Problem> {
+    let notes = lock_notes(&state)?;
+    let shares = lock_shares(&shares)?;
+    let name = &viewer.user.name;
+    Ok(Json(ShareList {
+        shares: shares
+            .grants
+            .iter()
+            .filter(|grant| grant.owner == *name || grant.user == *name)
+            .map(|grant| share_info(&notes, grant))
+            .collect(),
+        links: shares
+            .links
+            .iter()
+            .filter(|link| link.owner == *name)
+            .map(|link| share_link_info(&notes, link))
+            .collect(),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/shares",
+    tag = "sharing",
+    summary = "Share a note or notebook with another user; sharing again changes the access",
+    request_body = ShareRequest,
+    responses(
+        (status = 201, body = ShareInfo),
+        (status = 403, description = "Only the owner can share a note", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid fields", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn create_share(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(shares): State<Arc<Mutex<Shares>>>,
+    State(accounts): State<Arc<Mutex<Accounts>>>,
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    Viewer(viewer): Viewer,
+    ApiJson(payload): ApiJson<ShareRequest>,
+) -> Result<impl IntoResponse, Problem> {
+    let user_exists = lock_accounts(&accounts)?.user(&payload.user).is_some();
+    let reason = if payload.user == viewer.user.name {
+        Some(t!("api-share-self"))
+    } else if !user_exists {
+        Some(t!("account-not-found", name = payload.user.as_str()))
+    } else {
+        None
+    };
+    if let Some(reason) = reason {
+        return Err(Problem::invalid(vec![InvalidParam { name: "user", reason }]));
+    }
+    let (grant, info, saved) = {
+        let notes = lock_notes(&state)?;
+        let target = share_target(&notes, &viewer, payload.note, payload.notebook)?;
+        let mut shares = lock_shares(&shares)?;
+        let grant = shares.grant(&viewer.user.name, &payload.user, target, payload.access);
+        let info = share_info(&notes, &grant);
+        (grant, info, queue_shares(&storage, &shares))
+    };
+    saved.await.map_err(Problem::saving)?;
+    events.send(NoteEvent::Reload(Some(vec![grant.user])));
+    Ok((StatusCode::CREATED, Json(info)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/shares/{share_id}",
+    tag = "sharing",
+    summary = "Stop sharing; the owner and the recipient can both do this",
+    params(("share_id" = String, Path, description = "Share ID")),
+    responses(
+        (status = 204, description = "Share removed"),
+        (status = 404, description = "No such share", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn delete_share(
+    State(shares): State<Arc<Mutex<Shares>>>,
+    State(events): State<Events>,
+    State(storage): State<Storage>,
+    Viewer(viewer): Viewer,
+    ApiPath(share_id): ApiPath<String>,
+) -> Result<StatusCode, Problem> {
+    let (users, saved) = {
+        let mut shares = lock_shares(&shares)?;
+        let users = shares
+            .grants
+            .iter()
+            .find(|grant| grant.id == share_id)
+            .map(|grant| vec![grant.owner.clone(), grant.user.clone()]);
+        if !shares.revoke(&share_id, &viewer.user.name) {
+            return Err(Problem::new(StatusCode::NOT_FOUND, t!("api-share-not-found", id = share_id)));
+        }
+        (users, queue_shares(&storage, &shares))
+    };
+    saved.await.map_err(Problem::saving)?;
+    events.send(NoteEvent::Reload(users));
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/share-links",
+    tag = "sharing",
+    summary = "Create a public read-only link to a note or notebook; the token is only returned here",
+    request_body = ShareLinkRequest,
+    responses(
+        (status = 201, body = NewShareLink),
+        (status = 403, description = "Only the owner can share a note", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid fields", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn create_share_link(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(shares): State<Arc<Mutex<Shares>>>,
+    State(storage): State<Storage>,
+    Viewer(viewer): Viewer,
+    ApiJson(payload): ApiJson<ShareLinkRequest>,
+) -> Result<impl IntoResponse, Problem> {
+    if let Some(days) = payload.expires_in_days.filter(|days| !(1..=shares::MAX_LINK_DAYS).contains(days)) {
+        return Err(Problem::invalid(vec![InvalidParam {
+            name: "expires_in_days",
+            reason: t!("api-link-expiry", days = days, limit = shares::MAX_LINK_DAYS),
+        }]));
+    }
+    let (link, token, saved) = {
+        let notes = lock_notes(&state)?;
+        let target = share_target(&notes, &viewer, payload.note, payload.notebook)?;
+        let expires_at = payload.expires_in_days.map(shares::expiry_after);
+        let mut shares = lock_shares(&shares)?;
+        let (link, token) = shares.create_link(&viewer.user.name, target, expires_at);
+        (share_link_info(&notes, &link), token, queue_shares(&storage, &shares))
+    };
+    saved.await.map_err(Problem::saving)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(NewShareLink {
+            link,
+            url: format!("/?share={}", token),
+            token,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/share-links/{link_id}",
+    tag = "sharing",
+    summary = "Delete a public link",
+    params(("link_id" = String, Path, description = "Link ID")),
+    responses(
+        (status = 204, description = "Link deleted"),
+        (status = 404, description = "No such link", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn delete_share_link(
+    State(shares): State<Arc<Mutex<Shares>>>,
+    State(storage): State<Storage>,
+    Viewer(viewer): Viewer,
+    ApiPath(link_id): ApiPath<String>,
+) -> Result<StatusCode, Problem> {
+    let saved = {
+        let mut shares = lock_shares(&shares)?;
+        if !shares.delete_link(&link_id, &viewer.user.name) {
+            return Err(Problem::new(StatusCode::NOT_FOUND, t!("api-share-not-found", id = link_id)));
+        }
+        queue_shares(&storage, &shares)
+    };
+    saved.await.map_err(Problem::saving)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Link publik: siapa saja yang memegang token boleh membaca, tanpa login.
+// Note terkunci tidak ikut ditampilkan karena isinya hanya bisa dibuka dengan passphrase.
+#[utoipa::path(
+    get,
+    path = "/api/shared/{token}",
+    tag = "sharing",
+    summary = "Read the notes behind a public link",
+    security(()),
+    params(("token" = String, Path, description = "Token from the share link")),
+    responses(
+        (status = 200, body = Vec<SharedNote>),
+        (status = 404, description = "Unknown or expired link", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn get_shared(
+    state: axum::extract::State<Arc<Mutex<Notes>>>,
+    State(shares): State<Arc<Mutex<Shares>>>,
+    State(accounts): State<Arc<Mutex<Accounts>>>,
+    ApiPath(token): ApiPath<String>,
+) -> Result<Json<Vec<SharedNote>>, Problem> {
+    let link_not_found = || Problem::new(StatusCode::NOT_FOUND, t!("api-link-not-found"));
+    let link = lock_shares(&shares)?.link(&token).cloned().ok_or_else(link_not_found)?;
+    let owner = lock_accounts(&accounts)?.user(&link.owner).cloned().ok_or_else(link_not_found)?;
+    let notes = lock_notes(&state)?;
+    let shared = Shares::shared_notes(&link, &owner, &notes);
+    // Link ke satu note yang sudah dihapus sama dengan link yang tidak ada
+    if shared.is_empty() && matches!(link.target, Target::Note(_)) {
+        return Err(link_not_found());
+    }
+    Ok(Json(
+        shared
+            .into_iter()
+            .filter(|note| !note.is_locked())
+            .map(SharedNote::from)
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "audit",
+    summary = "Who created, changed or deleted which note, newest first (admins only)",
+    params(AuditQuery),
+    responses(
+        (status = 200, body = Vec<audit::Entry>),
+        (status = 403, description = "Not an admin", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid limit", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn get_audit(
+    CurrentUser(user): CurrentUser,
+    ApiQuery(query): ApiQuery<AuditQuery>,
+) -> Result<Json<Vec<audit::Entry>>, Problem> {
+    if !user.admin {
+        return Err(Problem::new(StatusCode::FORBIDDEN, t!("api-admin-only")));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_AUDIT_LIMIT);
+    if !(1..=MAX_AUDIT_LIMIT).contains(&limit) {
+        return Err(Problem::invalid(vec![InvalidParam {
+            name: "limit",
+            reason: t!("api-audit-limit", limit = MAX_AUDIT_LIMIT),
+        }]));
+    }
+    let filter = audit::Filter {
+        user: query.user,
+        note: query.note,
+        uid: query.uid,
+        action: query.action,
+        since: query.since,
+    };
+    let entries = blocking(move || audit::read(&filter, limit))
+        .await?
+        .map_err(|e| Problem::new(StatusCode::INTERNAL_SERVER_ERROR, t!("audit-read-failed", error = e.to_string())))?;
+    Ok(Json(entries))
+}
+
+/// Izin `user` saat ini, untuk koneksi yang hidup lebih lama dari satu request
+/// (SSE, edit bersama). `None` kalau user sudah dihapus.
+fn current_permissions(user: &str, accounts: &Mutex<Accounts>, shares: &Mutex<Shares>) -> Option<Permissions> {
+    let shares = lock_shares(shares).ok()?;
+    let accounts = lock_accounts(accounts).ok()?;
+    let user = accounts.user(user)?.clone();
+    Some(shares.permissions(&accounts, user))
+}
+
+/// Apakah `user` boleh menerima event ini. Akses dihitung ulang per event, jadi share
+/// yang baru dibuat atau dicabut langsung berlaku. `None` kalau user sudah dihapus:
+/// stream-nya ditutup.
+fn event_visible(
+    event: &NoteEvent,
+    user: &str,
+    accounts: &Mutex<Accounts>,
+    shares: &Mutex<Shares>,
+) -> Option<bool> {
+    let viewer = current_permissions(user, accounts, shares)?;
+    Some(match event {
+        NoteEvent::Reload(users) => users.as_ref().is_none_or(|users| users.contains(&viewer.user.name)),
+        _ => event.note().is_some_and(|note| viewer.can_read(note)),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "events",
+    summary = "Stream of note changes visible to the signed-in user",
+    description = "Server-Sent Events. `note-created` and `note-updated` carry the note, \
+        `note-deleted` carries `{\"id\": ...}`, and `reload` means many notes changed \
+        (sync, schedules, shares) or events were missed, so the list should be fetched again.",
+    responses(
+        (status = 200, description = "Event stream", content_type = "text/event-stream", body = String),
+    ),
+)]
+async fn note_events(
+    State(events): State<Events>,
+    State(accounts): State<Arc<Mutex<Accounts>>>,
+    State(shares): State<Arc<Mutex<Shares>>>,
+    State(shutdown): State<Shutdown>,
+    Viewer(viewer): Viewer,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let user = viewer.user.name;
+    let stream = BroadcastStream::new(events.subscribe())
+        .map(|event| match event {
+            Ok(event) => Some(event),
+            // Koneksi ini terlalu lambat dan melewatkan event: minta page memuat ulang
+            Err(BroadcastStreamRecvError::Lagged(_)) => Some(NoteEvent::Reload(None)),
+        })
+        // Stream ditutup saat server shutdown; EventSource di browser menyambung ulang sendiri
+        .merge(shutdown.into_stream().map(|()| None))
+        .map_while(move |event| {
+            let event = event?;
+            let visible = event_visible(&event, &user, &accounts, &shares)?;
+            Some(visible.then(|| Ok(Event::default().event(event.name()).data(event.data()))))
+        })
+        .filter_map(|event| event);
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Tulis isi session edit bersama ke note `id`; return note kalau berubah. Isi yang
+/// tidak valid (kosong atau terlalu besar) tidak ditulis; versi valid terakhir tetap ada.
+/// Kalau note sudah diubah di luar session (sync, CLI), session di-reset ke isi note
+/// supaya perubahan itu tidak tertimpa.
+fn apply_live(notes: &mut Notes, id: usize, session: &mut collab::Session) -> Option<Note> {
+    let note = notes.get_mut(id)?;
+    if note.is_locked() {
+        return None;
+    }
+    if note.content != session.saved {
+        session.reset(note.content.clone());
+        return None;
+    }
+    if note.content == session.content || validate_content(&session.content).is_err() {
+        return None;
+    }
+    note.content = session.content.clone();
+    note.touch();
+    session.saved = note.content.clone();
+    let note = note.clone();
+    notes.rebuild_links();
+    Some(note)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notes/{id}/collab",
+    tag = "collab",
+    summary = "Edit a note together with other users over WebSocket",
+    description = "Upgrades to a WebSocket carrying JSON messages. The server first sends \
+        `{\"type\": \"init\", client, revision, content, peers, read_only}`. Clients send \
+        `{\"type\": \"op\", revision, op}` with an ot.js-style operation (positive number = \
+        retain, string = insert, negative number = delete, counted in Unicode code points) \
+        and `{\"type\": \"cursor\", anchor, head}`. The server answers with `ack`, and relays \
+        `op`, `cursor`, `join` and `leave` from other peers, and sends `reset` with the new \
+        `revision` and `content` when the note was changed elsewhere (unacknowledged edits are \
+        dropped). The text is saved to the note a few seconds after edits and when the last \
+        peer leaves.",
+    params(("id" = usize, Path, description = "Note ID")),
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol"),
+        (status = 404, description = "Note not found", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Note is locked", body = Problem, content_type = "application/problem+json"),
+    ),
+)]
+async fn collab_note(
+    // Session live memegang hampir semua bagian state
+    State(app): State<AppState>,
+    Viewer(viewer): Viewer,
+    ApiPath(id): ApiPath<usize>,
+    upgrade: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Result<Response, Problem> {
+    {
+        let notes = lock_notes(&app.notes)?;
+        let note = note_for(&notes, &viewer, id, Access::Read)?;
+        if note.is_locked() {
+            return Err(Problem::new(StatusCode::CONFLICT, t!("api-note-locked", id = id)));
+        }
+    }
+    let upgrade = upgrade?;
+    let session = app.collab.session(id, || {
+        let notes = lock_notes(&app.notes)?;
+        notes.get(id).map(|note| note.content.clone()).ok_or_else(|| Problem::not_found(id))
+    })?;
+    let live = LiveEdit {
+        id,
+        user: viewer.user.name,
+        session,
+        collab: app.collab,
+        notes: app.notes,
+        accounts: app.accounts,
+        shares: app.shares,
+        events: app.events,
+        storage: app.storage,
+        shutdown: app.shutdown,
+    };
+    Ok(upgrade.on_upgrade(move |socket| live.run(socket)))
+}
+
+/// Satu koneksi WebSocket edit bersama
+#[derive(Clone)]
+struct LiveEdit {
+    id: usize,
+    user: String,
+    session: Arc<Mutex<collab::Session>>,
+    collab: Collab,
+    notes: Arc<Mutex<Notes>>,
+    accounts: Arc<Mutex<Accounts>>,
+    shares: Arc<Mutex<Shares>>,
+    events: Events,
+    storage: Storage,
+    shutdown: Shutdown,
+}
+
+async fn send_live(socket: &mut WebSocket, message: &ServerMessage) -> Result<(), axum::Error> {
+    let json = serde_json::to_string(message).unwrap_or_default();
+    socket.send(WsMessage::Text(json)).await
+}
+
+impl LiveEdit {
+    async fn run(self, mut socket: WebSocket) {
+        let read_only = !self.can_write(&self.user);
+        let (client, init, mut rx) = collab::lock_session(&self.session).join(&self.user, read_only);
+        if send_live(&mut socket, &init).await.is_ok() {
+            loop {
+                tokio::select! {
+                    incoming = socket.recv() => match incoming {
+                        Some(Ok(WsMessage::Text(text))) => {
+                            if let Err(detail) = self.handle(client, &text) {
+                                if send_live(&mut socket, &ServerMessage::Error { detail }).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                        Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => break,
+                        Some(Ok(_)) => {}
+                    },
+                    outgoing = rx.recv() => {
+                        // Operasi sendiri kembali lewat channel yang sama supaya ack
+                        // sampai ke client dalam urutan revisi yang benar
+                        let message = match outgoing {
+                            Ok(ServerMessage::Op { client: from, revision, .. }) if from == client => {
+                                ServerMessage::Ack { revision }
+                            }
+                            Ok(ServerMessage::Cursor { client: from, .. } | ServerMessage::Join { client: from, .. })
+                                if from == client => continue,
+                            Ok(message) => message,
+                            // Koneksi ini terlalu lambat dan melewatkan operasi: client harus membuka ulang
+                            Err(_) => {
+                                let detail = t!("collab-out-of-sync");
+                                let _ = send_live(&mut socket, &ServerMessage::Error { detail }).await;
+                                break;
+                            }
+                        };
+                        if send_live(&mut socket, &message).await.is_err() {
+                            break;
+                        }
+                    }
+                    () = self.shutdown.requested() => {
+                        let _ = socket.send(WsMessage::Close(None)).await;
+                        break;
+                    }
+                }
+            }
+        }
+        self.collab.leave(self.id, client, |session| self.persist(session));
+    }
+
+    /// Akses tulis `user` saat ini. Dicek ulang per operasi dan saat menyimpan, karena
+    /// share bisa dicabut (atau user dihapus) selama koneksi masih terbuka.
+    fn can_write(&self, user: &str) -> bool {
+        let Some(viewer) = current_permissions(user, &self.accounts, &self.shares) else {
+            return false;
+        };
+        lock_notes(&self.notes)
+            .is_ok_and(|notes| notes.get(self.id).is_some_and(|note| viewer.access(note) == Some(Access::Write)))
+    }
+
+    fn handle(&self, client: u64, text: &str) -> Result<(), String> {
+        let message: ClientMessage = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        // Dicek sebelum lock session; can_write mengunci notes
+        if matches!(message, ClientMessage::Op { .. }) && !self.can_write(&self.user) {
+            return Err(t!("api-read-only", id = self.id));
+        }
+        let mut session = collab::lock_session(&self.session);
+        match message {
+            ClientMessage::Cursor { anchor, head } => session.set_cursor(client, anchor, head),
+            ClientMessage::Op { revision, op } => {
+                session.receive(client, revision, op, MAX_NOTE_BYTES)?;
+                if !session.save_scheduled {
+                    session.save_scheduled = true;
+                    self.schedule_save();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn schedule_save(&self) {
+        let live = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(LIVE_SAVE_DELAY).await;
+            let mut session = collab::lock_session(&live.session);
+            session.save_scheduled = false;
+            live.persist(&mut session);
+        });
+    }
+
+    /// Simpan isi session ke note. Kalau editor terakhir sudah tidak punya akses tulis,
+    /// perubahan sejak penyimpanan terakhir dibuang dan session kembali ke isi note.
+    fn persist(&self, session: &mut collab::Session) {
+        // Belum ada operasi sejak session dibuka atau di-reset
+        let Some(editor) = session.editor.clone() else {
+            return;
+        };
+        let writable = self.can_write(&editor);
+        let Ok(mut notes) = lock_notes(&self.notes) else {
+            return;
+        };
+        if !writable {
+            if let Some(note) = notes.get(self.id) {
+                session.reset(note.content.clone());
+            }
+            return;
+        }
+        let Some(note) = apply_live(&mut notes, self.id, session) else {
+            return;
+        };
+        // Tidak ada request yang menunggu, jadi event dikirim dari thread penulis setelah tersimpan
+        let snapshot = notes.clone();
+        let events = self.events.clone();
+        let entry = audit::Entry::new(&editor, Action::LiveEdited, &note);
+        let id = self.id;
+        self.storage.run(move || match save_notes(&snapshot) {
+            Ok(()) => events.send(NoteEvent::Updated(note)),
+            Err(e) => eprintln!("❌ {}", t!("collab-save-failed", id = id, error = e.to_string())),
+        });
+        queue_audit(&self.storage, vec![entry]);
+    }
+}
+
+// Katalog pesan untuk halaman web: ?lang=, lalu Accept-Language browser, lalu bahasa server
+#[utoipa::path(
+    get,
+    path = "/api/i18n",
+    tag = "i18n",
+    summary = "Messages for the web page",
+    security(()),
+    params(CatalogQuery),
+    responses(
+        (status = 200, body = Catalog),
+    ),
+)]
+async fn get_catalog(headers: HeaderMap, Query(query): Query<CatalogQuery>) -> Json<Catalog> {
+    let lang = query
+        .lang
+        .as_deref()
+        .and_then(i18n::Lang::parse)
+        .or_else(|| {
+            headers
+                .get(header::ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(i18n::negotiate)
+        })
+        .unwrap_or_else(i18n::lang);
+    Json(Catalog {
+        lang: lang.code(),
+        messages: i18n::messages_in(lang, "web-").into_iter().collect(),
+    })
+}
+
+async fn index() -> Html<&'static str> {
+    Html(include_str!("../static/index.html"))
+}
+
+// Server berjalan lama, jadi cek jadwal recurring secara berkala (bukan hanya saat start)
+async fn run_schedules_periodically(state: Arc<Mutex<Notes>>, events: Events, storage: Storage) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+    loop {
+        interval.tick().await;
+        // Template dibaca tanpa lock; lock notes hanya dipegang selama menambah note
+        // dan mengambil snapshot, penulisannya dikerjakan thread penulis
+        let due = match blocking(templates::due_schedules).await {
+            Ok(Ok(due)) => due,
+            Ok(Err(e)) => {
+                eprintln!("❌ {}", t!("schedules-error", error = e.to_string()));
+                continue;
+            }
+            Err(_) => continue,
+        };
+        if due.contents.is_empty() {
+            continue;
+        }
+        let count = due.contents.len();
+        let saved = {
+            let Ok(mut notes) = lock_notes(&state) else {
+                continue;
+            };
+            for content in due.contents {
+                notes.add(content);
+            }
+            queue_notes(&storage, &notes)
+        };
+        let schedules = due.schedules;
+        // Jadwal baru ditandai sudah jalan setelah note-nya tersimpan
+        let result = async {
+            saved.await?;
+            storage.save(move || templates::save_schedules(&schedules)).await
+        }
+        .await;
+        events.send(NoteEvent::Reload(None));
+        match result {
+            Ok(()) => println!("{}", t!("schedules-ran", count = count)),
+            Err(e) => eprintln!("❌ {}", t!("schedules-error", error = e.to_string())),
+        }
+    }
+}
+
+/// State middleware rate limit: bucket per client dan proxy yang header-nya dipercaya
+#[derive(Clone)]
+struct ChangeLimit {
+    limiter: RateLimiter,
+    proxies: Arc<TrustedProxies>,
+}
+
+/// Tolak request yang mengubah data dengan 429 kalau client-nya melewati rate limit.
+/// Alamat client dari koneksi TCP (tidak ada untuk Unix socket); header proxy hanya
+/// dipakai kalau koneksinya datang dari proxy di `trusted-proxies`.
+async fn limit_changes(State(limit): State<ChangeLimit>, request: Request, next: Next) -> Response {
+    if matches!(*request.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return next.run(request).await;
+    }
+    let peer = match request.extensions().get::<ConnectInfo<Option<SocketAddr>>>() {
+        Some(ConnectInfo(Some(addr))) => Some(addr.ip()),
+        _ => None,
+    };
+    let client = limit.proxies.client(peer, request.headers());
+    match limit.limiter.check(client) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => too_many_requests(wait, |seconds| t!("api-rate-limited", seconds = seconds)),
+    }
+}
+
+/// 429 dengan `Retry-After` dalam detik, dibulatkan ke atas
+fn too_many_requests(wait: Duration, detail: impl FnOnce(u64) -> String) -> Response {
+    let seconds = wait.as_secs() + 1;
+    let problem = Problem::new(StatusCode::TOO_MANY_REQUESTS, detail(seconds));
+    ([(header::RETRY_AFTER, seconds.to_string())], problem).into_response()
+}
+
+/// Semua route JSON API. Setiap route di sini harus punya anotasi `#[utoipa::path]`
+/// dan terdaftar di `ApiDoc`; test `routes_match_openapi_spec` memeriksanya.
+fn api_routes() -> Vec<(&'static str, MethodRouter<AppState>)> {
+    vec![
+        ("/api/notes", get(get_notes).post(create_note)),
+        (
+            "/api/notes/:id",
+            get(get_note).put(replace_note).patch(patch_note).delete(delete_note),
+        ),
+        ("/api/notes/:id/reveal", post(reveal_note)),
+        ("/api/notes/:id/links", get(get_note_links)),
+        ("/api/links/broken", get(get_broken_links)),
+        ("/api/conflicts", get(get_conflicts)),
+        ("/api/i18n", get(get_catalog)),
+        ("/api/notes/:id/resolve", post(resolve_conflict)),
+        ("/api/sync", post(sync_notes).layer(DefaultBodyLimit::max(MAX_ATTACHMENT_BYTES))),
+        (
+            "/api/sync/blobs/:hash",
+            get(get_sync_blob)
+                .put(put_sync_blob)
+                .layer(DefaultBodyLimit::max(MAX_ATTACHMENT_BYTES)),
+        ),
+        (
+            "/api/notes/:id/attachments",
+            post(upload_attachments).layer(DefaultBodyLimit::max(MAX_ATTACHMENT_BYTES)),
+        ),
+        (
+            "/api/notes/:id/attachments/:hash",
+            get(download_attachment).delete(delete_attachment),
+        ),
+        ("/api/auth/login", post(login)),
+        ("/api/auth/logout", post(logout)),
+        ("/api/auth/me", get(get_me)),
+        ("/api/auth/tokens", get(list_tokens).post(create_token)),
+        ("/api/auth/tokens/:token_id", axum::routing::delete(revoke_token)),
+        ("/api/shares", get(list_shares).post(create_share)),
+        ("/api/shares/:share_id", axum::routing::delete(delete_share)),
+        ("/api/share-links", post(create_share_link)),
+        ("/api/share-links/:link_id", axum::routing::delete(delete_share_link)),
+        ("/api/shared/:token", get(get_shared)),
+        ("/api/audit", get(get_audit)),
+        ("/api/events", get(note_events)),
+        ("/api/notes/:id/collab", get(collab_note)),
+    ]
+}
+
+/// Subcommand akun: mengubah users.json langsung, server yang sedang jalan
+/// perlu direstart supaya melihat perubahan
+fn run_command(command: Command) -> Result<(), String> {
+    let mut accounts = Accounts::load().map_err(|e| t!("server-accounts-failed", error = e.to_string()))?;
+    match command {
+        Command::User(UserCommand::Add { name, admin }) => {
+            let password = accounts::read_new_password().map_err(|e| e.to_string())?;
+            let admin = accounts.add_user(&name, &password, admin)?.admin;
+            accounts.save().map_err(|e| e.to_string())?;
+            let key = if admin { "user-added-admin" } else { "user-added" };
+            println!("✅ {}", t!(key, name = name));
+        }
+        Command::User(UserCommand::List) => {
+            if accounts.users.is_empty() {
+                println!("{}", t!("users-empty"));
+            }
+            for user in &accounts.users {
+                let role = if user.admin { t!("user-role-admin") } else { String::new() };
+                println!("{:<32} {} {}", user.name, user.created_at, role);
+            }
+        }
+        Command::User(UserCommand::Remove { name }) => {
+            if !accounts.remove_user(&name) {
+                return Err(t!("account-not-found", name = name));
+            }
+            accounts.save().map_err(|e| e.to_string())?;
+            let mut shares = Shares::load().map_err(|e| t!("server-shares-failed", error = e.to_string()))?;
+            if shares.remove_user(&name) {
+                shares.save().map_err(|e| e.to_string())?;
+            }
+            println!("🗑️  {}", t!("user-removed", name = name));
+        }
+        Command::User(UserCommand::Passwd { name }) => {
+            if accounts.user(&name).is_none() {
+                return Err(t!("account-not-found", name = name));
+            }
+            let password = accounts::read_new_password().map_err(|e| e.to_string())?;
+            accounts.set_password(&name, &password)?;
+            accounts.save().map_err(|e| e.to_string())?;
+            println!("✅ {}", t!("user-password-changed", name = name));
+        }
+        Command::Token(TokenCommand::Create { user, name }) => {
+            let (token, secret) = accounts.create_token(&user, &name)?;
+            accounts.save().map_err(|e| e.to_string())?;
+            eprintln!("✅ {}", t!("token-created", id = token.id, user = user));
+            // Hanya token di stdout, supaya bisa langsung dipakai di script
+            println!("{}", secret);
+        }
+        Command::Token(TokenCommand::List { user }) => {
+            let tokens: Vec<_> = accounts
+                .tokens
+                .iter()
+                .filter(|token| user.as_ref().is_none_or(|user| token.user == *user))
+                .collect();
+            if tokens.is_empty() {
+                println!("{}", t!("tokens-empty"));
+            }
+            for token in tokens {
+                println!("{}  {:<16} {:<24} {}", token.id, token.user, token.name, token.created_at);
+            }
+        }
+        Command::Token(TokenCommand::Revoke { id }) => {
+            if !accounts.revoke_token(&id, None) {
+                return Err(t!("token-not-found", id = id));
+            }
+            accounts.save().map_err(|e| e.to_string())?;
+            println!("🗑️  {}", t!("token-revoked", id = id));
+        }
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    if let Some(lang) = args.lang {
+        i18n::set_lang(lang);
+    }
+    if let Some(command) = args.command {
+        if let Err(e) = run_command(command) {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Config dan cert dicek paling awal, supaya salah ketik langsung terlihat
+    let config = match server_config::load(args.config.as_deref(), args.server) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
+    let tls = match config.tls_acceptor() {
+        Ok(tls) => tls,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let accounts = match Accounts::load() {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            eprintln!("❌ {}", t!("server-accounts-failed", error = e.to_string()));
+            std::process::exit(1);
+        }
+    };
+    if accounts.users.is_empty() {
+        eprintln!("⚠️  {}", t!("server-no-users"));
+    }
+    let shares = match Shares::load() {
+        Ok(shares) => shares,
+        Err(e) => {
+            eprintln!("❌ {}", t!("server-shares-failed", error = e.to_string()));
+            std::process::exit(1);
+        }
+    };
+    if notes::is_encrypted() {
+        println!("🔐 {}", t!("server-encrypted"));
+    }
+    let storage = match Storage::start() {
+        Ok(storage) => storage,
+        Err(e) => {
+            eprintln!("❌ {}", t!("server-storage-failed", error = e.to_string()));
+            std::process::exit(1);
+        }
+    };
+    let notes = Arc::new(Mutex::new(notes::load_notes()));
+    let events = Events::default();
+    tokio::spawn(run_schedules_periodically(Arc::clone(&notes), events.clone(), storage.clone()));
+    let (controller, shutdown) = shutdown::channel();
+
+    // Build router
+    let app = api_routes()
+        .into_iter()
+        .fold(Router::new(), |router, (path, route)| router.route(path, route))
+        .route("/", get(index))
+        .merge(SwaggerUi::new("/api/docs").url("/api/openapi.json", ApiDoc::openapi()))
+        .nest_service("/static", ServeDir::new("static"))
+        .layer(DefaultBodyLimit::max(MAX_JSON_BYTES))
+        .layer(middleware::from_fn_with_state(
+            ChangeLimit {
+                limiter: RateLimiter::new(config.rate_limit),
+                proxies: Arc::new(config.trusted_proxies.clone()),
+            },
+            limit_changes,
+        ))
+        // Panic di handler jadi 500 problem+json, bukan koneksi yang terputus
+        .layer(CatchPanicLayer::custom(|_| {
+            Problem::new(StatusCode::INTERNAL_SERVER_ERROR, t!("api-internal-error")).into_response()
+        }))
+        .layer(config.cors())
+        .with_state(AppState {
+            notes,
+            accounts: Arc::new(Mutex::new(accounts)),
+            shares: Arc::new(Mutex::new(shares)),
+            events,
+            collab: Collab::default(),
+            storage: storage.clone(),
+            shutdown: shutdown.clone(),
+            cookies: Cookies {
+                secure: config.secure_cookies,
+            },
+            logins: RateLimiter::new(LOGIN_FAILURES_PER_MINUTE),
+        });
+
+    // serve() hanya selesai kalau gagal listen; saat sinyal datang, future-nya di-drop
+    // sehingga listener ditutup dan tidak ada koneksi baru
+    tokio::select! {
+        result = serve(&config, tls, app, shutdown) => {
+            if let Err(e) = result {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+        }
+        () = shutdown::signal() => {}
+    }
+
+    println!("\n⏳ {}", t!("server-shutting-down"));
+    controller.start();
+    if !controller.drained(SHUTDOWN_TIMEOUT).await {
+        eprintln!("⚠️  {}", t!("server-shutdown-timeout", seconds = SHUTDOWN_TIMEOUT.as_secs()));
+    }
+    match storage.flush().await {
+        Ok(()) => println!("👋 {}", t!("server-stopped")),
+        Err(e) => {
+            eprintln!("❌ {}", t!("error-saving", error = e.to_string()));
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Layani router di alamat dari config. Tidak memakai axum::serve karena itu hanya
+/// menerima TcpListener, sedangkan server ini juga bisa memakai TLS dan Unix socket.
+async fn serve(
+    config: &ServerConfig,
+    tls: Option<TlsAcceptor>,
+    app: Router,
+    shutdown: Shutdown,
+) -> Result<(), ConfigError> {
+    match &config.listen {
+        Listen::Tcp(addr) => {
+            let listener = tokio::net::TcpListener::bind(addr).await.map_err(|error| ConfigError::Bind {
+                address: addr.to_string(),
+                error,
+            })?;
+            let bound = Listen::Tcp(listener.local_addr().unwrap_or(*addr));
+            announce(&config.url(&bound), true);
+            loop {
+                let Some((stream, peer)) = accepted(listener.accept().await).await else {
+                    continue;
+                };
+                let (app, tls, shutdown) = (app.clone(), tls.clone(), shutdown.clone());
+                tokio::spawn(async move {
+                    let peer = Some(peer);
+                    match tls {
+                        // Handshake gagal (klien bicara HTTP biasa, cert ditolak): tutup saja
+                        Some(tls) => {
+                            if let Ok(stream) = tls.accept(stream).await {
+                                serve_connection(stream, peer, app, shutdown).await;
+                            }
+                        }
+                        None => serve_connection(stream, peer, app, shutdown).await,
+                    }
+                });
+            }
+        }
+        Listen::Unix(path) => {
+            let url = config.url(&config.listen);
+            serve_unix(path, app, shutdown, move || announce(&url, false)).await
+        }
+    }
+}
+
+/// Pesan startup, dicetak setelah bind supaya URL-nya pasti bisa dipakai
+fn announce(url: &str, browser: bool) {
+    println!("🚀 {}", t!("server-running", url = url));
+    if browser {
+        println!("📝 {}", t!("server-open", url = url));
+    }
+}
+
+/// Error accept (misalnya kehabisan file descriptor) tidak menghentikan server
+async fn accepted<S, A>(result: io::Result<(S, A)>) -> Option<(S, A)> {
+    match result {
+        Ok(accepted) => Some(accepted),
+        Err(e) => {
+            eprintln!("⚠️  {}", t!("server-accept-failed", error = e.to_string()));
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            None
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn serve_unix(
+    path: &std::path::Path,
+    app: Router,
+    shutdown: Shutdown,
+    bound: impl FnOnce(),
+) -> Result<(), ConfigError> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixStream;
+
+    // Socket sisa proses yang sudah mati menghalangi bind; socket yang masih dipakai
+    // server lain dan file biasa tidak disentuh
+    let stale = std::fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_socket())
+        && UnixStream::connect(path).is_err();
+    if stale {
+        let _ = std::fs::remove_file(path);
+    }
+    let listener = tokio::net::UnixListener::bind(path).map_err(|error| ConfigError::Bind {
+        address: path.display().to_string(),
+        error,
+    })?;
+    bound();
+    loop {
+        let Some((stream, _)) = accepted(listener.accept().await).await else {
+            continue;
+        };
+        tokio::spawn(serve_connection(stream, None, app.clone(), shutdown.clone()));
+    }
+}
+
+#[cfg(not(unix))]
+async fn serve_unix(
+    _path: &std::path::Path,
+    _app: Router,
+    _shutdown: Shutdown,
+    _bound: impl FnOnce(),
+) -> Result<(), ConfigError> {
+    Err(ConfigError::SocketUnsupported)
+}
+
+/// HTTP/1.1 atau HTTP/2 di satu koneksi, termasuk upgrade ke WebSocket.
+/// Alamat `peer` tersedia untuk handler sebagai `ConnectInfo<Option<SocketAddr>>`
+/// (`None` lewat Unix socket).
+/// Saat shutdown, request yang sedang berjalan diselesaikan dulu baru koneksi ditutup.
+async fn serve_connection<S>(stream: S, peer: Option<SocketAddr>, app: Router, shutdown: Shutdown)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let builder = auto::Builder::new(TokioExecutor::new());
+    let service = AddExtension::new(app, ConnectInfo(peer));
+    let connection = builder.serve_connection_with_upgrades(TokioIo::new(stream), TowerToHyperService::new(service));
+    tokio::pin!(connection);
+    // Error di sini (klien memutus di tengah request) hanya menyangkut koneksi itu
+    tokio::select! {
+        _ = connection.as_mut() => {}
+        () = shutdown.requested() => {
+            connection.as_mut().graceful_shutdown();
+            let _ = connection.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Method;
+    use std::collections::BTreeSet;
+    use tower::ServiceExt;
+
+    const METHODS: [Method; 5] = [Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE];
+
+    fn app_state(notes: Notes, accounts: Accounts, shares: Shares) -> AppState {
+        AppState {
+            notes: Arc::new(Mutex::new(notes)),
+            accounts: Arc::new(Mutex::new(accounts)),
+            shares: Arc::new(Mutex::new(shares)),
+            events: Events::default(),
+            collab: Collab::default(),
+            storage: Storage::start().unwrap(),
+            shutdown: Shutdown::default(),
+            cookies: Cookies::default(),
+            logins: RateLimiter::new(LOGIN_FAILURES_PER_MINUTE),
+        }
+    }
+
+    fn router(state: AppState) -> Router {
+        api_routes()
+            .into_iter()
+            .fold(Router::new(), |router, (path, route)| router.route(path, route))
+            .with_state(state)
+    }
+
+    /// User tanpa password (test memakai API token, bukan login)
+    fn user(name: &str, admin: bool) -> User {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "password_hash": "",
+            "admin": admin,
+            "created_at": "",
+        }))
+        .unwrap()
+    }
+
+    /// Notebook untuk test handler. alice (admin) memiliki semua note; bob boleh membaca
+    /// note 2 dan mengubah note 3; carol tidak punya akses. Note 4 terkunci.
+    struct Fixture {
+        state: AppState,
+        router: Router,
+        tokens: BTreeMap<&'static str, String>,
+    }
+
+    fn fixture() -> Fixture {
+        notes::test_data_dir();
+        let mut notes = Notes::new();
+        for content in ["private", "read only for bob", "writable by bob", ""] {
+            notes.add(content.to_string());
+        }
+        notes.get_mut(4).unwrap().locked = Some(crypto::Sealed {
+            kdf: "argon2id".to_string(),
+            cipher: "xchacha20poly1305".to_string(),
+            salt: String::new(),
+            nonce: String::new(),
+            ciphertext: String::new(),
+        });
+        let uid = |id: usize| notes.get(id).unwrap().uid.clone();
+
+        let mut accounts = Accounts::default();
+        accounts.users = vec![user("alice", true), user("bob", false), user("carol", false)];
+        let mut tokens = BTreeMap::new();
+        for name in ["alice", "bob", "carol"] {
+            tokens.insert(name, accounts.create_token(name, "test").unwrap().1);
+        }
+        let mut shares = Shares::default();
+        shares.grant("alice", "bob", Target::Note(uid(2)), Access::Read);
+        shares.grant("alice", "bob", Target::Note(uid(3)), Access::Write);
+
+        let state = app_state(notes, accounts, shares);
+        Fixture {
+            router: router(state.clone()),
+            state,
+            tokens,
+        }
+    }
+
+    impl Fixture {
+        /// Request sebagai `user` dengan body JSON opsional; return status dan body JSON
+        async fn call(&self, user: &str, method: Method, uri: &str, body: Option<serde_json::Value>) -> (StatusCode, serde_json::Value) {
+            let request = axum::http::Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(header::AUTHORIZATION, format!("Bearer {}", self.tokens[user]))
+                .header(header::CONTENT_TYPE, "application/json");
+            let body = body.map_or_else(Body::empty, |body| Body::from(body.to_string()));
+            let response = self.router.clone().oneshot(request.body(body).unwrap()).await.unwrap();
+            let status = response.status();
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+        }
+    }
+
+    #[tokio::test]
+    async fn patch_reports_every_invalid_field() {
+        let app = fixture();
+        let body = serde_json::json!({
+            "content": "   ",
+            "tags": ["ok", "two words", "#"],
+            "notebook": "n".repeat(MAX_NOTEBOOK_CHARS + 1),
+        });
+        let (status, problem) = app.call("alice", Method::PATCH, "/api/notes/1", Some(body)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem["status"], 422);
+        let names: Vec<&str> = problem["invalid-params"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|param| param["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["content", "tags", "tags", "notebook"]);
+        // Tidak ada yang disimpan sebagian
+        let (_, note) = app.call("alice", Method::GET, "/api/notes/1", None).await;
+        assert_eq!(note["content"], "private");
+        assert!(note.get("tags").is_none_or(|tags| tags.as_array().unwrap().is_empty()));
+    }
+
+    #[tokio::test]
+    async fn hidden_notes_are_not_found_and_read_only_notes_forbidden() {
+        let app = fixture();
+        let patch = || Some(serde_json::json!({ "pinned": true }));
+        // Note orang lain sama saja dengan note yang tidak ada
+        let (hidden, hidden_problem) = app.call("carol", Method::GET, "/api/notes/2", None).await;
+        let (missing, missing_problem) = app.call("carol", Method::GET, "/api/notes/99", None).await;
+        assert_eq!((hidden, missing), (StatusCode::NOT_FOUND, StatusCode::NOT_FOUND));
+        assert_eq!(hidden_problem["title"], missing_problem["title"]);
+        assert_eq!(app.call("carol", Method::PATCH, "/api/notes/2", patch()).await.0, StatusCode::NOT_FOUND);
+        assert_eq!(app.call("bob", Method::GET, "/api/notes/1", None).await.0, StatusCode::NOT_FOUND);
+
+        assert_eq!(app.call("bob", Method::GET, "/api/notes/2", None).await.0, StatusCode::OK);
+        let (status, problem) = app.call("bob", Method::PATCH, "/api/notes/2", patch()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(problem["status"], 403);
+        assert_eq!(app.call("bob", Method::DELETE, "/api/notes/2", None).await.0, StatusCode::FORBIDDEN);
+        assert_eq!(app.call("bob", Method::PATCH, "/api/notes/3", patch()).await.0, StatusCode::OK);
+        assert!(lock_notes(&app.state.notes).unwrap().get(3).unwrap().pinned);
+    }
+
+    #[tokio::test]
+    async fn only_the_owner_moves_a_note_to_another_notebook() {
+        let app = fixture();
+        let (status, problem) = app.call("bob", Method::PATCH, "/api/notes/3", Some(serde_json::json!({ "notebook": "shared" }))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(problem["status"], 403);
+        // Tidak ada perubahan sebagian dari request yang ditolak
+        let rejected = serde_json::json!({ "content": "edited", "notebook": "shared" });
+        assert_eq!(app.call("bob", Method::PATCH, "/api/notes/3", Some(rejected)).await.0, StatusCode::FORBIDDEN);
+        let note = lock_notes(&app.state.notes).unwrap().get(3).unwrap().clone();
+        assert_eq!((note.content.as_str(), note.notebook), ("writable by bob", None));
+
+        // Penerima grant tetap boleh mengirim notebook yang sama
+        let replace = serde_json::json!({ "content": "edited", "notebook": null });
+        assert_eq!(app.call("bob", Method::PUT, "/api/notes/3", Some(replace)).await.0, StatusCode::OK);
+        let (status, note) = app.call("alice", Method::PATCH, "/api/notes/3", Some(serde_json::json!({ "notebook": "shared" }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(note["notebook"], "shared");
+    }
+
+    #[test]
+    fn events_only_reach_users_who_can_read_the_note() {
+        let app = fixture();
+        let (accounts, shares) = (&app.state.accounts, &app.state.shares);
+        let note = |id: usize| lock_notes(&app.state.notes).unwrap().get(id).unwrap().clone();
+        let mut bobs = note(1);
+        bobs.owner = Some("bob".to_string());
+        let visible = |event: &NoteEvent, user: &str| event_visible(event, user, accounts, shares);
+
+        let private = NoteEvent::Updated(note(1));
+        let shared = NoteEvent::Created(note(2));
+        let deleted = NoteEvent::Deleted(note(3));
+        assert_eq!(visible(&private, "alice"), Some(true));
+        assert_eq!(visible(&private, "bob"), Some(false));
+        assert_eq!(visible(&shared, "bob"), Some(true));
+        assert_eq!(visible(&deleted, "bob"), Some(true));
+        assert_eq!(visible(&shared, "carol"), Some(false));
+        // Admin memiliki note tanpa owner, bukan note user lain
+        assert_eq!(visible(&NoteEvent::Updated(bobs.clone()), "alice"), Some(false));
+        assert_eq!(visible(&NoteEvent::Updated(bobs), "bob"), Some(true));
+
+        let reload_carol = NoteEvent::Reload(Some(vec!["carol".to_string()]));
+        assert_eq!(visible(&reload_carol, "carol"), Some(true));
+        assert_eq!(visible(&reload_carol, "bob"), Some(false));
+        assert_eq!(visible(&NoteEvent::Reload(None), "bob"), Some(true));
+
+        // Share yang dicabut langsung berlaku untuk event berikutnya
+        let mut all = lock_shares(shares).unwrap();
+        let grant = all.grants.iter().find(|grant| grant.target == Target::Note(note(2).uid)).unwrap().id.clone();
+        assert!(all.revoke(&grant, "alice"));
+        drop(all);
+        assert_eq!(visible(&shared, "bob"), Some(false));
+        assert_eq!(visible(&deleted, "bob"), Some(true));
+
+        // User yang dihapus tidak menerima apa pun lagi
+        lock_accounts(accounts).unwrap().remove_user("bob");
+        assert_eq!(visible(&deleted, "bob"), None);
+        assert_eq!(visible(&NoteEvent::Reload(None), "bob"), None);
+    }
+
+    /// Koneksi edit bersama `user` ke note `id`, tanpa WebSocket
+    fn live_edit(app: &Fixture, user: &str, id: usize) -> (LiveEdit, u64) {
+        let state = app.state.clone();
+        let notes = Arc::clone(&state.notes);
+        let session = state
+            .collab
+            .session(id, || Ok::<_, Problem>(lock_notes(&notes)?.get(id).unwrap().content.clone()))
+            .unwrap();
+        let (client, _, _) = collab::lock_session(&session).join(user, false);
+        let live = LiveEdit {
+            id,
+            user: user.to_string(),
+            session,
+            collab: state.collab,
+            notes: state.notes,
+            accounts: state.accounts,
+            shares: state.shares,
+            events: state.events,
+            storage: state.storage,
+            shutdown: state.shutdown,
+        };
+        (live, client)
+    }
+
+    /// Ketik `text` di akhir dokumen session
+    fn type_text(live: &LiveEdit, client: u64, text: &str) -> Result<(), String> {
+        let (revision, len) = {
+            let session = collab::lock_session(&live.session);
+            (session.revision(), session.content.chars().count())
+        };
+        let message = serde_json::json!({ "type": "op", "revision": revision, "op": [len, text] });
+        live.handle(client, &message.to_string())
+    }
+
+    fn content(app: &Fixture, id: usize) -> String {
+        lock_notes(&app.state.notes).unwrap().get(id).unwrap().content.clone()
+    }
+
+    #[tokio::test]
+    async fn live_edits_need_current_write_access() {
+        let app = fixture();
+        let (live, client) = live_edit(&app, "bob", 3);
+        type_text(&live, client, " once").unwrap();
+        live.persist(&mut collab::lock_session(&live.session));
+        assert_eq!(content(&app, 3), "writable by bob once");
+
+        // Write dicabut saat bob masih terhubung: operasi berikutnya ditolak...
+        type_text(&live, client, " twice").unwrap();
+        let uid = lock_notes(&app.state.notes).unwrap().get(3).unwrap().uid.clone();
+        lock_shares(&app.state.shares).unwrap().grant("alice", "bob", Target::Note(uid), Access::Read);
+        assert!(type_text(&live, client, " thrice").is_err());
+        // ...dan yang sudah diketik tapi belum tersimpan tidak ditulis ke note
+        live.persist(&mut collab::lock_session(&live.session));
+        assert_eq!(content(&app, 3), "writable by bob once");
+        assert_eq!(collab::lock_session(&live.session).content, "writable by bob once");
+    }
+
+    #[tokio::test]
+    async fn live_session_does_not_overwrite_outside_changes() {
+        let app = fixture();
+        let (live, client) = live_edit(&app, "alice", 1);
+        type_text(&live, client, " typed").unwrap();
+        lock_notes(&app.state.notes).unwrap().get_mut(1).unwrap().content = "changed elsewhere".to_string();
+
+        live.persist(&mut collab::lock_session(&live.session));
+        assert_eq!(content(&app, 1), "changed elsewhere");
+        let session = collab::lock_session(&live.session);
+        assert_eq!((session.content.as_str(), session.saved.as_str()), ("changed elsewhere", "changed elsewhere"));
+    }
+
+    #[tokio::test]
+    async fn resolve_waits_for_live_session() {
+        let app = fixture();
+        lock_notes(&app.state.notes).unwrap().get_mut(1).unwrap().conflict = Some(notes::Conflict {
+            base: None,
+            ours: "private".to_string(),
+            theirs: "theirs".to_string(),
+            theirs_updated_at: String::new(),
+            detected_at: String::new(),
+        });
+        let (live, client) = live_edit(&app, "alice", 1);
+        let resolved = || Some(serde_json::json!({ "content": "merged" }));
+        let (status, problem) = app.call("alice", Method::POST, "/api/notes/1/resolve", resolved()).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(problem["status"], 409);
+        assert!(lock_notes(&app.state.notes).unwrap().get(1).unwrap().has_conflict());
+
+        app.state.collab.leave(1, client, |session| live.persist(session));
+        assert_eq!(app.call("alice", Method::POST, "/api/notes/1/resolve", resolved()).await.0, StatusCode::OK);
+        assert_eq!(content(&app, 1), "merged");
+    }
+
+    #[tokio::test]
+    async fn sync_merges_unsaved_live_edits_and_resets_changed_sessions() {
+        let app = fixture();
+        let (typing, client) = live_edit(&app, "alice", 1);
+        type_text(&typing, client, " typed").unwrap();
+        let (watching, _) = live_edit(&app, "bob", 3);
+
+        // Device lain mengubah note 3
+        let mut payload = sync::payload(&lock_notes(&app.state.notes).unwrap());
+        let remote = payload.notes.iter_mut().find(|note| note.id == 3).unwrap();
+        remote.content = "edited on another device".to_string();
+        *remote.version.entry("remote".to_string()).or_insert(0) += 1;
+        let body = serde_json::to_value(&payload).unwrap();
+        let (status, merged) = app.call("alice", Method::POST, "/api/sync", Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let notes = merged["notes"].as_array().unwrap();
+        let merged_content = |id: usize| notes.iter().find(|note| note["id"] == id).unwrap()["content"].clone();
+        assert_eq!(merged_content(1), "private typed");
+        assert_eq!(merged_content(3), "edited on another device");
+        assert_eq!(content(&app, 1), "private typed");
+        let typing = collab::lock_session(&typing.session);
+        assert_eq!((typing.content.as_str(), typing.saved.as_str()), ("private typed", "private typed"));
+        assert_eq!(collab::lock_session(&watching.session).content, "edited on another device");
+    }
+
+    #[tokio::test]
+    async fn locked_note_content_cannot_change() {
+        let app = fixture();
+        let content = Some(serde_json::json!({ "content": "new text" }));
+        let (status, problem) = app.call("alice", Method::PATCH, "/api/notes/4", content).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(problem["status"], 409);
+        let replace = Some(serde_json::json!({ "content": "new text", "pinned": true }));
+        assert_eq!(app.call("alice", Method::PUT, "/api/notes/4", replace).await.0, StatusCode::CONFLICT);
+        // Metadata note terkunci tetap bisa diubah
+        let pin = Some(serde_json::json!({ "pinned": true }));
+        let (status, note) = app.call("alice", Method::PATCH, "/api/notes/4", pin).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(note["pinned"], true);
+    }
+
+    /// Path axum (`/api/notes/:id`) dalam format OpenAPI (`/api/notes/{id}`)
+    fn openapi_path(path: &str) -> String {
+        path.split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(param) => format!("{{{}}}", param),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Apakah router punya handler untuk method + path ini. Request dikirim tanpa body
+    /// dan tanpa login, jadi handler menolaknya (401/415) sebelum menyentuh notes.
+    async fn is_routed(router: &Router, method: &Method, path: &str) -> bool {
+        let uri = path
+            .replace(":id", "1")
+            .replace(":hash", &"0".repeat(64))
+            .replace(":token_id", "0")
+            .replace(":share_id", "0")
+            .replace(":link_id", "0")
+            .replace(":token", "0");
+        let request = axum::http::Request::builder()
+            .method(method.clone())
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        // 405 dari method router, 404 tanpa problem+json dari fallback router
+        let from_handler = response.headers().get(header::CONTENT_TYPE).is_some();
+        response.status() != StatusCode::METHOD_NOT_ALLOWED
+            && (response.status() != StatusCode::NOT_FOUND || from_handler)
+    }
+
+    #[tokio::test]
+    async fn routes_match_openapi_spec() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let spec_paths = spec["paths"].as_object().unwrap();
+
+        let routes = api_routes();
+        let route_paths: BTreeSet<String> = routes.iter().map(|(path, _)| openapi_path(path)).collect();
+        let documented: BTreeSet<String> = spec_paths.keys().cloned().collect();
+        assert_eq!(route_paths, documented, "paths in api_routes() and ApiDoc differ");
+
+        let router = router(app_state(Notes::new(), Accounts::default(), Shares::default()));
+        for (path, _) in api_routes() {
+            let operations = &spec_paths[&openapi_path(path)];
+            for method in &METHODS {
+                let documented = operations.get(method.as_str().to_lowercase()).is_some();
+                let routed = is_routed(&router, method, path).await;
+                assert_eq!(routed, documented, "{} {}: routed = {}, documented = {}", method, path, routed, documented);
+            }
+        }
+    }
+}
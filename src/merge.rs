@@ -0,0 +1,262 @@
+// Three-way merge (diff3) per baris untuk menyelesaikan konflik note
+// base = versi terakhir yang sama-sama diketahui, ours/theirs = dua versi yang berbeda
+
+#![allow(dead_code)]
+
+use std::io::{self, Write};
+use std::process::Command;
+
+pub const MARKER_OURS: &str = "<<<<<<< ours";
+pub const MARKER_BASE: &str = "||||||| base";
+pub const MARKER_SEP: &str = "=======";
+pub const MARKER_THEIRS: &str = ">>>>>>> theirs";
+/// Batas ukuran tabel LCS (baris base × baris versi lain). Di atas ini seluruh note
+/// dianggap satu bagian: dua note 10k baris tidak perlu tabel 100 juta sel.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+pub struct MergeResult {
+    pub text: String,
+    /// false kalau ada bagian yang diubah di kedua sisi (ada conflict marker)
+    pub clean: bool,
+}
+
+/// Untuk setiap baris di `a`, index baris yang cocok di `b` menurut LCS
+fn lcs_matches(a: &[&str], b: &[&str]) -> Vec<Option<usize>> {
+    let mut table = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut matches = vec![None; a.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            matches[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+fn resolve_chunk(out: &mut Vec<String>, base: &[&str], ours: &[&str], theirs: &[&str]) -> bool {
+    let lines: &[&str] = if ours == base {
+        theirs
+    } else if theirs == base || ours == theirs {
+        ours
+    } else {
+        out.push(MARKER_OURS.to_string());
+        out.extend(ours.iter().map(|l| l.to_string()));
+        out.push(MARKER_BASE.to_string());
+        out.extend(base.iter().map(|l| l.to_string()));
+        out.push(MARKER_SEP.to_string());
+        out.extend(theirs.iter().map(|l| l.to_string()));
+        out.push(MARKER_THEIRS.to_string());
+        return false;
+    };
+    out.extend(lines.iter().map(|l| l.to_string()));
+    true
+}
+
+pub fn merge3(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    let base: Vec<&str> = base.lines().collect();
+    let ours: Vec<&str> = ours.lines().collect();
+    let theirs: Vec<&str> = theirs.lines().collect();
+    let too_large = |other: &[&str]| (base.len() + 1).saturating_mul(other.len() + 1) > MAX_LCS_CELLS;
+    if too_large(&ours) || too_large(&theirs) {
+        let mut out = Vec::new();
+        let clean = resolve_chunk(&mut out, &base, &ours, &theirs);
+        return MergeResult {
+            text: out.join("\n"),
+            clean,
+        };
+    }
+    let to_ours = lcs_matches(&base, &ours);
+    let to_theirs = lcs_matches(&base, &theirs);
+
+    let mut out = Vec::new();
+    let mut clean = true;
+    let (mut b, mut o, mut t) = (0, 0, 0);
+    loop {
+        // Cari baris base berikutnya yang tidak berubah di kedua sisi (anchor)
+        let anchor = (b..base.len()).find_map(|k| match (to_ours[k], to_theirs[k]) {
+            (Some(ok), Some(tk)) => Some((k, ok, tk)),
+            _ => None,
+        });
+        match anchor {
+            Some((k, ok, tk)) if k == b && ok == o && tk == t => {
+                out.push(base[k].to_string());
+                b += 1;
+                o += 1;
+                t += 1;
+            }
+            Some((k, ok, tk)) => {
+                clean &= resolve_chunk(&mut out, &base[b..k], &ours[o..ok], &theirs[t..tk]);
+                b = k;
+                o = ok;
+                t = tk;
+            }
+            None => {
+                clean &= resolve_chunk(&mut out, &base[b..], &ours[o..], &theirs[t..]);
+                break;
+            }
+        }
+    }
+
+    MergeResult {
+        text: out.join("\n"),
+        clean,
+    }
+}
+
+pub fn has_markers(text: &str) -> bool {
+    text.lines()
+        .any(|line| [MARKER_OURS, MARKER_BASE, MARKER_SEP, MARKER_THEIRS].contains(&line))
+}
+
+/// Buka $EDITOR (default: vi) dengan teks awal, return isi file setelah editor ditutup.
+/// File sementara dibuat dengan nama acak dan O_EXCL, jadi symlink yang sudah disiapkan
+/// orang lain di temp dir tidak bisa dipakai untuk menimpa file lain.
+pub fn edit_in_editor(initial: &str) -> io::Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut file = tempfile::Builder::new()
+        .prefix("rust_app_merge_")
+        .suffix(".md")
+        .tempfile()?;
+    file.write_all(initial.as_bytes())?;
+    file.flush()?;
+
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(file.path())
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{} exited with {}", editor, status)));
+    }
+    // Dibaca lewat path: banyak editor menyimpan dengan menulis file baru lalu rename
+    std::fs::read_to_string(file.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merged(base: &str, ours: &str, theirs: &str) -> (String, bool) {
+        let result = merge3(base, ours, theirs);
+        (result.text, result.clean)
+    }
+
+    #[test]
+    fn clean_merges() {
+        let cases = [
+            // Perubahan di baris berbeda
+            ("a\nb\nc", "A\nb\nc", "a\nb\nC", "A\nb\nC"),
+            // Perubahan sama di kedua sisi
+            ("a\nb\nc", "a\nB\nc", "a\nB\nc", "a\nB\nc"),
+            // Hanya satu sisi berubah
+            ("a\nb", "a\nb", "a\nb\nc", "a\nb\nc"),
+            ("a\nb", "a", "a\nb", "a"),
+            // Sisipan di awal dan di akhir
+            ("a\nb", "start\na\nb", "a\nb\nend", "start\na\nb\nend"),
+            // Base kosong, satu sisi kosong juga
+            ("", "", "new", "new"),
+            ("", "same", "same", "same"),
+            // Tidak ada yang berubah
+            ("a\nb", "a\nb", "a\nb", "a\nb"),
+        ];
+        for (base, ours, theirs, expected) in cases {
+            assert_eq!(
+                merged(base, ours, theirs),
+                (expected.to_string(), true),
+                "base {:?}, ours {:?}, theirs {:?}",
+                base,
+                ours,
+                theirs
+            );
+        }
+    }
+
+    #[test]
+    fn conflicting_edits_get_markers() {
+        let (text, clean) = merged("a\nb\nc", "a\nours\nc", "a\ntheirs\nc");
+        assert!(!clean);
+        let expected = ["a", MARKER_OURS, "ours", MARKER_BASE, "b", MARKER_SEP, "theirs", MARKER_THEIRS, "c"];
+        assert_eq!(text, expected.join("\n"));
+        assert!(has_markers(&text));
+    }
+
+    #[test]
+    fn conflicts_at_start_end_and_empty_base() {
+        let conflict = |ours: &str, base: &str, theirs: &str| {
+            [MARKER_OURS, ours, MARKER_BASE, base, MARKER_SEP, theirs, MARKER_THEIRS]
+                .iter()
+                .filter(|line| !line.is_empty())
+                .copied()
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let cases = [
+            ("a\nb", "x\nb", "y\nb", format!("{}\nb", conflict("x", "a", "y"))),
+            ("a\nb", "a\nx", "a\ny", format!("a\n{}", conflict("x", "b", "y"))),
+            ("", "ours", "theirs", conflict("ours", "", "theirs")),
+        ];
+        for (base, ours, theirs, expected) in cases {
+            let (text, clean) = merged(base, ours, theirs);
+            assert!(!clean, "base {:?}, ours {:?}, theirs {:?}", base, ours, theirs);
+            assert_eq!(text, expected);
+            assert!(has_markers(&text));
+        }
+    }
+
+    #[test]
+    fn conflict_keeps_clean_parts_merged() {
+        let (text, clean) = merged(
+            "a\nb\nc\nd\ne",
+            "A\nb\nours\nd\ne",
+            "a\nb\ntheirs\nd\nE",
+        );
+        assert!(!clean);
+        assert!(text.starts_with(&format!("A\nb\n{}\nours\n", MARKER_OURS)));
+        assert!(text.ends_with(&format!("theirs\n{}\nd\nE", MARKER_THEIRS)));
+    }
+
+    #[test]
+    fn large_notes_fall_back_to_one_block() {
+        let lines = |prefix: &str| (0..3000).map(|i| format!("{} {}", prefix, i)).collect::<Vec<_>>().join("\n");
+        let base = lines("line");
+        let ours = format!("{}\nours", base);
+        let theirs = format!("theirs\n{}", base);
+
+        // Edit yang biasanya bisa digabung: tanpa tabel LCS menjadi satu konflik besar
+        let (text, clean) = merged(&base, &ours, &theirs);
+        assert!(!clean);
+        assert!(text.starts_with(&format!("{}\n{}", MARKER_OURS, ours)));
+        assert!(text.ends_with(&format!("{}\n{}\n{}", MARKER_SEP, theirs, MARKER_THEIRS)));
+
+        // Satu sisi tidak berubah tetap bersih tanpa LCS
+        assert_eq!(merged(&base, &base, &theirs), (theirs.clone(), true));
+        assert_eq!(merged(&base, &ours, &ours), (ours.clone(), true));
+    }
+
+    #[test]
+    fn detects_each_marker() {
+        for marker in [MARKER_OURS, MARKER_BASE, MARKER_SEP, MARKER_THEIRS] {
+            assert!(has_markers(&format!("text\n{}\nmore", marker)), "{}", marker);
+        }
+        assert!(!has_markers("text\n<<<<<<< not a marker\n== heading"));
+    }
+}
@@ -0,0 +1,515 @@
+// Note store yang dipakai bersama oleh CLI, TUI dan Web server
+// Semua binary membaca/menulis file yang sama: <data dir>/notes.json
+// Data dir default = working directory, bisa diganti lewat env RUST_APP_DATA_DIR
+// Kalau notebook dienkripsi, isinya ada di notes.json.enc (lihat crypto.rs)
+// Kalau ada repo notes_git/, note disimpan sebagai file Markdown di git (lihat git_store.rs)
+
+#![allow(dead_code)]
+
+use crate::crypto::{self, Sealed};
+use crate::git_store;
+use crate::links::{self, LinkTarget};
+use crate::merge::{self, MergeResult};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use utoipa::ToSchema;
+
+pub const NOTES_FILE: &str = "notes.json";
+pub const ENCRYPTED_NOTES_FILE: &str = "notes.json.enc";
+pub const DATA_DIR_ENV: &str = "RUST_APP_DATA_DIR";
+pub const DEVICE_ID_FILE: &str = "device_id";
+
+/// Version vector: device id -> jumlah perubahan dari device tersebut
+pub type Version = BTreeMap<String, u64>;
+
+/// Passphrase notebook yang sedang terbuka (hanya di memory)
+static PASSPHRASE: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct Note {
+    pub id: usize,
+    pub content: String,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+    /// Isi note yang dikunci dengan passphrase sendiri; `content` dikosongkan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked: Option<Sealed>,
+    /// Identitas global note untuk sync (id angka hanya berlaku lokal)
+    #[serde(default)]
+    pub uid: String,
+    #[serde(default)]
+    pub version: Version,
+    #[serde(default)]
+    pub updated_at: String,
+    /// Content saat sync terakhir, dipakai sebagai base three-way merge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Ada dua versi yang diedit terpisah dan belum diselesaikan user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<Conflict>,
+    /// Note yang di-pin selalu tampil paling atas
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notebook: Option<String>,
+    /// Note yang diarsipkan disembunyikan dari list biasa
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    /// Pemilik note di web server (lihat accounts.rs); None untuk note lokal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct Conflict {
+    pub base: Option<String>,
+    pub ours: String,
+    pub theirs: String,
+    pub theirs_updated_at: String,
+    pub detected_at: String,
+}
+
+/// Bekas note yang sudah dihapus, disimpan supaya penghapusan ikut ter-sync
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct Tombstone {
+    pub uid: String,
+    pub version: Version,
+    pub deleted_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct Attachment {
+    pub name: String,
+    /// SHA-256 dari isi file, sekaligus nama blob di folder attachments/
+    pub hash: String,
+    pub size: u64,
+    pub added_at: String,
+}
+
+impl Note {
+    pub fn title(&self) -> &str {
+        links::title_of(&self.content)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked.is_some()
+    }
+
+    /// Content untuk ditampilkan; locked note tidak pernah menampilkan isinya
+    pub fn display_content(&self) -> Cow<'_, str> {
+        if self.is_locked() {
+            Cow::Owned(format!("🔒 {}", t!("locked-note")))
+        } else {
+            Cow::Borrowed(&self.content)
+        }
+    }
+
+    /// Catat perubahan lokal: naikkan counter device ini di version vector
+    pub fn touch(&mut self) {
+        *self.version.entry(device_id().to_string()).or_insert(0) += 1;
+        self.updated_at = now_timestamp();
+    }
+
+    pub fn has_conflict(&self) -> bool {
+        self.conflict.is_some()
+    }
+
+    /// Hasil three-way merge otomatis dari conflict record
+    pub fn merge_preview(&self) -> Option<MergeResult> {
+        let conflict = self.conflict.as_ref()?;
+        Some(merge::merge3(
+            conflict.base.as_deref().unwrap_or(""),
+            &conflict.ours,
+            &conflict.theirs,
+        ))
+    }
+
+    /// Selesaikan konflik dengan content pilihan user (ours, theirs, atau hasil merge)
+    pub fn resolve_conflict(&mut self, content: String) {
+        self.content = content;
+        self.conflict = None;
+        self.touch();
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Tanggal dibuat (YYYY-MM-DD), dipakai untuk filter --before/--after
+    pub fn created_date(&self) -> Option<chrono::NaiveDate> {
+        self.created_at
+            .get(..10)
+            .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+    }
+
+    /// Ganti isi note; return false kalau isinya sama
+    pub fn edit(&mut self, content: String) -> bool {
+        if self.content == content {
+            return false;
+        }
+        self.content = content;
+        self.touch();
+        true
+    }
+
+    pub fn lock(&mut self, passphrase: &str) -> Result<(), crypto::CryptoError> {
+        if self.is_locked() {
+            return Ok(());
+        }
+        self.locked = Some(crypto::seal(passphrase, self.content.as_bytes())?);
+        self.content.clear();
+        self.touch();
+        Ok(())
+    }
+
+    /// Dekripsi isi locked note tanpa mengubah note
+    pub fn reveal(&self, passphrase: &str) -> Result<String, crypto::CryptoError> {
+        match &self.locked {
+            Some(sealed) => {
+                let bytes = crypto::open(passphrase, sealed)?;
+                String::from_utf8(bytes)
+                    .map_err(|_| crypto::CryptoError::Format("content is not UTF-8".to_string()))
+            }
+            None => Ok(self.content.clone()),
+        }
+    }
+
+    /// Buka kunci note secara permanen
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), crypto::CryptoError> {
+        self.content = self.reveal(passphrase)?;
+        self.locked = None;
+        self.touch();
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Notes {
+    pub notes: Vec<Note>,
+    pub next_id: usize,
+    /// Index backlink: id note tujuan -> id note yang me-link ke sana.
+    /// Tidak disimpan ke file, dibangun ulang setiap load/mutasi.
+    #[serde(skip)]
+    backlinks: HashMap<usize, BTreeSet<usize>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tombstones: Vec<Tombstone>,
+}
+
+impl Notes {
+    pub fn new() -> Self {
+        Notes {
+            notes: Vec::new(),
+            next_id: 1,
+            backlinks: HashMap::new(),
+            tombstones: Vec::new(),
+        }
+    }
+
+    /// Beri uid dan version ke note lama yang dibuat sebelum ada fitur sync
+    pub fn ensure_sync_metadata(&mut self) {
+        for note in &mut self.notes {
+            if note.uid.is_empty() {
+                note.uid = new_uid();
+            }
+            if note.version.is_empty() {
+                note.version.insert(device_id().to_string(), 1);
+            }
+            if note.updated_at.is_empty() {
+                note.updated_at = note.created_at.clone();
+            }
+        }
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Note> {
+        self.notes.iter().find(|note| note.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut Note> {
+        self.notes.iter_mut().find(|note| note.id == id)
+    }
+
+    pub fn conflicts(&self) -> Vec<&Note> {
+        self.notes.iter().filter(|note| note.has_conflict()).collect()
+    }
+
+    /// Cari id note yang dituju sebuah link (judul dibandingkan case-insensitive)
+    pub fn resolve(&self, target: &LinkTarget) -> Option<usize> {
+        match target {
+            LinkTarget::Id(id) => self.get(*id).map(|note| note.id),
+            LinkTarget::Title(title) => self
+                .notes
+                .iter()
+                .find(|note| note.title().eq_ignore_ascii_case(title))
+                .map(|note| note.id),
+        }
+    }
+
+    /// Link keluar dari sebuah note beserta id tujuannya (None = broken link)
+    pub fn links_from(&self, id: usize) -> Option<Vec<(LinkTarget, Option<usize>)>> {
+        let note = self.get(id)?;
+        Some(
+            links::parse_links(&note.content)
+                .into_iter()
+                .map(|target| {
+                    let resolved = self.resolve(&target);
+                    (target, resolved)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn backlinks(&self, id: usize) -> Vec<usize> {
+        self.backlinks
+            .get(&id)
+            .map(|ids| ids.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Semua link yang tidak bisa di-resolve: (id note sumber, target)
+    pub fn broken_links(&self) -> Vec<(usize, LinkTarget)> {
+        self.notes
+            .iter()
+            .flat_map(|note| {
+                links::parse_links(&note.content)
+                    .into_iter()
+                    .filter(|target| self.resolve(target).is_none())
+                    .map(move |target| (note.id, target))
+            })
+            .collect()
+    }
+
+    pub fn rebuild_links(&mut self) {
+        let mut backlinks: HashMap<usize, BTreeSet<usize>> = HashMap::new();
+        for note in &self.notes {
+            for target in links::parse_links(&note.content) {
+                if let Some(to) = self.resolve(&target) {
+                    if to != note.id {
+                        backlinks.entry(to).or_default().insert(note.id);
+                    }
+                }
+            }
+        }
+        self.backlinks = backlinks;
+    }
+
+    pub fn add(&mut self, content: String) -> &Note {
+        let note = Note {
+            id: self.next_id,
+            content,
+            created_at: now_timestamp(),
+            attachments: Vec::new(),
+            locked: None,
+            uid: new_uid(),
+            version: Version::from([(device_id().to_string(), 1)]),
+            updated_at: now_timestamp(),
+            base: None,
+            conflict: None,
+            pinned: false,
+            tags: Vec::new(),
+            notebook: None,
+            archived: false,
+            owner: None,
+        };
+        self.notes.push(note);
+        self.next_id += 1;
+        self.rebuild_links();
+        self.notes.last().unwrap()
+    }
+
+    pub fn delete(&mut self, id: usize) -> bool {
+        let Some(index) = self.notes.iter().position(|note| note.id == id) else {
+            return false;
+        };
+        let mut note = self.notes.remove(index);
+        note.touch();
+        self.tombstones.push(Tombstone {
+            uid: note.uid,
+            version: note.version,
+            deleted_at: now_timestamp(),
+        });
+        self.rebuild_links();
+        true
+    }
+
+    /// Kembalikan note yang baru dihapus (undo). Tombstone-nya dibuang dan
+    /// version dinaikkan supaya note ini menang dari tombstone yang sudah ter-sync.
+    /// Return id note; id baru dipakai kalau id lama sudah terisi.
+    pub fn restore(&mut self, mut note: Note) -> usize {
+        if let Some(index) = self.tombstones.iter().position(|t| t.uid == note.uid) {
+            let tombstone = self.tombstones.remove(index);
+            for (device, count) in tombstone.version {
+                let entry = note.version.entry(device).or_insert(0);
+                *entry = (*entry).max(count);
+            }
+        }
+        note.touch();
+        if self.get(note.id).is_some() {
+            note.id = self.next_id;
+            self.next_id += 1;
+        }
+        let id = note.id;
+        let index = self.notes.partition_point(|other| other.id < id);
+        self.notes.insert(index, note);
+        self.rebuild_links();
+        id
+    }
+}
+
+pub fn data_dir() -> PathBuf {
+    std::env::var_os(DATA_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub fn data_path(name: &str) -> PathBuf {
+    data_dir().join(name)
+}
+
+/// Data dir sementara untuk unit test, satu untuk seluruh proses test: env var berlaku
+/// untuk semua thread, jadi setiap test harus memakai folder yang sama
+#[cfg(test)]
+pub fn test_data_dir() -> &'static Path {
+    static DIR: OnceLock<tempfile::TempDir> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var(DATA_DIR_ENV, dir.path());
+        dir
+    })
+    .path()
+}
+
+pub fn new_uid() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Id unik device ini, dibuat sekali dan disimpan di <data dir>/device_id
+pub fn device_id() -> &'static str {
+    static DEVICE_ID: OnceLock<String> = OnceLock::new();
+    DEVICE_ID.get_or_init(|| {
+        let path = data_path(DEVICE_ID_FILE);
+        if let Ok(id) = fs::read_to_string(&path) {
+            if !id.trim().is_empty() {
+                return id.trim().to_string();
+            }
+        }
+        let id = new_uid();
+        if let Err(e) = fs::write(&path, &id) {
+            eprintln!("⚠️  {}", t!("device-id-failed", path = path.display().to_string(), error = e.to_string()));
+        }
+        id
+    })
+}
+
+pub fn now_timestamp() -> String {
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+pub fn is_encrypted() -> bool {
+    data_path(ENCRYPTED_NOTES_FILE).exists()
+}
+
+/// Set passphrase notebook (misalnya dari form unlock di web) sebelum load_notes()
+pub fn set_passphrase(passphrase: String) {
+    *PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()) = Some(passphrase);
+}
+
+pub fn current_passphrase() -> io::Result<String> {
+    let mut guard = PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(passphrase) = guard.as_ref() {
+        return Ok(passphrase.clone());
+    }
+    let passphrase = crypto::read_passphrase(&t!("prompt-notebook-passphrase"))?;
+    *guard = Some(passphrase.clone());
+    Ok(passphrase)
+}
+
+fn parse_notes(json: &str) -> Notes {
+    serde_json::from_str(json).unwrap_or_else(|_| Notes::new())
+}
+
+pub fn try_load_notes() -> Result<Notes, Box<dyn Error>> {
+    if git_store::is_git_backed() && is_encrypted() {
+        return Err("both notes_git/ and notes.json.enc exist; git storage cannot be encrypted".into());
+    }
+    let mut notes = if git_store::is_git_backed() {
+        git_store::load()?
+    } else if is_encrypted() {
+        let sealed: Sealed = serde_json::from_str(&fs::read_to_string(data_path(ENCRYPTED_NOTES_FILE))?)?;
+        let json = crypto::open(&current_passphrase()?, &sealed)?;
+        parse_notes(&String::from_utf8(json)?)
+    } else {
+        match fs::read_to_string(data_path(NOTES_FILE)) {
+            Ok(content) => parse_notes(&content),
+            Err(_) => Notes::new(),
+        }
+    };
+    notes.ensure_sync_metadata();
+    notes.rebuild_links();
+    Ok(notes)
+}
+
+/// Load notes; kalau notebook terenkripsi/git tidak bisa dibuka, keluar daripada
+/// menimpa data lama dengan notebook kosong saat save berikutnya
+pub fn load_notes() -> Notes {
+    match try_load_notes() {
+        Ok(notes) => notes,
+        Err(e) => {
+            eprintln!("❌ {}", t!("open-notebook-failed", error = e.to_string()));
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)
+}
+
+fn write_encrypted(notes: &Notes, passphrase: &str) -> io::Result<()> {
+    let json = serde_json::to_string(notes)?;
+    let sealed = crypto::seal(passphrase, json.as_bytes())
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    let content = serde_json::to_string_pretty(&sealed)?;
+    write_atomic(&data_path(ENCRYPTED_NOTES_FILE), content.as_bytes())
+}
+
+pub fn save_notes(notes: &Notes) -> io::Result<()> {
+    if git_store::is_git_backed() {
+        return git_store::save(notes).map_err(|e| io::Error::other(e.to_string()));
+    }
+    if is_encrypted() {
+        return write_encrypted(notes, &current_passphrase()?);
+    }
+    // Atomic supaya TUI yang me-watch file tidak pernah membaca JSON setengah jadi
+    let json = serde_json::to_string_pretty(notes)?;
+    write_atomic(&data_path(NOTES_FILE), json.as_bytes())
+}
+
+/// Ubah notes.json plaintext menjadi notes.json.enc
+pub fn encrypt_notebook(notes: &Notes, passphrase: String) -> io::Result<()> {
+    write_encrypted(notes, &passphrase)?;
+    set_passphrase(passphrase);
+    match fs::remove_file(data_path(NOTES_FILE)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Kembalikan notebook terenkripsi menjadi notes.json plaintext
+pub fn decrypt_notebook(notes: &Notes) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(notes)?;
+    write_atomic(&data_path(NOTES_FILE), json.as_bytes())?;
+    fs::remove_file(data_path(ENCRYPTED_NOTES_FILE))?;
+    *PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    Ok(())
+}
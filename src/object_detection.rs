@@ -39,7 +39,7 @@ pub struct Detection {
 
 impl ObjectDetector {
     pub fn new(model_path: &str) -> Self {
-        println!("Loading model: {}", model_path);
+        println!("{}", t!("detect-loading-model", path = model_path));
         
         Self {
             model_path: model_path.to_string(),
@@ -48,16 +48,16 @@ impl ObjectDetector {
 
     pub fn detect_image(&self, image_path: &str) -> Result<Vec<Detection>, String> {
         if !Path::new(image_path).exists() {
-            return Err(format!("Image not found: {}", image_path));
+            return Err(t!("detect-image-not-found", path = image_path));
         }
 
-        println!("Detecting objects in: {}", image_path);
+        println!("{}", t!("detect-detecting", path = image_path));
         
         // Load image
         let img = image::open(image_path)
-            .map_err(|e| format!("Failed to load image: {}", e))?;
+            .map_err(|e| t!("detect-image-load-failed", error = e.to_string()))?;
         
-        println!("Image loaded: {}x{}", img.width(), img.height());
+        println!("{}", t!("detect-image-loaded", width = img.width(), height = img.height()));
         
         // Preprocess image
         let input_tensor = self.preprocess_image(&img)?;
@@ -93,7 +93,7 @@ impl ObjectDetector {
         // 3. Post-process outputs (NMS, etc.)
         // 4. Return detections
         
-        println!("Running inference (placeholder - implement with ONNX Runtime)");
+        println!("{}", t!("detect-inference-placeholder"));
         
         // Example output
         Ok(vec![
@@ -123,30 +123,30 @@ impl ObjectDetector {
         });
         
         fs::write(output_path, serde_json::to_string_pretty(&json).unwrap())
-            .map_err(|e| format!("Failed to save results: {}", e))?;
+            .map_err(|e| t!("detect-save-failed", error = e.to_string()))?;
         
-        println!("Results saved to: {}", output_path);
+        println!("{}", t!("detect-saved", path = output_path));
         Ok(())
     }
 
     #[cfg(feature = "webcam")]
     pub fn detect_webcam(&self, conf_threshold: f32) -> Result<(), String> {
         
-        println!("Opening webcam...");
+        println!("{}", t!("detect-webcam-opening"));
         
         // Open webcam (device 0)
         let mut cam = videoio::VideoCapture::new(0, videoio::CAP_ANY)
-            .map_err(|e| format!("Failed to open webcam: {}", e))?;
+            .map_err(|e| t!("detect-webcam-open-failed", error = e.to_string()))?;
         
         let opened = videoio::VideoCapture::is_opened(&cam)
-            .map_err(|e| format!("Failed to check webcam: {}", e))?;
+            .map_err(|e| t!("detect-webcam-check-failed", error = e.to_string()))?;
         
         if !opened {
             return Err("Cannot open webcam. Make sure webcam is connected.".to_string());
         }
         
-        println!("Webcam opened successfully!");
-        println!("Press 'q' to quit, 's' to save current frame");
+        println!("{}", t!("detect-webcam-opened"));
+        println!("{}", t!("detect-webcam-keys"));
         
         let mut frame_count = 0;
         let mut frame = core::Mat::default();
@@ -160,7 +160,7 @@ impl ObjectDetector {
                     }
                 }
                 Err(e) => {
-                    eprintln!("Error reading frame: {}", e);
+                    eprintln!("{}", t!("detect-frame-read-failed", error = e.to_string()));
                     break;
                 }
             }
@@ -178,7 +178,7 @@ impl ObjectDetector {
             // Print status every 30 frames to show it's working
             if frame_count % 30 == 0 {
                 if !detections.is_empty() {
-                    println!("\n📹 Frame {} - Detections:", frame_count);
+                    println!("\n📹 {}", t!("detect-frame-header", frame = frame_count));
                     for det in &detections {
                         println!("  ✅ {}", det);
                     }
@@ -200,7 +200,7 @@ impl ObjectDetector {
                             match highgui::wait_key(1) {
                                 Ok(key) => {
                                     if key == 113 || key == 27 { // 'q' or ESC
-                                        println!("\nQuitting...");
+                                        println!("\n{}", t!("detect-quitting"));
                                         break;
                                     }
                                     
@@ -209,9 +209,9 @@ impl ObjectDetector {
                                         let filename = format!("webcam_frame_{}.jpg", frame_count);
                                         use opencv::imgcodecs;
                                         if let Err(e) = imgcodecs::imwrite(&filename, &frame, &core::Vector::new()) {
-                                            eprintln!("Failed to save frame: {}", e);
+                                            eprintln!("{}", t!("detect-frame-save-failed", error = e.to_string()));
                                         } else {
-                                            println!("\nFrame saved to: {}", filename);
+                                            println!("\n{}", t!("detect-frame-saved", path = &filename));
                                         }
                                     }
                                 }
@@ -223,7 +223,7 @@ impl ObjectDetector {
                         Err(e) => {
                             // Window display failed, but continue processing
                             if frame_count % 30 == 0 {
-                                eprintln!("\nWarning: Cannot display window: {}. Processing frames...", e);
+                                eprintln!("\n{}", t!("detect-no-window", error = e.to_string()));
                             }
                         }
                     }
@@ -241,7 +241,7 @@ impl ObjectDetector {
             }
         }
         
-        println!("\nWebcam detection stopped. Processed {} frames.", frame_count);
+        println!("\n{}", t!("detect-webcam-stopped", count = frame_count));
         Ok(())
     }
 
@@ -256,7 +256,7 @@ impl ObjectDetector {
     fn detect_demo(&self, frame: &core::Mat) -> Result<Vec<Detection>, String> {
         use std::time::{SystemTime, UNIX_EPOCH};
         
-        let size = frame.size().map_err(|e| format!("Failed to get frame size: {}", e))?;
+        let size = frame.size().map_err(|e| t!("detect-frame-size-failed", error = e.to_string()))?;
         let width = size.width;
         let height = size.height;
         
@@ -381,7 +381,7 @@ impl ObjectDetector {
                 2,
                 imgproc::LINE_8,
                 0,
-            ).map_err(|e| format!("Failed to draw rectangle: {}", e))?;
+            ).map_err(|e| t!("detect-draw-failed", error = e.to_string()))?;
             
             // Draw label
             let label = format!("{}: {:.1}%", det.class, det.confidence * 100.0);
@@ -395,7 +395,7 @@ impl ObjectDetector {
                 1,
                 imgproc::LINE_8,
                 false,
-            ).map_err(|e| format!("Failed to draw text: {}", e))?;
+            ).map_err(|e| t!("detect-draw-failed", error = e.to_string()))?;
         }
         
         Ok(())
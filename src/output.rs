@@ -0,0 +1,440 @@
+// Output CLI untuk manusia (text) dan untuk script (json, jsonl, tsv)
+//
+// Schema machine-readable dianggap stabil: field hanya boleh ditambah di akhir,
+// tidak diganti nama atau dihapus. Dokumentasinya ada di README (Machine-readable Output).
+
+use crate::git_store::LogEntry;
+use crate::links::LinkTarget;
+use crate::notes::{Attachment, Note};
+use crate::sync::MergeReport;
+use crate::templates::Schedule;
+use crate::text::strip_icon;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::io::{IsTerminal, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Jsonl,
+    Tsv,
+}
+
+/// Satu baris output machine-readable. `COLUMNS` menentukan urutan kolom TSV.
+pub trait Record: Serialize {
+    const COLUMNS: &'static [&'static str];
+}
+
+#[derive(Debug, Serialize)]
+pub struct NoteRecord {
+    pub id: usize,
+    pub uid: String,
+    pub title: String,
+    /// null untuk locked note
+    pub content: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub locked: bool,
+    pub conflict: bool,
+    pub attachments: usize,
+    pub pinned: bool,
+    pub tags: Vec<String>,
+    pub notebook: Option<String>,
+    pub archived: bool,
+}
+
+impl From<&Note> for NoteRecord {
+    fn from(note: &Note) -> Self {
+        NoteRecord {
+            id: note.id,
+            uid: note.uid.clone(),
+            title: note.title().to_string(),
+            content: (!note.is_locked()).then(|| note.content.clone()),
+            created_at: note.created_at.clone(),
+            updated_at: note.updated_at.clone(),
+            locked: note.is_locked(),
+            conflict: note.has_conflict(),
+            attachments: note.attachments.len(),
+            pinned: note.pinned,
+            tags: note.tags.clone(),
+            notebook: note.notebook.clone(),
+            archived: note.archived,
+        }
+    }
+}
+
+impl Record for NoteRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "uid",
+        "title",
+        "content",
+        "created_at",
+        "updated_at",
+        "locked",
+        "conflict",
+        "attachments",
+        "pinned",
+        "tags",
+        "notebook",
+        "archived",
+    ];
+}
+
+/// Hasil perintah yang tidak mengembalikan data, misalnya delete atau lock
+#[derive(Debug, Serialize)]
+pub struct StatusRecord {
+    pub status: &'static str,
+    pub id: Option<usize>,
+    pub message: String,
+}
+
+impl Record for StatusRecord {
+    const COLUMNS: &'static [&'static str] = &["status", "id", "message"];
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkRecord {
+    pub from: usize,
+    /// Teks link seperti ditulis di note, misalnya [[Meeting]] atau [[#3]]
+    pub target: String,
+    /// Note yang dituju, null kalau link rusak
+    pub to: Option<usize>,
+}
+
+impl LinkRecord {
+    pub fn new(from: usize, target: &LinkTarget, to: Option<usize>) -> Self {
+        LinkRecord {
+            from,
+            target: target.to_string(),
+            to,
+        }
+    }
+}
+
+impl Record for LinkRecord {
+    const COLUMNS: &'static [&'static str] = &["from", "target", "to"];
+}
+
+impl Record for Attachment {
+    const COLUMNS: &'static [&'static str] = &["name", "hash", "size", "added_at"];
+}
+
+impl Record for MergeReport {
+    const COLUMNS: &'static [&'static str] = &[
+        "added",
+        "updated",
+        "deleted",
+        "conflicts",
+        "blobs_sent",
+        "blobs_received",
+        "blobs_missing",
+    ];
+}
+
+#[derive(Debug, Serialize)]
+pub struct GcRecord {
+    pub removed: usize,
+    pub freed_bytes: u64,
+}
+
+impl Record for GcRecord {
+    const COLUMNS: &'static [&'static str] = &["removed", "freed_bytes"];
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogRecord {
+    pub commit: String,
+    pub time: String,
+    pub author: String,
+    pub message: String,
+}
+
+impl From<&LogEntry> for LogRecord {
+    fn from(entry: &LogEntry) -> Self {
+        LogRecord {
+            commit: entry.id.clone(),
+            time: entry.time.clone(),
+            author: entry.author.clone(),
+            message: entry.message.clone(),
+        }
+    }
+}
+
+impl Record for LogRecord {
+    const COLUMNS: &'static [&'static str] = &["commit", "time", "author", "message"];
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateRecord {
+    pub name: String,
+    /// Hanya diisi oleh `template show`
+    pub content: Option<String>,
+}
+
+impl Record for TemplateRecord {
+    const COLUMNS: &'static [&'static str] = &["name", "content"];
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleRecord {
+    pub number: usize,
+    pub template: String,
+    pub every: String,
+    pub last_run: Option<String>,
+}
+
+impl ScheduleRecord {
+    pub fn new(number: usize, schedule: &Schedule) -> Self {
+        ScheduleRecord {
+            number,
+            template: schedule.template.clone(),
+            every: schedule.every.to_string(),
+            last_run: schedule.last_run.map(|d| d.to_string()),
+        }
+    }
+}
+
+impl Record for ScheduleRecord {
+    const COLUMNS: &'static [&'static str] = &["number", "template", "every", "last_run"];
+}
+
+pub struct Output {
+    pub format: Format,
+    pub quiet: bool,
+    /// Tanpa emoji: stdout bukan TTY atau NO_COLOR di-set
+    pub plain: bool,
+    /// Sudah ada record yang dicetak, jadi status tidak perlu dicetak lagi
+    printed: Cell<bool>,
+}
+
+impl Output {
+    pub fn new(format: Format, quiet: bool) -> Self {
+        let plain = !std::io::stdout().is_terminal() || std::env::var_os("NO_COLOR").is_some();
+        Output {
+            format,
+            quiet,
+            plain,
+            printed: Cell::new(false),
+        }
+    }
+
+    pub fn machine(&self) -> bool {
+        self.format != Format::Text
+    }
+
+    /// Cetak pesan aplikasi untuk manusia (ikon di depannya dibuang kalau plain).
+    /// Tidak dipakai di mode machine.
+    pub fn line(&self, text: impl std::fmt::Display) {
+        let text = text.to_string();
+        if self.plain {
+            emit(&strip_icon(&text));
+        } else {
+            emit(&text);
+        }
+    }
+
+    /// Cetak baris yang berisi teks user (isi note, judul, nama file) apa adanya;
+    /// ikon aplikasi di baris ini harus sudah mengikuti `plain` sendiri
+    pub fn text(&self, text: impl std::fmt::Display) {
+        emit(&text.to_string());
+    }
+
+    /// Isi note untuk output text; hanya label locked note yang ikonnya mengikuti `plain`
+    pub fn content<'a>(&self, note: &'a Note) -> Cow<'a, str> {
+        if self.plain && note.is_locked() {
+            Cow::Owned(strip_icon(&note.display_content()))
+        } else {
+            note.display_content()
+        }
+    }
+
+    /// Pesan informasi yang bisa dimatikan dengan --quiet; diabaikan di mode machine
+    pub fn info(&self, text: impl std::fmt::Display) {
+        if !self.quiet && !self.machine() {
+            self.line(text);
+        }
+    }
+
+    /// Hasil perintah: teks di mode text, StatusRecord di mode machine
+    /// (kecuali perintah ini sudah mencetak record datanya sendiri)
+    pub fn status(&self, status: &'static str, id: Option<usize>, text: impl std::fmt::Display) {
+        if !self.machine() {
+            self.info(text);
+        } else if !self.printed.get() {
+            let message = strip_icon(&text.to_string()).trim().to_string();
+            self.record(&StatusRecord {
+                status,
+                id,
+                message,
+            });
+        }
+    }
+
+    pub fn record<R: Record>(&self, record: &R) {
+        self.printed.set(true);
+        match self.format {
+            Format::Text => {}
+            Format::Json => emit(&serde_json::to_string_pretty(record).unwrap()),
+            Format::Jsonl => emit(&serde_json::to_string(record).unwrap()),
+            Format::Tsv => {
+                emit(&R::COLUMNS.join("\t"));
+                emit(&tsv_row::<R>(record));
+            }
+        }
+    }
+
+    pub fn records<R: Record>(&self, records: &[R]) {
+        self.printed.set(true);
+        match self.format {
+            Format::Text => {}
+            Format::Json => emit(&serde_json::to_string_pretty(records).unwrap()),
+            Format::Jsonl => {
+                for record in records {
+                    emit(&serde_json::to_string(record).unwrap());
+                }
+            }
+            Format::Tsv => {
+                emit(&R::COLUMNS.join("\t"));
+                for record in records {
+                    emit(&tsv_row::<R>(record));
+                }
+            }
+        }
+    }
+}
+
+/// Seperti println!, tapi berhenti diam-diam kalau pipe ditutup (misalnya `| head`)
+fn emit(text: &str) {
+    let mut stdout = std::io::stdout().lock();
+    if let Err(e) = writeln!(stdout, "{}", text) {
+        if e.kind() == std::io::ErrorKind::BrokenPipe {
+            std::process::exit(0);
+        }
+        panic!("failed printing to stdout: {}", e);
+    }
+}
+
+fn tsv_row<R: Record>(record: &R) -> String {
+    let value = serde_json::to_value(record).unwrap();
+    R::COLUMNS
+        .iter()
+        .map(|column| match value.get(column) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(text)) => escape_tsv(text),
+            Some(other) => escape_tsv(&other.to_string()),
+        })
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// Tab, newline dan backslash di-escape supaya satu record tetap satu baris
+fn escape_tsv(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// `COLUMNS` harus sama dengan field hasil serialize, dengan urutan yang sama
+    fn assert_columns<R: Record>(record: &R) {
+        let json = serde_json::to_string(record).unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let keys: BTreeSet<&str> = value.as_object().unwrap().keys().map(String::as_str).collect();
+        let columns: BTreeSet<&str> = R::COLUMNS.iter().copied().collect();
+        assert_eq!(keys, columns, "{}", json);
+        assert_eq!(columns.len(), R::COLUMNS.len(), "duplicate column in {:?}", R::COLUMNS);
+        let positions: Vec<usize> = R::COLUMNS
+            .iter()
+            .map(|column| json.find(&format!("\"{}\":", column)).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]), "column order differs from {}", json);
+    }
+
+    fn note_record() -> NoteRecord {
+        NoteRecord {
+            id: 1,
+            uid: "u".to_string(),
+            title: "Title".to_string(),
+            content: None,
+            created_at: "2024-05-15 10:00:00".to_string(),
+            updated_at: "2024-05-15 11:00:00".to_string(),
+            locked: true,
+            conflict: false,
+            attachments: 2,
+            pinned: false,
+            tags: vec!["work".to_string(), "a\tb".to_string()],
+            notebook: Some("Projects".to_string()),
+            archived: false,
+        }
+    }
+
+    #[test]
+    fn columns_match_serialized_fields() {
+        assert_columns(&note_record());
+        assert_columns(&StatusRecord {
+            status: "deleted",
+            id: None,
+            message: "Deleted".to_string(),
+        });
+        assert_columns(&LinkRecord::new(1, &LinkTarget::Id(2), Some(2)));
+        assert_columns(&Attachment {
+            name: "a.txt".to_string(),
+            hash: "0".repeat(64),
+            size: 3,
+            added_at: "2024-05-15 10:00:00".to_string(),
+        });
+        assert_columns(&MergeReport::default());
+        assert_columns(&GcRecord {
+            removed: 1,
+            freed_bytes: 10,
+        });
+        assert_columns(&LogRecord {
+            commit: "abc".to_string(),
+            time: "t".to_string(),
+            author: "me".to_string(),
+            message: "m".to_string(),
+        });
+        assert_columns(&TemplateRecord {
+            name: "daily".to_string(),
+            content: None,
+        });
+        assert_columns(&ScheduleRecord {
+            number: 1,
+            template: "daily".to_string(),
+            every: "daily".to_string(),
+            last_run: None,
+        });
+    }
+
+    #[test]
+    fn tsv_escapes_separators() {
+        assert_eq!(escape_tsv("a\tb\nc\r\\d"), "a\\tb\\nc\\r\\\\d");
+        assert_eq!(escape_tsv("plain 🙂"), "plain 🙂");
+        // Backslash di-escape lebih dulu, jadi "\n" literal tidak tertukar dengan newline
+        assert_eq!(escape_tsv("\\n"), "\\\\n");
+
+        let record = StatusRecord {
+            status: "added",
+            id: None,
+            message: "line one\nline\ttwo".to_string(),
+        };
+        assert_eq!(tsv_row(&record), "added\t\tline one\\nline\\ttwo");
+        let row = tsv_row(&note_record());
+        let cells: Vec<&str> = row.split('\t').collect();
+        assert_eq!(cells.len(), NoteRecord::COLUMNS.len());
+        assert_eq!(cells[3], "", "null content");
+        assert_eq!(cells[6], "true");
+        // Array jadi JSON; backslash dari escape JSON ikut di-escape
+        assert_eq!(cells[10], r#"["work","a\\tb"]"#);
+    }
+}
@@ -0,0 +1,217 @@
+// Fuzzy picker inline untuk perintah CLI yang butuh id note
+// Dipakai kalau id tidak diberikan dan stdin/stdout adalah terminal:
+// ketik untuk filter, panah atas/bawah untuk pilih, Enter untuk konfirmasi, Esc untuk batal.
+
+use crate::notes::Note;
+use crossterm::cursor::{MoveToColumn, MoveToNextLine, MoveUp};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType};
+use crossterm::{execute, queue};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::io::{self, IsTerminal, Write};
+
+const LIST_ROWS: usize = 8;
+const PREVIEW_ROWS: usize = 4;
+
+pub fn available() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Teks satu baris untuk daftar: isi note dengan newline diganti spasi
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut out: String = text.chars().take(width.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
+
+struct Picker<'a> {
+    prompt: &'a str,
+    notes: Vec<&'a Note>,
+    query: String,
+    /// Index ke `notes`, urut dari skor tertinggi
+    matches: Vec<usize>,
+    selected: usize,
+    /// Baris pertama daftar yang terlihat (untuk scroll)
+    offset: usize,
+    matcher: SkimMatcherV2,
+}
+
+impl<'a> Picker<'a> {
+    fn new(prompt: &'a str, notes: Vec<&'a Note>) -> Self {
+        let mut picker = Picker {
+            prompt,
+            notes,
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            offset: 0,
+            matcher: SkimMatcherV2::default().ignore_case(),
+        };
+        picker.filter();
+        picker
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .notes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, note)| {
+                if self.query.is_empty() {
+                    return Some((0, i));
+                }
+                let text = format!("{} {}", note.id, note.display_content());
+                self.matcher.fuzzy_match(&text, &self.query).map(|score| (score, i))
+            })
+            .collect();
+        // Sort stabil: skor sama tetap urut id
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let last = self.matches.len() as isize - 1;
+        self.selected = (self.selected as isize + delta).clamp(0, last) as usize;
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + LIST_ROWS {
+            self.offset = self.selected + 1 - LIST_ROWS;
+        }
+    }
+
+    fn current(&self) -> Option<&Note> {
+        self.matches.get(self.selected).map(|i| self.notes[*i])
+    }
+
+    fn height() -> usize {
+        // prompt + daftar + garis pemisah + preview
+        1 + LIST_ROWS + 1 + PREVIEW_ROWS
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let width = terminal::size().map(|(w, _)| w as usize).unwrap_or(80).max(20);
+        let mut lines: Vec<(String, bool)> = Vec::with_capacity(Self::height());
+
+        lines.push((
+            truncate(
+                &format!("{} ({}/{}) > {}", self.prompt, self.matches.len(), self.notes.len(), self.query),
+                width,
+            ),
+            false,
+        ));
+        for row in 0..LIST_ROWS {
+            let line = match self.matches.get(self.offset + row) {
+                Some(i) => {
+                    let note = self.notes[*i];
+                    let selected = self.offset + row == self.selected;
+                    let cursor = if selected { ">" } else { " " };
+                    let text = format!("{} [{}] {}", cursor, note.id, one_line(&note.display_content()));
+                    (truncate(&text, width), selected)
+                }
+                None => (String::new(), false),
+            };
+            lines.push(line);
+        }
+        lines.push(("─".repeat(width.min(60)), false));
+        let preview: Vec<String> = self
+            .current()
+            .map(|note| note.display_content().lines().take(PREVIEW_ROWS).map(str::to_string).collect())
+            .unwrap_or_default();
+        for row in 0..PREVIEW_ROWS {
+            let text = preview.get(row).map(|line| format!("  {}", line)).unwrap_or_default();
+            lines.push((truncate(&text, width), false));
+        }
+
+        queue!(out, MoveToColumn(0))?;
+        for (i, (text, highlight)) in lines.iter().enumerate() {
+            queue!(out, Clear(ClearType::CurrentLine))?;
+            if *highlight {
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Reverse),
+                    Print(text),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(out, Print(text))?;
+            }
+            if i + 1 < lines.len() {
+                queue!(out, MoveToNextLine(1))?;
+            }
+        }
+        // Kembali ke baris prompt, cursor di akhir query
+        let prompt_len = lines[0].0.chars().count() as u16;
+        queue!(out, MoveUp(lines.len() as u16 - 1), MoveToColumn(prompt_len))?;
+        out.flush()
+    }
+
+    /// Return Some(id) kalau user memilih, None kalau batal
+    fn handle(&mut self, key: KeyEvent) -> Option<Option<usize>> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Some(None),
+            KeyCode::Char('c') | KeyCode::Char('d') if ctrl => return Some(None),
+            KeyCode::Enter => return self.current().map(|note| Some(note.id)),
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Char('p') if ctrl => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('n') if ctrl => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-(LIST_ROWS as isize)),
+            KeyCode::PageDown => self.move_selection(LIST_ROWS as isize),
+            KeyCode::Backspace if self.query.pop().is_some() => self.filter(),
+            KeyCode::Char('u') if ctrl => {
+                self.query.clear();
+                self.filter();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.filter();
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Tampilkan picker di bawah cursor. Return id note yang dipilih, atau None kalau batal.
+pub fn pick(prompt: &str, notes: Vec<&Note>) -> io::Result<Option<usize>> {
+    let mut picker = Picker::new(prompt, notes);
+    let mut stdout = io::stdout();
+
+    // Sediakan ruang di bawah cursor (terminal akan scroll kalau perlu)
+    let height = Picker::height() as u16;
+    execute!(stdout, Print("\n".repeat(height as usize - 1)), MoveUp(height - 1))?;
+    terminal::enable_raw_mode()?;
+
+    let result = (|| loop {
+        picker.draw(&mut stdout)?;
+        match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                if let Some(result) = picker.handle(key) {
+                    return Ok(result);
+                }
+            }
+            _ => {}
+        }
+    })();
+
+    // Bersihkan area picker apa pun hasilnya
+    let _ = terminal::disable_raw_mode();
+    execute!(stdout, MoveToColumn(0), Clear(ClearType::FromCursorDown))?;
+    result
+}
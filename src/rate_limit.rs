@@ -0,0 +1,111 @@
+// Rate limit per client untuk request yang mengubah data (POST/PUT/PATCH/DELETE)
+// Token bucket: setiap client boleh `per_minute` request sekaligus, lalu bucket-nya
+// terisi lagi `per_minute` token per menit. Request baca (GET) tidak dibatasi, begitu
+// juga pesan di dalam WebSocket edit bersama yang sudah terbuka.
+// Login yang gagal punya bucket sendiri per username, terpisah dari alamat client,
+// supaya tebakan password ke satu akun tetap dibatasi walau datang dari banyak alamat.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Di atas jumlah client ini, bucket yang sudah penuh lagi dibuang
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Key default: alamat client; `None` berarti alamat tidak diketahui (Unix socket
+/// tanpa proxy terpercaya)
+#[derive(Clone)]
+pub struct RateLimiter<K = Option<IpAddr>> {
+    per_minute: u32,
+    buckets: Arc<Mutex<HashMap<K, Bucket>>>,
+}
+
+impl<K: Hash + Eq> RateLimiter<K> {
+    /// `per_minute` 0 berarti tidak ada batas
+    pub fn new(per_minute: u32) -> RateLimiter<K> {
+        RateLimiter {
+            per_minute,
+            buckets: Arc::default(),
+        }
+    }
+
+    /// Ambil satu token untuk `client`; kalau habis, return lama menunggu token berikutnya
+    pub fn check(&self, client: K) -> Result<(), Duration> {
+        self.take(client, 1.0)
+    }
+
+    /// Seperti `check`, tapi tanpa mengambil token: untuk request yang baru dihitung
+    /// kalau hasilnya gagal (login)
+    pub fn peek(&self, client: K) -> Result<(), Duration> {
+        self.take(client, 0.0)
+    }
+
+    fn take(&self, client: K, cost: f64) -> Result<(), Duration> {
+        if self.per_minute == 0 {
+            return Ok(());
+        }
+        let capacity = f64::from(self.per_minute);
+        let per_second = capacity / 60.0;
+        let now = Instant::now();
+        // Isinya hanya hitungan; kalau ada panic di tengah, state lama tetap bisa dipakai
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_TRACKED_CLIENTS {
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * per_second < capacity
+            });
+        }
+        let bucket = buckets.entry(client).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let refill = now.duration_since(bucket.updated).as_secs_f64() * per_second;
+        bucket.tokens = (bucket.tokens + refill).min(capacity);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= cost;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_each_key_separately() {
+        let limiter = RateLimiter::new(2);
+        assert!(limiter.check("alice").is_ok());
+        assert!(limiter.check("alice").is_ok());
+        let wait = limiter.check("alice").unwrap_err();
+        assert!(wait > Duration::from_secs(25) && wait <= Duration::from_secs(30), "{:?}", wait);
+        assert!(limiter.check("bob").is_ok());
+    }
+
+    #[test]
+    fn peek_does_not_use_a_token() {
+        let limiter = RateLimiter::new(1);
+        for _ in 0..3 {
+            assert!(limiter.peek("alice").is_ok());
+        }
+        assert!(limiter.check("alice").is_ok());
+        assert!(limiter.peek("alice").is_err());
+    }
+
+    #[test]
+    fn zero_means_unlimited() {
+        let limiter: RateLimiter = RateLimiter::new(0);
+        for _ in 0..100 {
+            assert!(limiter.check(None).is_ok());
+        }
+    }
+}
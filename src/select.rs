@@ -0,0 +1,299 @@
+// Memilih banyak note sekaligus untuk operasi bulk di CLI
+// Berdasarkan daftar id (`3-7,10`) dan/atau query (`--tag old --before 2025-01-01`).
+// normalize_tag juga dipakai web server untuk validasi tag.
+
+#![allow(dead_code)]
+
+use crate::notes::{Note, Notes};
+use chrono::NaiveDate;
+use clap::Args;
+use std::collections::BTreeSet;
+
+/// Batas ukuran range supaya `1-999999999` tidak menghabiskan memori
+const MAX_RANGE: usize = 100_000;
+
+/// Parse daftar id seperti `3`, `3-7` atau `3-7,10,12`
+pub fn parse_ids(spec: &str) -> Result<BTreeSet<usize>, String> {
+    let mut ids = BTreeSet::new();
+    for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let parse = |text: &str| {
+            text.trim()
+                .parse::<usize>()
+                .map_err(|_| t!("select-invalid-id", id = text.trim(), spec = spec))
+        };
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(t!("select-range-reversed", range = part));
+                }
+                if end - start >= MAX_RANGE {
+                    return Err(t!("select-range-too-large", range = part));
+                }
+                ids.extend(start..=end);
+            }
+            None => {
+                ids.insert(parse(part)?);
+            }
+        }
+    }
+    if ids.is_empty() {
+        return Err(t!("select-no-ids", spec = spec));
+    }
+    Ok(ids)
+}
+
+fn parse_date(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|_| t!("select-invalid-date", date = text))
+}
+
+/// Filter note untuk `list` dan operasi bulk
+#[derive(Debug, Args, Default, Clone)]
+pub struct Query {
+    /// Only notes with this tag (repeat for several tags)
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+    /// Only notes in this notebook
+    #[arg(long, value_name = "NAME")]
+    pub notebook: Option<String>,
+    /// Only notes created before this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub before: Option<NaiveDate>,
+    /// Only notes created on or after this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub after: Option<NaiveDate>,
+    /// Only archived notes (archived notes are skipped otherwise)
+    #[arg(long)]
+    pub archived: bool,
+}
+
+impl Query {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+            && self.notebook.is_none()
+            && self.before.is_none()
+            && self.after.is_none()
+            && !self.archived
+    }
+
+    /// `hide_archived` false dipakai untuk id yang disebut langsung:
+    /// `unarchive 5` harus tetap bisa menemukan note 5.
+    pub fn matches(&self, note: &Note, hide_archived: bool) -> bool {
+        if self.archived && !note.archived || hide_archived && !self.archived && note.archived {
+            return false;
+        }
+        if !self.tags.iter().all(|tag| note.has_tag(tag)) {
+            return false;
+        }
+        if let Some(notebook) = &self.notebook {
+            if note.notebook.as_deref() != Some(notebook.as_str()) {
+                return false;
+            }
+        }
+        let created = note.created_date();
+        if let Some(before) = self.before {
+            if created.is_none_or(|date| date >= before) {
+                return false;
+            }
+        }
+        if let Some(after) = self.after {
+            if created.is_none_or(|date| date < after) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Hasil seleksi: id note yang cocok, dan id dari daftar yang tidak ada
+pub struct Selected {
+    pub ids: Vec<usize>,
+    pub missing: Vec<usize>,
+}
+
+pub fn select(notes: &Notes, ids: Option<&BTreeSet<usize>>, query: &Query) -> Selected {
+    match ids {
+        Some(ids) => Selected {
+            ids: notes
+                .notes
+                .iter()
+                .filter(|note| ids.contains(&note.id) && query.matches(note, false))
+                .map(|note| note.id)
+                .collect(),
+            missing: ids
+                .iter()
+                .filter(|id| notes.get(**id).is_none())
+                .copied()
+                .collect(),
+        },
+        None => Selected {
+            ids: notes
+                .notes
+                .iter()
+                .filter(|note| query.matches(note, true))
+                .map(|note| note.id)
+                .collect(),
+            missing: Vec::new(),
+        },
+    }
+}
+
+/// Rapikan nama tag: tanpa '#' di depan dan tanpa spasi
+pub fn normalize_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.trim().trim_start_matches('#');
+    if tag.is_empty() || tag.contains(char::is_whitespace) || tag.contains(',') {
+        return Err(t!("select-invalid-tag", tag = tag));
+    }
+    Ok(tag.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ids(list: &[usize]) -> BTreeSet<usize> {
+        list.iter().copied().collect()
+    }
+
+    fn date(text: &str) -> Option<NaiveDate> {
+        Some(parse_date(text).unwrap())
+    }
+
+    /// Notebook kecil tanpa Notes::add (yang butuh device id di data dir)
+    fn notes() -> Notes {
+        serde_json::from_value(json!({
+            "next_id": 6,
+            "notes": [
+                {"id": 1, "content": "a", "created_at": "2024-12-31 10:00:00", "tags": ["old"]},
+                {"id": 2, "content": "b", "created_at": "2025-01-01 09:00:00", "tags": ["old", "Work"], "notebook": "job"},
+                {"id": 3, "content": "c", "created_at": "2025-02-01 09:00:00", "notebook": "job"},
+                {"id": 4, "content": "d", "created_at": "2025-03-01 09:00:00", "tags": ["old"], "archived": true},
+                {"id": 5, "content": "e", "created_at": "", "tags": ["old"]},
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_id_lists() {
+        let cases: [(&str, &[usize]); 7] = [
+            ("3", &[3]),
+            ("3-7", &[3, 4, 5, 6, 7]),
+            ("3-7,10,12", &[3, 4, 5, 6, 7, 10, 12]),
+            (" 2 , 4 - 5 ", &[2, 4, 5]),
+            ("5,1,5,1-2", &[1, 2, 5]),
+            ("1,,2", &[1, 2]),
+            ("4-4", &[4]),
+        ];
+        for (spec, expected) in cases {
+            assert_eq!(parse_ids(spec), Ok(ids(expected)), "{:?}", spec);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_id_lists() {
+        let too_large = format!("1-{}", MAX_RANGE + 1);
+        let cases = ["", ",", "3-1", "x", "1,x", "-3", "3-", "1-2-3", "1.5", too_large.as_str()];
+        for spec in cases {
+            assert!(parse_ids(spec).is_err(), "{:?} should be rejected", spec);
+        }
+        // Range terbesar yang masih boleh
+        assert_eq!(parse_ids(&format!("1-{}", MAX_RANGE)).unwrap().len(), MAX_RANGE);
+    }
+
+    #[test]
+    fn query_filters() {
+        let notes = notes();
+        let cases: [(Query, &[usize]); 9] = [
+            (Query::default(), &[1, 2, 3, 5]),
+            (
+                Query {
+                    tags: vec!["old".into()],
+                    ..Query::default()
+                },
+                &[1, 2, 5],
+            ),
+            (
+                Query {
+                    tags: vec!["old".into(), "work".into()],
+                    ..Query::default()
+                },
+                &[2],
+            ),
+            (
+                Query {
+                    notebook: Some("job".into()),
+                    ..Query::default()
+                },
+                &[2, 3],
+            ),
+            (
+                Query {
+                    before: date("2025-01-01"),
+                    ..Query::default()
+                },
+                &[1],
+            ),
+            (
+                Query {
+                    after: date("2025-01-01"),
+                    ..Query::default()
+                },
+                &[2, 3],
+            ),
+            (
+                Query {
+                    tags: vec!["old".into()],
+                    notebook: Some("job".into()),
+                    after: date("2025-01-01"),
+                    before: date("2025-02-01"),
+                    ..Query::default()
+                },
+                &[2],
+            ),
+            (
+                Query {
+                    archived: true,
+                    ..Query::default()
+                },
+                &[4],
+            ),
+            (
+                Query {
+                    archived: true,
+                    tags: vec!["work".into()],
+                    ..Query::default()
+                },
+                &[],
+            ),
+        ];
+        for (query, expected) in cases {
+            assert_eq!(select(&notes, None, &query).ids, expected, "{:?}", query);
+        }
+    }
+
+    #[test]
+    fn explicit_ids_report_unknown_and_include_archived() {
+        let notes = notes();
+        let selected = select(&notes, Some(&ids(&[2, 4, 7, 9])), &Query::default());
+        assert_eq!(selected.ids, [2, 4]);
+        assert_eq!(selected.missing, [7, 9]);
+
+        let old = Query {
+            tags: vec!["old".into()],
+            ..Query::default()
+        };
+        let selected = select(&notes, Some(&parse_ids("1-4").unwrap()), &old);
+        assert_eq!(selected.ids, [1, 2, 4]);
+        assert!(selected.missing.is_empty());
+    }
+
+    #[test]
+    fn normalizes_tags() {
+        assert_eq!(normalize_tag(" #work "), Ok("work".to_string()));
+        for tag in ["", "#", "two words", "a,b"] {
+            assert!(normalize_tag(tag).is_err(), "{:?}", tag);
+        }
+    }
+}
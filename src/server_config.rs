@@ -0,0 +1,425 @@
+// Konfigurasi rust_app_web: alamat listen, Unix socket, TLS, CORS dan rate limit
+// Urutan prioritas: flag command line, lalu environment (keduanya lewat clap), lalu
+// file TOML (default <data dir>/server.toml, atau --config / RUST_APP_CONFIG), lalu
+// nilai default. Path di dalam file relatif terhadap folder file itu.
+//
+// Contoh server.toml:
+//   host = "127.0.0.1"
+//   port = 8443
+//   tls-cert = "cert.pem"
+//   tls-key = "key.pem"
+//   cors-origins = ["https://notes.example.com"]
+//   rate-limit = 120
+//   trusted-proxies = ["127.0.0.1"]
+
+use crate::notes::data_path;
+use axum::http::{header, HeaderMap, HeaderValue, Method};
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufReader};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_rustls::rustls::{self, crypto::ring};
+use tokio_rustls::TlsAcceptor;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+pub const CONFIG_FILE: &str = "server.toml";
+pub const CONFIG_ENV: &str = "RUST_APP_CONFIG";
+const DEFAULT_HOST: &str = "0.0.0.0";
+const DEFAULT_PORT: u16 = 3000;
+const DEFAULT_RATE_LIMIT: u32 = 60;
+
+/// Pengaturan yang sama bentuknya di file dan di flag/env; `None` berarti tidak diisi
+#[derive(clap::Args, Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Settings {
+    /// Address to listen on [default: 0.0.0.0]
+    #[arg(long, env = "RUST_APP_HOST")]
+    pub host: Option<String>,
+    /// Port to listen on [default: 3000]
+    #[arg(long, env = "RUST_APP_PORT")]
+    pub port: Option<u16>,
+    /// Listen on a Unix socket at this path instead of TCP
+    #[arg(long, env = "RUST_APP_SOCKET", value_name = "PATH")]
+    pub socket: Option<PathBuf>,
+    /// Certificate chain (PEM) for serving HTTPS; needs --tls-key
+    #[arg(long, env = "RUST_APP_TLS_CERT", value_name = "PATH")]
+    pub tls_cert: Option<PathBuf>,
+    /// Private key (PEM) for --tls-cert
+    #[arg(long, env = "RUST_APP_TLS_KEY", value_name = "PATH")]
+    pub tls_key: Option<PathBuf>,
+    /// Origin allowed to call the API from other sites, e.g. https://notes.example.com
+    /// (repeatable; comma-separated in the env var; "*" allows any origin)
+    #[arg(long = "cors-origin", env = "RUST_APP_CORS_ORIGINS", value_delimiter = ',', value_name = "ORIGIN")]
+    pub cors_origins: Vec<String>,
+    /// Changes (POST/PUT/PATCH/DELETE) allowed per minute from one client address;
+    /// 0 turns rate limiting off [default: 60]
+    #[arg(long, env = "RUST_APP_RATE_LIMIT", value_name = "PER_MINUTE")]
+    pub rate_limit: Option<u32>,
+    /// Send the session cookie only over HTTPS; always on with --tls-cert, set it
+    /// when an HTTPS reverse proxy sits in front of the server
+    #[arg(long, env = "RUST_APP_SECURE_COOKIES")]
+    pub secure_cookies: bool,
+    /// Address of a reverse proxy whose X-Forwarded-For / X-Real-IP header names the
+    /// client for the rate limit; "unix" trusts the proxy on --socket
+    /// (repeatable; comma-separated in the env var)
+    #[arg(long = "trusted-proxy", env = "RUST_APP_TRUSTED_PROXIES", value_delimiter = ',', value_name = "ADDRESS")]
+    pub trusted_proxies: Vec<String>,
+}
+
+impl Settings {
+    /// Isi yang kosong dari `fallback` (pengaturan dengan prioritas lebih rendah)
+    fn or(self, fallback: Settings) -> Settings {
+        Settings {
+            host: self.host.or(fallback.host),
+            port: self.port.or(fallback.port),
+            socket: self.socket.or(fallback.socket),
+            tls_cert: self.tls_cert.or(fallback.tls_cert),
+            tls_key: self.tls_key.or(fallback.tls_key),
+            cors_origins: if self.cors_origins.is_empty() {
+                fallback.cors_origins
+            } else {
+                self.cors_origins
+            },
+            rate_limit: self.rate_limit.or(fallback.rate_limit),
+            secure_cookies: self.secure_cookies || fallback.secure_cookies,
+            trusted_proxies: if self.trusted_proxies.is_empty() {
+                fallback.trusted_proxies
+            } else {
+                self.trusted_proxies
+            },
+        }
+    }
+
+    /// Path relatif di file config dibaca dari folder file itu, bukan working directory
+    fn relative_to(mut self, dir: &Path) -> Settings {
+        for path in [&mut self.socket, &mut self.tls_cert, &mut self.tls_key].into_iter().flatten() {
+            *path = dir.join(&*path);
+        }
+        self
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read { path: PathBuf, error: String },
+    Address(String),
+    TlsPair,
+    TlsSocket,
+    CorsOrigin(String),
+    TrustedProxy(String),
+    Tls { path: PathBuf, error: String },
+    NoKey(PathBuf),
+    SocketUnsupported,
+    Bind { address: String, error: io::Error },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ConfigError::Read { path, error } => {
+                t!("server-config-read", path = path.display().to_string(), error = error.clone())
+            }
+            ConfigError::Address(address) => t!("server-config-address", address = address.clone()),
+            ConfigError::TlsPair => t!("server-config-tls-pair"),
+            ConfigError::TlsSocket => t!("server-config-tls-socket"),
+            ConfigError::CorsOrigin(origin) => t!("server-config-cors-origin", origin = origin.clone()),
+            ConfigError::TrustedProxy(proxy) => t!("server-config-trusted-proxy", proxy = proxy.clone()),
+            ConfigError::Tls { path, error } => {
+                t!("server-tls-failed", path = path.display().to_string(), error = error.clone())
+            }
+            ConfigError::NoKey(path) => t!("server-tls-no-key", path = path.display().to_string()),
+            ConfigError::SocketUnsupported => t!("server-socket-unsupported"),
+            ConfigError::Bind { address, error } => {
+                t!("server-bind-failed", address = address.clone(), error = error.to_string())
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+pub enum Listen {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+pub struct ServerConfig {
+    pub listen: Listen,
+    /// (certificate chain, private key)
+    pub tls: Option<(PathBuf, PathBuf)>,
+    pub cors_origins: Vec<String>,
+    /// Request yang mengubah data per menit per client, 0 = tanpa batas
+    pub rate_limit: u32,
+    /// Cookie session diberi atribut Secure (browser hanya mengirimnya lewat HTTPS)
+    pub secure_cookies: bool,
+    pub trusted_proxies: TrustedProxies,
+}
+
+/// Proxy yang header `X-Forwarded-For` / `X-Real-IP`-nya dipercaya. Tanpa proxy
+/// terpercaya header itu diabaikan: siapa saja bisa mengisinya dengan alamat baru
+/// di setiap request untuk lolos dari rate limit.
+#[derive(Clone, Debug, Default)]
+pub struct TrustedProxies {
+    addresses: Vec<IpAddr>,
+    /// Proxy yang terhubung lewat Unix socket (`unix`)
+    socket: bool,
+}
+
+impl TrustedProxies {
+    /// Alamat client untuk rate limit. `peer` adalah alamat koneksi TCP, `None` untuk
+    /// Unix socket; tanpa proxy terpercaya di depan, alamat itulah yang dipakai.
+    pub fn client(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+        let trusted = match peer {
+            Some(ip) => self.addresses.contains(&ip),
+            None => self.socket,
+        };
+        if !trusted {
+            return peer;
+        }
+        // Entry X-Forwarded-For ditambahkan di akhir oleh setiap proxy; entry dari proxy
+        // terpercaya dilewati, yang pertama setelahnya ditulis oleh proxy kita sendiri.
+        // Entry sebelum itu bisa dipalsukan client.
+        let forwarded = headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| {
+                value
+                    .rsplit(',')
+                    .map(|ip| ip.trim().parse::<IpAddr>().ok())
+                    .find(|ip| ip.is_none_or(|ip| !self.addresses.contains(&ip)))
+                    .flatten()
+            });
+        let real_ip = || {
+            headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|ip| ip.trim().parse().ok())
+        };
+        forwarded.or_else(real_ip).or(peer)
+    }
+}
+
+/// Gabungkan flag/env dengan file config. File default boleh tidak ada; file yang
+/// disebut eksplisit lewat `path` harus ada.
+pub fn load(path: Option<&Path>, flags: Settings) -> Result<ServerConfig, ConfigError> {
+    let file = match path {
+        Some(path) => read_file(path)?,
+        None => {
+            let path = data_path(CONFIG_FILE);
+            if path.exists() {
+                read_file(&path)?
+            } else {
+                Settings::default()
+            }
+        }
+    };
+    resolve(flags.or(file))
+}
+
+fn read_file(path: &Path) -> Result<Settings, ConfigError> {
+    let read_error = |error: String| ConfigError::Read {
+        path: path.to_path_buf(),
+        error,
+    };
+    let text = fs::read_to_string(path).map_err(|e| read_error(e.to_string()))?;
+    let settings: Settings = toml::from_str(&text).map_err(|e| read_error(e.message().to_string()))?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    Ok(settings.relative_to(dir))
+}
+
+fn resolve(settings: Settings) -> Result<ServerConfig, ConfigError> {
+    let tls = match (settings.tls_cert, settings.tls_key) {
+        (Some(cert), Some(key)) => Some((cert, key)),
+        (None, None) => None,
+        _ => return Err(ConfigError::TlsPair),
+    };
+    let listen = match settings.socket {
+        // TLS lewat Unix socket tidak berguna; biasanya proxy di depannya yang memegang TLS
+        Some(_) if tls.is_some() => return Err(ConfigError::TlsSocket),
+        Some(path) if cfg!(unix) => Listen::Unix(path),
+        Some(_) => return Err(ConfigError::SocketUnsupported),
+        None => {
+            let host = settings.host.unwrap_or_else(|| DEFAULT_HOST.to_string());
+            let port = settings.port.unwrap_or(DEFAULT_PORT);
+            let address = format!("{}:{}", host, port);
+            let addr = (host.as_str(), port)
+                .to_socket_addrs()
+                .ok()
+                .and_then(|mut addrs| addrs.next())
+                .ok_or(ConfigError::Address(address))?;
+            Listen::Tcp(addr)
+        }
+    };
+    for origin in &settings.cors_origins {
+        if !is_valid_origin(origin) {
+            return Err(ConfigError::CorsOrigin(origin.clone()));
+        }
+    }
+    let mut trusted_proxies = TrustedProxies::default();
+    for proxy in &settings.trusted_proxies {
+        if proxy == "unix" {
+            trusted_proxies.socket = true;
+        } else {
+            let ip = proxy.parse().map_err(|_| ConfigError::TrustedProxy(proxy.clone()))?;
+            trusted_proxies.addresses.push(ip);
+        }
+    }
+    Ok(ServerConfig {
+        listen,
+        cors_origins: settings.cors_origins,
+        rate_limit: settings.rate_limit.unwrap_or(DEFAULT_RATE_LIMIT),
+        secure_cookies: settings.secure_cookies || tls.is_some(),
+        tls,
+        trusted_proxies,
+    })
+}
+
+/// Origin seperti yang dikirim browser: `scheme://host[:port]`, tanpa path atau garis miring akhir
+fn is_valid_origin(origin: &str) -> bool {
+    if origin == "*" {
+        return true;
+    }
+    let Some((scheme, host)) = origin.split_once("://") else {
+        return false;
+    };
+    matches!(scheme, "http" | "https")
+        && !host.is_empty()
+        && !host.contains(['/', '?', '#', ' '])
+        && HeaderValue::from_str(origin).is_ok()
+}
+
+impl ServerConfig {
+    /// Alamat untuk pesan startup. `listen` adalah alamat yang benar-benar terpasang:
+    /// dengan port 0 sistem operasi yang memilih port-nya.
+    pub fn url(&self, listen: &Listen) -> String {
+        match listen {
+            Listen::Tcp(addr) => {
+                let scheme = if self.tls.is_some() { "https" } else { "http" };
+                // 0.0.0.0 / :: bisa dibuka lewat localhost
+                let host = if addr.ip().is_unspecified() {
+                    "localhost".to_string()
+                } else {
+                    addr.ip().to_string()
+                };
+                let host = if host.contains(':') { format!("[{}]", host) } else { host };
+                format!("{}://{}:{}", scheme, host, addr.port())
+            }
+            Listen::Unix(path) => format!("unix:{}", path.display()),
+        }
+    }
+
+    /// Tanpa origin yang diizinkan, browser hanya boleh memanggil API dari halaman ini sendiri
+    pub fn cors(&self) -> CorsLayer {
+        let origins = if self.cors_origins.iter().any(|origin| origin == "*") {
+            AllowOrigin::any()
+        } else {
+            AllowOrigin::list(
+                self.cors_origins
+                    .iter()
+                    .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+            )
+        };
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
+            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+            .max_age(Duration::from_secs(3600))
+    }
+
+    /// Baca cert dan key sekali saat startup, supaya kesalahan file terlihat sebelum listen
+    pub fn tls_acceptor(&self) -> Result<Option<TlsAcceptor>, ConfigError> {
+        let Some((cert_path, key_path)) = &self.tls else {
+            return Ok(None);
+        };
+        let tls_error = |path: &Path, error: String| ConfigError::Tls {
+            path: path.to_path_buf(),
+            error,
+        };
+        let mut certs = BufReader::new(fs::File::open(cert_path).map_err(|e| tls_error(cert_path, e.to_string()))?);
+        let certs = rustls_pemfile::certs(&mut certs)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| tls_error(cert_path, e.to_string()))?;
+        if certs.is_empty() {
+            return Err(tls_error(cert_path, t!("server-tls-no-certificate")));
+        }
+        let mut key = BufReader::new(fs::File::open(key_path).map_err(|e| tls_error(key_path, e.to_string()))?);
+        let key = rustls_pemfile::private_key(&mut key)
+            .map_err(|e| tls_error(key_path, e.to_string()))?
+            .ok_or_else(|| ConfigError::NoKey(key_path.clone()))?;
+        let mut config = rustls::ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+            .map_err(|e| tls_error(cert_path, e.to_string()))?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(Some(TlsAcceptor::from(Arc::new(config))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxies(list: &[&str]) -> TrustedProxies {
+        let settings = Settings {
+            trusted_proxies: list.iter().map(|proxy| proxy.to_string()).collect(),
+            ..Settings::default()
+        };
+        resolve(settings).unwrap().trusted_proxies
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn ip(text: &str) -> Option<IpAddr> {
+        Some(text.parse().unwrap())
+    }
+
+    #[test]
+    fn forwarded_headers_ignored_without_trusted_proxy() {
+        let spoofed = headers(&[("x-forwarded-for", "10.0.0.9"), ("x-real-ip", "10.0.0.8")]);
+        let none = proxies(&[]);
+        assert_eq!(none.client(ip("192.0.2.1"), &spoofed), ip("192.0.2.1"));
+        assert_eq!(none.client(None, &spoofed), None);
+        // Proxy terpercaya lain tidak membuat client langsung dipercaya
+        let other = proxies(&["127.0.0.1"]);
+        assert_eq!(other.client(ip("192.0.2.1"), &spoofed), ip("192.0.2.1"));
+        assert_eq!(other.client(None, &spoofed), None);
+    }
+
+    #[test]
+    fn client_from_trusted_proxy() {
+        let tcp = proxies(&["127.0.0.1", "10.0.0.2"]);
+        let proxy = ip("127.0.0.1");
+        // Entry yang ditulis client sendiri (paling kiri) tidak dipakai
+        let chain = headers(&[("x-forwarded-for", "6.6.6.6, 203.0.113.5, 10.0.0.2")]);
+        assert_eq!(tcp.client(proxy, &chain), ip("203.0.113.5"));
+        let real_ip = headers(&[("x-real-ip", "203.0.113.7")]);
+        assert_eq!(tcp.client(proxy, &real_ip), ip("203.0.113.7"));
+        assert_eq!(tcp.client(proxy, &HeaderMap::new()), proxy);
+        let garbage = headers(&[("x-forwarded-for", "not an ip")]);
+        assert_eq!(tcp.client(proxy, &garbage), proxy);
+
+        let socket = proxies(&["unix"]);
+        assert_eq!(socket.client(None, &real_ip), ip("203.0.113.7"));
+        assert_eq!(socket.client(ip("127.0.0.1"), &real_ip), ip("127.0.0.1"));
+    }
+
+    #[test]
+    fn invalid_trusted_proxy() {
+        let settings = Settings {
+            trusted_proxies: vec!["proxy.local".to_string()],
+            ..Settings::default()
+        };
+        assert!(matches!(resolve(settings), Err(ConfigError::TrustedProxy(proxy)) if proxy == "proxy.local"));
+    }
+}
@@ -0,0 +1,366 @@
+// Berbagi note antar user web server: grant baca/tulis ke user lain untuk satu note
+// atau satu notebook, dan link publik read-only untuk siapa saja yang memegang token.
+// Disimpan di <data dir>/shares.json; token link disimpan sebagai SHA-256, sama seperti
+// API token di accounts.rs.
+//
+// Grant note memakai uid (bukan id angka) supaya tetap menunjuk note yang sama setelah
+// sync. Grant notebook berlaku untuk semua note milik pemberi grant di notebook itu,
+// termasuk note yang ditambahkan belakangan. Grant hanya berlaku selama pemberinya
+// masih memiliki note tersebut.
+
+#![allow(dead_code)]
+
+use crate::accounts::{hash_secret, random_secret, Accounts, User};
+use crate::notes::{data_path, new_uid, now_timestamp, write_atomic, Note, Notes};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use utoipa::ToSchema;
+
+pub const SHARES_FILE: &str = "shares.json";
+pub const MAX_LINK_DAYS: u32 = 365;
+
+/// Hak akses dari grant; pemilik note selalu punya `Write` plus hak khusus pemilik
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Access {
+    Read,
+    Write,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    /// uid note
+    Note(String),
+    /// Nama notebook
+    Notebook(String),
+}
+
+impl Target {
+    /// Apakah `note` termasuk yang dibagikan `owner` lewat target ini
+    pub fn covers(&self, owner: &User, note: &Note) -> bool {
+        owner.owns(note)
+            && match self {
+                Target::Note(uid) => note.uid == *uid,
+                Target::Notebook(name) => note.notebook.as_deref() == Some(name.as_str()),
+            }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Grant {
+    pub id: String,
+    /// User yang membagikan
+    pub owner: String,
+    /// User yang menerima
+    pub user: String,
+    pub target: Target,
+    pub access: Access,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Link {
+    pub id: String,
+    pub owner: String,
+    pub target: Target,
+    /// SHA-256 dari token; token aslinya hanya ditampilkan sekali saat dibuat
+    hash: String,
+    pub created_at: String,
+    /// Format sama dengan `now_timestamp`, jadi bisa dibandingkan sebagai string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+impl Link {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.as_ref().is_some_and(|expires| *expires <= now_timestamp())
+    }
+}
+
+#[derive(Default, Serialize, Deserialize, Clone)]
+pub struct Shares {
+    #[serde(default)]
+    pub grants: Vec<Grant>,
+    #[serde(default)]
+    pub links: Vec<Link>,
+}
+
+/// Apa saja yang boleh dilihat/diubah satu user, dihitung sekali per request
+/// supaya handler tidak perlu memegang lock accounts dan shares bersamaan dengan notes
+pub struct Permissions {
+    pub user: User,
+    /// (pemberi grant, target, akses)
+    grants: Vec<(User, Target, Access)>,
+}
+
+impl Permissions {
+    pub fn access(&self, note: &Note) -> Option<Access> {
+        if self.user.owns(note) {
+            return Some(Access::Write);
+        }
+        self.grants
+            .iter()
+            .filter(|(owner, target, _)| target.covers(owner, note))
+            .map(|(_, _, access)| *access)
+            .max()
+    }
+
+    pub fn can_read(&self, note: &Note) -> bool {
+        self.access(note).is_some()
+    }
+
+    pub fn owns(&self, note: &Note) -> bool {
+        self.user.owns(note)
+    }
+}
+
+/// Waktu kedaluwarsa `days` hari dari sekarang, dalam format `now_timestamp`
+pub fn expiry_after(days: u32) -> String {
+    (chrono::Local::now() + chrono::Duration::days(days.into()))
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+impl Shares {
+    pub fn load() -> io::Result<Shares> {
+        match fs::read_to_string(data_path(SHARES_FILE)) {
+            Ok(json) => serde_json::from_str(&json).map_err(io::Error::other),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Shares::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_atomic(&data_path(SHARES_FILE), json.as_bytes())
+    }
+
+    pub fn permissions(&self, accounts: &Accounts, user: User) -> Permissions {
+        let grants = self
+            .grants
+            .iter()
+            .filter(|grant| grant.user == user.name)
+            .filter_map(|grant| Some((accounts.user(&grant.owner)?.clone(), grant.target.clone(), grant.access)))
+            .collect();
+        Permissions { user, grants }
+    }
+
+    /// Bagikan `target` ke `user`. Grant yang sama untuk pasangan owner/user/target
+    /// diganti, jadi membagikan ulang hanya mengubah aksesnya.
+    pub fn grant(&mut self, owner: &str, user: &str, target: Target, access: Access) -> Grant {
+        if let Some(grant) = self
+            .grants
+            .iter_mut()
+            .find(|grant| grant.owner == owner && grant.user == user && grant.target == target)
+        {
+            grant.access = access;
+            return grant.clone();
+        }
+        let grant = Grant {
+            id: new_uid()[..12].to_string(),
+            owner: owner.to_string(),
+            user: user.to_string(),
+            target,
+            access,
+            created_at: now_timestamp(),
+        };
+        self.grants.push(grant.clone());
+        grant
+    }
+
+    /// Hapus grant `id`; boleh oleh pemberi maupun penerimanya
+    pub fn revoke(&mut self, id: &str, user: &str) -> bool {
+        let before = self.grants.len();
+        self.grants
+            .retain(|grant| grant.id != id || (grant.owner != user && grant.user != user));
+        self.grants.len() != before
+    }
+
+    /// Buat link publik; token-nya hanya bisa dilihat sekali, dari nilai kembalian ini
+    pub fn create_link(&mut self, owner: &str, target: Target, expires_at: Option<String>) -> (Link, String) {
+        let secret = random_secret();
+        let link = Link {
+            id: new_uid()[..12].to_string(),
+            owner: owner.to_string(),
+            target,
+            hash: hash_secret(&secret),
+            created_at: now_timestamp(),
+            expires_at,
+        };
+        self.links.push(link.clone());
+        (link, secret)
+    }
+
+    /// Link untuk token ini, kalau ada dan belum kedaluwarsa
+    pub fn link(&self, secret: &str) -> Option<&Link> {
+        let hash = hash_secret(secret);
+        self.links
+            .iter()
+            .find(|link| link.hash == hash)
+            .filter(|link| !link.is_expired())
+    }
+
+    pub fn delete_link(&mut self, id: &str, owner: &str) -> bool {
+        let before = self.links.len();
+        self.links.retain(|link| link.id != id || link.owner != owner);
+        self.links.len() != before
+    }
+
+    /// Note yang terlihat lewat link, urut seperti di notebook
+    pub fn shared_notes<'a>(link: &Link, owner: &User, notes: &'a Notes) -> Vec<&'a Note> {
+        notes
+            .notes
+            .iter()
+            .filter(|note| link.target.covers(owner, note))
+            .collect()
+    }
+
+    /// Dipanggil saat user dihapus: grant dari/ke user itu dan link miliknya ikut hilang
+    pub fn remove_user(&mut self, name: &str) -> bool {
+        let before = self.grants.len() + self.links.len();
+        self.grants.retain(|grant| grant.owner != name && grant.user != name);
+        self.links.retain(|link| link.owner != name);
+        self.grants.len() + self.links.len() != before
+    }
+
+    /// Dipanggil saat note dihapus, supaya grant dan link ke uid-nya tidak menggantung
+    pub fn remove_note(&mut self, uid: &str) -> bool {
+        let target = Target::Note(uid.to_string());
+        let before = self.grants.len() + self.links.len();
+        self.grants.retain(|grant| grant.target != target);
+        self.links.retain(|link| link.target != target);
+        self.grants.len() + self.links.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn user(name: &str, admin: bool) -> User {
+        serde_json::from_value(json!({"name": name, "password_hash": "", "admin": admin, "created_at": ""})).unwrap()
+    }
+
+    fn note(uid: &str, owner: Option<&str>, notebook: Option<&str>) -> Note {
+        serde_json::from_value(json!({
+            "id": 1, "uid": uid, "content": "", "created_at": "", "owner": owner, "notebook": notebook,
+        }))
+        .unwrap()
+    }
+
+    /// alice (admin), bob dan carol
+    fn accounts() -> Accounts {
+        let mut accounts = Accounts::default();
+        accounts.users = vec![user("alice", true), user("bob", false), user("carol", false)];
+        accounts
+    }
+
+    fn permissions(shares: &Shares, name: &str) -> Permissions {
+        let accounts = accounts();
+        let user = accounts.user(name).unwrap().clone();
+        shares.permissions(&accounts, user)
+    }
+
+    #[test]
+    fn targets_only_cover_the_owners_notes() {
+        let (alice, bob) = (user("alice", true), user("bob", false));
+        let local = note("a1", None, Some("work"));
+        let bobs = note("b1", Some("bob"), Some("work"));
+        assert!(Target::Note("a1".to_string()).covers(&alice, &local));
+        assert!(!Target::Note("a2".to_string()).covers(&alice, &local));
+        assert!(Target::Notebook("work".to_string()).covers(&alice, &local));
+        assert!(!Target::Notebook("home".to_string()).covers(&alice, &local));
+        assert!(!Target::Notebook("work".to_string()).covers(&alice, &note("a2", None, None)));
+        // Note bob di notebook yang sama tidak ikut terbagi oleh grant alice
+        assert!(!Target::Notebook("work".to_string()).covers(&alice, &bobs));
+        assert!(!Target::Note("a1".to_string()).covers(&bob, &local));
+    }
+
+    #[test]
+    fn access_is_the_strongest_grant() {
+        let mut shares = Shares::default();
+        let work = note("a1", None, Some("work"));
+        assert_eq!(permissions(&shares, "alice").access(&work), Some(Access::Write));
+        assert_eq!(permissions(&shares, "bob").access(&work), None);
+
+        shares.grant("alice", "bob", Target::Notebook("work".to_string()), Access::Read);
+        assert_eq!(permissions(&shares, "bob").access(&work), Some(Access::Read));
+        shares.grant("alice", "bob", Target::Note("a1".to_string()), Access::Write);
+        assert_eq!(permissions(&shares, "bob").access(&work), Some(Access::Write));
+        assert!(!permissions(&shares, "bob").owns(&work));
+        assert_eq!(permissions(&shares, "carol").access(&work), None);
+        // Note yang dipindah keluar dari notebook hanya terlihat lewat grant note-nya
+        assert_eq!(permissions(&shares, "bob").access(&note("a2", None, Some("work"))), Some(Access::Read));
+        assert_eq!(permissions(&shares, "bob").access(&note("a2", None, Some("home"))), None);
+
+        // Membagikan ulang mengganti akses, bukan menambah grant
+        shares.grant("alice", "bob", Target::Note("a1".to_string()), Access::Read);
+        assert_eq!(shares.grants.len(), 2);
+        assert_eq!(permissions(&shares, "bob").access(&work), Some(Access::Read));
+    }
+
+    #[test]
+    fn grants_from_removed_users_do_not_apply() {
+        let mut shares = Shares::default();
+        shares.grant("dave", "bob", Target::Note("d1".to_string()), Access::Write);
+        assert_eq!(permissions(&shares, "bob").access(&note("d1", Some("dave"), None)), None);
+    }
+
+    #[test]
+    fn revoke_by_grantor_or_grantee() {
+        let mut shares = Shares::default();
+        let target = || Target::Note("a1".to_string());
+        let grant = shares.grant("alice", "bob", target(), Access::Read);
+        assert!(!shares.revoke(&grant.id, "carol"));
+        assert!(!shares.revoke("unknown", "alice"));
+        assert!(shares.revoke(&grant.id, "bob"));
+        assert!(shares.grants.is_empty());
+
+        let grant = shares.grant("alice", "bob", target(), Access::Read);
+        assert!(shares.revoke(&grant.id, "alice"));
+        assert!(!shares.revoke(&grant.id, "alice"));
+    }
+
+    #[test]
+    fn links_expire() {
+        let mut shares = Shares::default();
+        let target = || Target::Notebook("work".to_string());
+        let (forever, forever_secret) = shares.create_link("alice", target(), None);
+        let (_, expired_secret) = shares.create_link("alice", target(), Some("2000-01-01 00:00:00".to_string()));
+        let (later, later_secret) = shares.create_link("alice", target(), Some(expiry_after(1)));
+        assert!(!forever.is_expired());
+        assert!(!later.is_expired());
+        assert!(shares.link(&forever_secret).is_some());
+        assert!(shares.link(&later_secret).is_some());
+        assert!(shares.link(&expired_secret).is_none());
+        assert!(shares.link(&forever.hash).is_none());
+
+        assert!(!shares.delete_link(&forever.id, "bob"));
+        assert!(shares.delete_link(&forever.id, "alice"));
+        assert!(shares.link(&forever_secret).is_none());
+    }
+
+    #[test]
+    fn removing_users_and_notes_drops_their_shares() {
+        let mut shares = Shares::default();
+        shares.grant("alice", "bob", Target::Note("a1".to_string()), Access::Read);
+        shares.grant("alice", "carol", Target::Note("a2".to_string()), Access::Read);
+        shares.grant("bob", "alice", Target::Notebook("home".to_string()), Access::Write);
+        shares.create_link("alice", Target::Note("a1".to_string()), None);
+        shares.create_link("bob", Target::Notebook("home".to_string()), None);
+
+        assert!(shares.remove_note("a1"));
+        assert_eq!(shares.grants.len(), 2);
+        assert_eq!(shares.links.len(), 1);
+        assert!(!shares.remove_note("a1"));
+
+        assert!(shares.remove_user("bob"));
+        assert_eq!(shares.grants.len(), 1);
+        assert_eq!(shares.grants[0].user, "carol");
+        assert!(shares.links.is_empty());
+        assert!(!shares.remove_user("bob"));
+    }
+}
@@ -0,0 +1,143 @@
+// Shutdown yang rapi untuk rust_app_web
+// SIGINT/SIGTERM menghentikan accept, koneksi yang sedang berjalan diberi waktu untuk
+// selesai (stream SSE dan WebSocket ditutup), lalu antrian storage dikosongkan.
+// Setiap `Shutdown` yang masih hidup dihitung sebagai pekerjaan yang belum selesai.
+
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_stream::wrappers::WatchStream;
+use tokio_stream::{Stream, StreamExt};
+
+pub struct Controller {
+    signal: watch::Sender<bool>,
+    alive: watch::Sender<()>,
+}
+
+#[derive(Clone)]
+pub struct Shutdown {
+    signal: watch::Receiver<bool>,
+    _alive: watch::Receiver<()>,
+}
+
+pub fn channel() -> (Controller, Shutdown) {
+    let (signal, signal_rx) = watch::channel(false);
+    let (alive, alive_rx) = watch::channel(());
+    (
+        Controller { signal, alive },
+        Shutdown {
+            signal: signal_rx,
+            _alive: alive_rx,
+        },
+    )
+}
+
+/// Untuk state yang tidak pernah di-shutdown (test)
+impl Default for Shutdown {
+    fn default() -> Self {
+        channel().1
+    }
+}
+
+impl Shutdown {
+    /// Selesai saat shutdown dimulai
+    pub async fn requested(&self) {
+        let mut signal = self.signal.clone();
+        if signal.wait_for(|stop| *stop).await.is_err() {
+            // Controller sudah tidak ada, jadi shutdown tidak akan pernah diminta
+            std::future::pending::<()>().await;
+        }
+    }
+
+    /// Satu item saat shutdown dimulai, untuk digabung ke stream yang berumur panjang (SSE)
+    pub fn into_stream(self) -> impl Stream<Item = ()> + Send + 'static {
+        let alive = self._alive;
+        WatchStream::new(self.signal)
+            .filter(|stop| *stop)
+            .map(move |_| {
+                let _ = &alive;
+            })
+    }
+}
+
+impl Controller {
+    pub fn start(&self) {
+        self.signal.send_replace(true);
+    }
+
+    /// Tunggu semua `Shutdown` di-drop; false kalau `timeout` habis duluan
+    pub async fn drained(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, self.alive.closed()).await.is_ok()
+    }
+}
+
+/// Selesai saat proses menerima Ctrl-C (SIGINT) atau SIGTERM
+pub async fn signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHORT: Duration = Duration::from_millis(50);
+
+    #[tokio::test]
+    async fn drained_when_every_handle_is_dropped() {
+        let (controller, shutdown) = channel();
+        let handle = shutdown.clone();
+        let task = tokio::spawn(async move {
+            handle.requested().await;
+        });
+        drop(shutdown);
+        assert!(!controller.drained(SHORT).await, "a task still holds a handle");
+
+        controller.start();
+        task.await.unwrap();
+        assert!(controller.drained(SHORT).await);
+    }
+
+    #[tokio::test]
+    async fn stream_yields_once_shutdown_starts() {
+        let (controller, shutdown) = channel();
+        let mut stream = Box::pin(shutdown.into_stream());
+        assert!(tokio::time::timeout(SHORT, stream.next()).await.is_err());
+        // Stream yang masih hidup dihitung sebagai pekerjaan yang belum selesai
+        assert!(!controller.drained(SHORT).await);
+
+        controller.start();
+        assert_eq!(tokio::time::timeout(SHORT, stream.next()).await.unwrap(), Some(()));
+        drop(stream);
+        assert!(controller.drained(SHORT).await);
+    }
+
+    #[tokio::test]
+    async fn requested_after_start_returns_immediately() {
+        let (controller, shutdown) = channel();
+        controller.start();
+        tokio::time::timeout(SHORT, shutdown.requested()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn default_handle_never_shuts_down() {
+        let shutdown = Shutdown::default();
+        assert!(tokio::time::timeout(SHORT, shutdown.requested()).await.is_err());
+    }
+}
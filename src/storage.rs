@@ -0,0 +1,117 @@
+// Penulisan ke disk untuk web server, di luar executor async
+// Handler hanya memegang lock (std Mutex) selama mengubah data di memory. Menulis ke
+// disk (serialize, fs::write, enkripsi, commit git) dikerjakan satu thread penulis
+// lewat antrian ini. Snapshot diantrikan selagi lock masih dipegang, jadi urutan
+// tulis di disk sama dengan urutan perubahan; handler menunggu hasilnya tanpa lock.
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::task::{Context, Poll};
+use std::thread;
+use tokio::sync::oneshot;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+#[derive(Clone)]
+pub struct Storage(mpsc::Sender<Job>);
+
+/// Hasil satu penulisan yang sudah diantrikan
+pub struct Saved(oneshot::Receiver<io::Result<()>>);
+
+impl Future for Saved {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0)
+            .poll(cx)
+            .map(|result| result.unwrap_or_else(|_| Err(io::Error::other(t!("server-storage-stopped")))))
+    }
+}
+
+impl Storage {
+    /// Jalankan thread penulis; berhenti sendiri setelah semua `Storage` di-drop
+    pub fn start() -> io::Result<Storage> {
+        let (tx, rx) = mpsc::channel::<Job>();
+        thread::Builder::new().name("storage".to_string()).spawn(move || {
+            for job in rx {
+                job();
+            }
+        })?;
+        Ok(Storage(tx))
+    }
+
+    /// Antrikan `save`; hasilnya bisa di-await tanpa memblokir executor
+    pub fn save(&self, save: impl FnOnce() -> io::Result<()> + Send + 'static) -> Saved {
+        let (done, saved) = oneshot::channel();
+        self.run(move || {
+            let _ = done.send(save());
+        });
+        Saved(saved)
+    }
+
+    /// Antrikan pekerjaan yang tidak ditunggu siapa pun (error dicatat oleh `job` sendiri)
+    pub fn run(&self, job: impl FnOnce() + Send + 'static) {
+        // Kalau thread penulis sudah berhenti, Saved melaporkannya lewat error
+        let _ = self.0.send(Box::new(job));
+    }
+
+    /// Selesai setelah semua yang diantrikan sebelumnya sudah ditulis
+    pub fn flush(&self) -> Saved {
+        self.save(|| Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn writes_in_queue_order() {
+        let storage = Storage::start().unwrap();
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let saves: Vec<Saved> = (0..20)
+            .map(|i| {
+                let written = Arc::clone(&written);
+                storage.save(move || {
+                    written.lock().unwrap().push(i);
+                    Ok(())
+                })
+            })
+            .collect();
+        let last = saves.into_iter().last().unwrap();
+        last.await.unwrap();
+        assert_eq!(*written.lock().unwrap(), (0..20).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn reports_save_errors() {
+        let storage = Storage::start().unwrap();
+        let error = storage.save(|| Err(io::Error::other("disk full"))).await.unwrap_err();
+        assert_eq!(error.to_string(), "disk full");
+        // Error satu penulisan tidak menghentikan thread penulis
+        storage.flush().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn flush_waits_for_earlier_jobs() {
+        let storage = Storage::start().unwrap();
+        let done = Arc::new(Mutex::new(false));
+        let flag = Arc::clone(&done);
+        storage.run(move || {
+            thread::sleep(std::time::Duration::from_millis(50));
+            *flag.lock().unwrap() = true;
+        });
+        storage.flush().await.unwrap();
+        assert!(*done.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn stopped_writer_is_an_error() {
+        let storage = Storage::start().unwrap();
+        storage.run(|| panic!("writer crashed"));
+        assert!(storage.flush().await.is_err());
+    }
+}
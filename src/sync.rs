@@ -0,0 +1,641 @@
+// Sync antar note store (multi-device)
+// Setiap note punya uid global dan version vector (device id -> counter).
+// Merge membandingkan version vector:
+//   - satu sisi lebih baru (dominan)  -> ambil versi itu
+//   - keduanya berubah (concurrent)   -> simpan kedua versi sebagai conflict record di note,
+//                                        user memilih/menggabungkan lewat three-way merge (merge.rs)
+//   - edit vs delete yang concurrent  -> edit menang, note tidak hilang
+//
+// rust_app_web berperan sebagai sync server (POST /api/sync),
+// CLI/TUI menjadi client lewat `rust_app sync <url>`.
+// Note hanya membawa metadata attachment; setelah merge, client mengirim blob yang belum
+// dimiliki server dan mengambil blob yang belum dimilikinya (/api/sync/blobs/<hash>).
+
+#![allow(dead_code)]
+
+use crate::attachments;
+use crate::notes::{now_timestamp, Conflict, Note, Notes, Tombstone, Version};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::io::Read;
+use utoipa::ToSchema;
+
+pub const SYNC_URL_ENV: &str = "RUST_APP_SYNC_URL";
+/// API token admin di server (`rust_app_web token create <admin>`)
+pub const SYNC_TOKEN_ENV: &str = "RUST_APP_SYNC_TOKEN";
+
+/// Data yang dikirim/diterima saat sync: semua note dan tombstone
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct SyncPayload {
+    pub notes: Vec<Note>,
+    pub tombstones: Vec<Tombstone>,
+    /// Jawaban server: hash attachment yang direferensikan note tapi belum ada di server
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_blobs: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MergeReport {
+    pub added: usize,
+    pub updated: usize,
+    pub deleted: usize,
+    pub conflicts: usize,
+    /// Blob attachment yang dikirim ke / diambil dari server
+    #[serde(default)]
+    pub blobs_sent: usize,
+    #[serde(default)]
+    pub blobs_received: usize,
+    /// Blob yang direferensikan note tapi tidak ada di kedua sisi
+    #[serde(default)]
+    pub blobs_missing: usize,
+}
+
+impl MergeReport {
+    /// Tidak ada note yang berubah (transfer blob tidak dihitung)
+    pub fn is_empty(&self) -> bool {
+        self.added + self.updated + self.deleted + self.conflicts == 0
+    }
+}
+
+impl std::fmt::Display for MergeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let report = t!(
+            "sync-report",
+            added = self.added,
+            updated = self.updated,
+            deleted = self.deleted,
+            conflicts = self.conflicts,
+        );
+        write!(f, "{}", report)?;
+        if self.blobs_sent + self.blobs_received > 0 {
+            let blobs = t!("sync-report-blobs", sent = self.blobs_sent, received = self.blobs_received);
+            write!(f, ", {}", blobs)?;
+        }
+        if self.blobs_missing > 0 {
+            write!(f, ", {}", t!("sync-report-blobs-missing", count = self.blobs_missing))?;
+        }
+        Ok(())
+    }
+}
+
+/// Hasil perbandingan dua version vector
+#[derive(Debug, PartialEq)]
+pub enum Causality {
+    Equal,
+    /// Sisi kiri lebih lama (semua perubahannya sudah ada di kanan)
+    Before,
+    After,
+    Concurrent,
+}
+
+pub fn compare(a: &Version, b: &Version) -> Causality {
+    let mut ordering = Ordering::Equal;
+    for device in a.keys().chain(b.keys()) {
+        let left = a.get(device).copied().unwrap_or(0);
+        let right = b.get(device).copied().unwrap_or(0);
+        match (ordering, left.cmp(&right)) {
+            (_, Ordering::Equal) => {}
+            (Ordering::Equal, other) => ordering = other,
+            (current, other) if current != other => return Causality::Concurrent,
+            _ => {}
+        }
+    }
+    match ordering {
+        Ordering::Equal => Causality::Equal,
+        Ordering::Less => Causality::Before,
+        Ordering::Greater => Causality::After,
+    }
+}
+
+pub fn merge_versions(a: &Version, b: &Version) -> Version {
+    let mut merged = a.clone();
+    for (device, counter) in b {
+        let entry = merged.entry(device.clone()).or_insert(0);
+        *entry = (*entry).max(*counter);
+    }
+    merged
+}
+
+pub fn payload(notes: &Notes) -> SyncPayload {
+    SyncPayload {
+        notes: notes.notes.clone(),
+        tombstones: notes.tombstones.clone(),
+        missing_blobs: Vec::new(),
+    }
+}
+
+/// Hash attachment yang direferensikan `notes` tapi blob-nya tidak ada di store ini
+pub fn missing_blobs(notes: &[Note]) -> Vec<String> {
+    let hashes: BTreeSet<&str> = notes
+        .iter()
+        .flat_map(|note| note.attachments.iter().map(|a| a.hash.as_str()))
+        .collect();
+    hashes
+        .into_iter()
+        .filter(|hash| !attachments::has_blob(hash))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Terapkan versi remote ke note lokal, id lokal tetap dipertahankan
+fn replace_note(local: &mut Note, remote: &Note) {
+    let id = local.id;
+    *local = remote.clone();
+    local.id = id;
+}
+
+/// Locked note tidak bisa di-merge per baris: versi yang kalah disimpan sebagai note baru
+fn add_conflicted_copy(notes: &mut Notes, loser: Note) {
+    let id = notes.add(format!("{}\n\n(conflicted copy)", loser.content)).id;
+    let copy = notes.get_mut(id).unwrap();
+    copy.locked = loser.locked;
+    copy.attachments = loser.attachments;
+}
+
+/// Data dari device lain tidak dipercaya begitu saja: hash attachment dipakai sebagai
+/// path blob, jadi yang bukan SHA-256 hex dibuang; nama file dibersihkan seperti upload
+fn sanitize_remote(note: &mut Note) {
+    note.attachments.retain(|a| attachments::is_hash(&a.hash));
+    for attachment in &mut note.attachments {
+        attachment.hash.make_ascii_lowercase();
+        attachment.name = attachments::sanitize_name(&attachment.name);
+    }
+}
+
+/// Perubahan concurrent selain content. Attachment (per hash) dan tag digabung supaya
+/// tidak ada yang hilang; pinned, archived dan notebook diambil dari versi dengan
+/// `updated_at` terbaru. Pemenangnya sama di setiap device, jadi hasil merge juga sama.
+fn merge_metadata(local: &mut Note, remote: &Note) {
+    for attachment in &remote.attachments {
+        if !local.attachments.iter().any(|a| a.hash == attachment.hash) {
+            local.attachments.push(attachment.clone());
+        }
+    }
+    for tag in &remote.tags {
+        if !local.has_tag(tag) {
+            local.tags.push(tag.clone());
+        }
+    }
+    let key = |note: &Note| (note.updated_at.clone(), note.pinned, note.archived, note.notebook.clone());
+    if key(remote) > key(local) {
+        local.pinned = remote.pinned;
+        local.archived = remote.archived;
+        local.notebook = remote.notebook.clone();
+        local.updated_at = remote.updated_at.clone();
+    }
+}
+
+/// Gabungkan payload dari store lain ke store lokal
+pub fn merge(notes: &mut Notes, remote: SyncPayload) -> MergeReport {
+    let mut report = MergeReport::default();
+
+    for mut remote_note in remote.notes {
+        sanitize_remote(&mut remote_note);
+        if let Some(pos) = notes.notes.iter().position(|n| n.uid == remote_note.uid) {
+            let local = &mut notes.notes[pos];
+            match compare(&local.version, &remote_note.version) {
+                Causality::Equal | Causality::After => {}
+                Causality::Before => {
+                    // Konflik yang terdeteksi di sisi lain ikut dilaporkan sebagai konflik
+                    if remote_note.has_conflict() && !local.has_conflict() {
+                        report.conflicts += 1;
+                    } else {
+                        report.updated += 1;
+                    }
+                    replace_note(local, &remote_note);
+                }
+                Causality::Concurrent => {
+                    let merged = merge_versions(&local.version, &remote_note.version);
+                    if local.locked.is_some() || remote_note.locked.is_some() {
+                        // Pemenang deterministik (sama di semua device): updated_at terbaru
+                        let loser = if remote_note.updated_at > local.updated_at {
+                            let loser = local.clone();
+                            replace_note(local, &remote_note);
+                            loser
+                        } else {
+                            remote_note
+                        };
+                        local.version = merged;
+                        add_conflicted_copy(notes, loser);
+                        report.conflicts += 1;
+                        continue;
+                    }
+
+                    local.version = merged;
+                    merge_metadata(local, &remote_note);
+                    if local.content != remote_note.content {
+                        let base = local
+                            .conflict
+                            .as_ref()
+                            .map(|c| c.base.clone())
+                            .unwrap_or_else(|| remote_note.base.clone().or_else(|| local.base.clone()));
+                        local.conflict = Some(Conflict {
+                            base,
+                            ours: local.content.clone(),
+                            theirs: remote_note.content,
+                            theirs_updated_at: remote_note.updated_at,
+                            detected_at: now_timestamp(),
+                        });
+                        report.conflicts += 1;
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(pos) = notes.tombstones.iter().position(|t| t.uid == remote_note.uid) {
+            match compare(&notes.tombstones[pos].version, &remote_note.version) {
+                // Remote belum melihat penghapusan lokal
+                Causality::Equal | Causality::After => continue,
+                // Remote mengubah note setelah/bersamaan dengan delete lokal: edit menang
+                Causality::Before | Causality::Concurrent => {
+                    let version = merge_versions(&notes.tombstones[pos].version, &remote_note.version);
+                    notes.tombstones.remove(pos);
+                    let id = notes.add(String::new()).id;
+                    let local = notes.get_mut(id).unwrap();
+                    replace_note(local, &remote_note);
+                    local.version = version;
+                    report.added += 1;
+                    continue;
+                }
+            }
+        }
+
+        let id = notes.add(String::new()).id;
+        replace_note(notes.get_mut(id).unwrap(), &remote_note);
+        report.added += 1;
+    }
+
+    for remote_tombstone in remote.tombstones {
+        if let Some(pos) = notes.notes.iter().position(|n| n.uid == remote_tombstone.uid) {
+            match compare(&notes.notes[pos].version, &remote_tombstone.version) {
+                Causality::Before | Causality::Equal => {
+                    notes.notes.remove(pos);
+                    report.deleted += 1;
+                }
+                // Note diubah lokal tanpa tahu ada delete: pertahankan, tapi ingat versi delete
+                Causality::After | Causality::Concurrent => {
+                    let note = &mut notes.notes[pos];
+                    note.version = merge_versions(&note.version, &remote_tombstone.version);
+                    note.touch();
+                    continue;
+                }
+            }
+        }
+        match notes.tombstones.iter_mut().find(|t| t.uid == remote_tombstone.uid) {
+            Some(local) => local.version = merge_versions(&local.version, &remote_tombstone.version),
+            None => notes.tombstones.push(Tombstone {
+                deleted_at: if remote_tombstone.deleted_at.is_empty() {
+                    now_timestamp()
+                } else {
+                    remote_tombstone.deleted_at.clone()
+                },
+                ..remote_tombstone
+            }),
+        }
+    }
+
+    notes.rebuild_links();
+    report
+}
+
+/// Client: kirim semua data lokal ke server, lalu merge state gabungan dari server.
+/// Setelah itu blob attachment dipertukarkan supaya kedua sisi punya semua file yang
+/// direferensikan note.
+pub fn sync_with(notes: &mut Notes, url: &str) -> Result<MergeReport, Box<dyn std::error::Error>> {
+    let url = url.trim_end_matches('/');
+    let token = std::env::var(SYNC_TOKEN_ENV).ok();
+    let authorized = |request: ureq::Request| match &token {
+        Some(token) => request.set("Authorization", &format!("Bearer {}", token)),
+        None => request,
+    };
+    let mut response: SyncPayload = authorized(ureq::post(&format!("{}/api/sync", url)))
+        .send_json(payload(notes))?
+        .into_json()?;
+    let server_missing = std::mem::take(&mut response.missing_blobs);
+    let mut report = merge(notes, response);
+    // Setelah sync, content sekarang menjadi base untuk konflik berikutnya
+    for note in notes.notes.iter_mut().filter(|note| note.locked.is_none()) {
+        note.base = Some(note.content.clone());
+    }
+
+    let blob_url = |hash: &str| format!("{}/api/sync/blobs/{}", url, hash);
+    for hash in server_missing {
+        if !attachments::has_blob(&hash) {
+            report.blobs_missing += 1;
+            continue;
+        }
+        let bytes = attachments::read_blob(&hash)?;
+        authorized(ureq::put(&blob_url(&hash))).send_bytes(&bytes)?;
+        report.blobs_sent += 1;
+    }
+    for hash in missing_blobs(&notes.notes) {
+        let response = match authorized(ureq::get(&blob_url(&hash))).call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(404, _)) => {
+                report.blobs_missing += 1;
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut bytes = Vec::new();
+        response.into_reader().read_to_end(&mut bytes)?;
+        if attachments::hash_bytes(&bytes) != hash {
+            report.blobs_missing += 1;
+            continue;
+        }
+        attachments::store_blob(&bytes)?;
+        report.blobs_received += 1;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::{test_data_dir, Attachment};
+
+    fn version(entries: &[(&str, u64)]) -> Version {
+        entries.iter().map(|(device, counter)| (device.to_string(), *counter)).collect()
+    }
+
+    /// Notes baru; device_id() menulis ke data dir, jadi arahkan sekali ke folder sementara
+    fn notes() -> Notes {
+        test_data_dir();
+        Notes::new()
+    }
+
+    /// Store dengan satu note, plus salinan note itu seperti yang dimiliki device lain
+    fn shared_note(content: &str) -> (Notes, Note) {
+        let mut notes = notes();
+        let remote = notes.add(content.to_string()).clone();
+        (notes, remote)
+    }
+
+    /// Edit di device lain: counter "remote" di version vector naik
+    fn remote_edit(note: &mut Note, content: &str) {
+        note.content = content.to_string();
+        *note.version.entry("remote".to_string()).or_insert(0) += 1;
+    }
+
+    fn from_remote(notes: Vec<Note>, tombstones: Vec<Tombstone>) -> SyncPayload {
+        SyncPayload {
+            notes,
+            tombstones,
+            missing_blobs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn compare_version_vectors() {
+        let cases = [
+            (version(&[]), version(&[]), Causality::Equal),
+            (version(&[("a", 1)]), version(&[("a", 1)]), Causality::Equal),
+            (version(&[("a", 1)]), version(&[("a", 2)]), Causality::Before),
+            (version(&[("a", 1)]), version(&[("a", 1), ("b", 1)]), Causality::Before),
+            (version(&[]), version(&[("a", 1)]), Causality::Before),
+            (version(&[("a", 3), ("b", 1)]), version(&[("a", 2), ("b", 1)]), Causality::After),
+            (version(&[("a", 2), ("b", 1)]), version(&[("a", 1), ("b", 2)]), Causality::Concurrent),
+            (version(&[("a", 1)]), version(&[("b", 1)]), Causality::Concurrent),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(compare(&a, &b), expected, "{:?} vs {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn merge_versions_takes_highest_counter() {
+        let merged = merge_versions(&version(&[("a", 2), ("b", 1)]), &version(&[("b", 3), ("c", 1)]));
+        assert_eq!(merged, version(&[("a", 2), ("b", 3), ("c", 1)]));
+        assert_eq!(compare(&merged, &version(&[("a", 2), ("b", 1)])), Causality::After);
+    }
+
+    #[test]
+    fn newer_remote_replaces_local() {
+        let (mut notes, mut remote) = shared_note("first");
+        let id = notes.notes[0].id;
+        remote_edit(&mut remote, "second");
+
+        let report = merge(&mut notes, from_remote(vec![remote], Vec::new()));
+        assert_eq!(report.updated, 1);
+        let note = notes.get(id).unwrap();
+        assert_eq!(note.content, "second");
+        assert!(!note.has_conflict());
+    }
+
+    #[test]
+    fn older_or_equal_remote_is_ignored() {
+        let (mut notes, remote) = shared_note("first");
+        notes.notes[0].content = "local edit".to_string();
+        notes.notes[0].touch();
+
+        let report = merge(&mut notes, from_remote(vec![remote.clone(), remote], Vec::new()));
+        assert!(report.is_empty());
+        assert_eq!(notes.notes[0].content, "local edit");
+    }
+
+    #[test]
+    fn concurrent_edits_keep_both_versions() {
+        let (mut notes, mut remote) = shared_note("line\n");
+        notes.notes[0].base = Some("line\n".to_string());
+        notes.notes[0].content = "ours\n".to_string();
+        notes.notes[0].touch();
+        remote_edit(&mut remote, "theirs\n");
+        let expected = merge_versions(&notes.notes[0].version, &remote.version);
+
+        let report = merge(&mut notes, from_remote(vec![remote], Vec::new()));
+        assert_eq!(report.conflicts, 1);
+        let note = &notes.notes[0];
+        let conflict = note.conflict.as_ref().unwrap();
+        assert_eq!(conflict.base.as_deref(), Some("line\n"));
+        assert_eq!(conflict.ours, "ours\n");
+        assert_eq!(conflict.theirs, "theirs\n");
+        assert_eq!(note.content, "ours\n");
+        assert_eq!(note.version, expected);
+    }
+
+    fn attachment(name: &str, hash: &str) -> Attachment {
+        Attachment {
+            name: name.to_string(),
+            hash: hash.to_string(),
+            size: 1,
+            added_at: now_timestamp(),
+        }
+    }
+
+    #[test]
+    fn concurrent_metadata_changes_are_merged() {
+        let (mut notes, mut remote) = shared_note("same");
+        let local = &mut notes.notes[0];
+        local.attachments.push(attachment("local.txt", &"a".repeat(64)));
+        local.tags.push("work".to_string());
+        local.pinned = true;
+        local.touch();
+        local.updated_at = "2024-01-01 10:00:00".to_string();
+        remote.attachments.push(attachment("remote.txt", &"b".repeat(64)));
+        remote.attachments.push(attachment("local copy.txt", &"a".repeat(64)));
+        remote.tags.extend(["WORK".to_string(), "home".to_string()]);
+        remote.archived = true;
+        remote.notebook = Some("Projects".to_string());
+        remote.updated_at = "2024-01-01 11:00:00".to_string();
+        remote_edit(&mut remote, "same");
+
+        let report = merge(&mut notes, from_remote(vec![remote.clone()], Vec::new()));
+        assert!(report.is_empty());
+        let note = &notes.notes[0];
+        let names: Vec<_> = note.attachments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["local.txt", "remote.txt"]);
+        assert_eq!(note.tags, ["work", "home"]);
+        // Versi remote lebih baru: field skalarnya yang dipakai
+        assert!(!note.pinned);
+        assert!(note.archived);
+        assert_eq!(note.notebook.as_deref(), Some("Projects"));
+        assert_eq!(note.updated_at, remote.updated_at);
+    }
+
+    #[test]
+    fn concurrent_metadata_merge_is_the_same_on_both_sides() {
+        let (mut notes, mut remote) = shared_note("text");
+        let mut local = notes.notes[0].clone();
+        local.pinned = true;
+        local.notebook = Some("Home".to_string());
+        local.updated_at = "2024-05-01 09:00:00".to_string();
+        *local.version.entry("local".to_string()).or_insert(0) += 1;
+        notes.notes[0] = local.clone();
+        remote.archived = true;
+        remote.updated_at = "2024-05-01 08:00:00".to_string();
+        remote_edit(&mut remote, "text");
+
+        let mut other = self::notes();
+        merge(&mut other, from_remote(vec![remote.clone()], Vec::new()));
+        merge(&mut notes, from_remote(vec![remote], Vec::new()));
+        merge(&mut other, from_remote(vec![local], Vec::new()));
+        let (a, b) = (&notes.notes[0], &other.notes[0]);
+        assert_eq!((a.pinned, a.archived, &a.notebook), (true, false, &Some("Home".to_string())));
+        assert_eq!((a.pinned, a.archived, &a.notebook), (b.pinned, b.archived, &b.notebook));
+        assert_eq!(a.version, b.version);
+    }
+
+    #[test]
+    fn remote_attachments_with_invalid_hashes_are_dropped() {
+        let mut notes = notes();
+        let mut remote = self::notes().add("with files".to_string()).clone();
+        let valid = "C".repeat(64);
+        remote.attachments = vec![
+            attachment("ok.txt", &valid),
+            attachment("short.txt", "abc"),
+            attachment("escape.txt", &format!("../{}", "d".repeat(61))),
+            attachment("..\\..\\evil/name\u{7}.txt", &"e".repeat(64)),
+        ];
+
+        merge(&mut notes, from_remote(vec![remote], Vec::new()));
+        let attachments = &notes.notes[0].attachments;
+        let kept: Vec<_> = attachments.iter().map(|a| (a.name.as_str(), a.hash.as_str())).collect();
+        assert_eq!(kept, [("ok.txt", "c".repeat(64).as_str()), ("name.txt", "e".repeat(64).as_str())]);
+    }
+
+    #[test]
+    fn concurrent_identical_edits_are_not_a_conflict() {
+        let (mut notes, mut remote) = shared_note("first");
+        notes.notes[0].content = "same".to_string();
+        notes.notes[0].touch();
+        remote_edit(&mut remote, "same");
+
+        let report = merge(&mut notes, from_remote(vec![remote], Vec::new()));
+        assert!(report.is_empty());
+        assert!(!notes.notes[0].has_conflict());
+    }
+
+    #[test]
+    fn concurrent_edit_of_locked_note_makes_conflicted_copy() {
+        let (mut notes, mut remote) = shared_note("");
+        let sealed = crate::crypto::seal("secret", b"locked text").unwrap();
+        notes.notes[0].locked = Some(sealed.clone());
+        notes.notes[0].touch();
+        remote.locked = Some(sealed);
+        remote.updated_at = "2999-01-01 00:00:00".to_string();
+        remote_edit(&mut remote, "");
+        let remote_uid = remote.uid.clone();
+
+        let report = merge(&mut notes, from_remote(vec![remote], Vec::new()));
+        assert_eq!(report.conflicts, 1);
+        assert_eq!(notes.notes.len(), 2);
+        // updated_at remote lebih baru: versi remote menang, versi lokal jadi salinan
+        let copy = &notes.notes[1];
+        assert_ne!(copy.uid, remote_uid);
+        assert!(copy.is_locked());
+        assert!(copy.content.ends_with("(conflicted copy)"));
+        assert!(notes.notes.iter().all(|note| !note.has_conflict()));
+    }
+
+    #[test]
+    fn unknown_remote_note_is_added() {
+        let mut notes = notes();
+        let remote = notes.add("elsewhere".to_string()).clone();
+        let mut other = self::notes();
+
+        let report = merge(&mut other, from_remote(vec![remote.clone()], Vec::new()));
+        assert_eq!(report.added, 1);
+        assert_eq!(other.notes[0].uid, remote.uid);
+        assert_eq!(other.notes[0].content, "elsewhere");
+    }
+
+    #[test]
+    fn tombstone_deletes_unchanged_note() {
+        let (mut notes, remote) = shared_note("bye");
+        let tombstone = Tombstone {
+            uid: remote.uid.clone(),
+            version: merge_versions(&remote.version, &version(&[("remote", 1)])),
+            deleted_at: String::new(),
+        };
+
+        let report = merge(&mut notes, from_remote(Vec::new(), vec![tombstone]));
+        assert_eq!(report.deleted, 1);
+        assert!(notes.notes.is_empty());
+        assert_eq!(notes.tombstones.len(), 1);
+        assert!(!notes.tombstones[0].deleted_at.is_empty());
+    }
+
+    #[test]
+    fn local_edit_survives_concurrent_delete() {
+        let (mut notes, remote) = shared_note("keep me");
+        notes.notes[0].touch();
+        let tombstone = Tombstone {
+            uid: remote.uid.clone(),
+            version: merge_versions(&remote.version, &version(&[("remote", 1)])),
+            deleted_at: now_timestamp(),
+        };
+
+        let report = merge(&mut notes, from_remote(Vec::new(), vec![tombstone.clone()]));
+        assert_eq!(report.deleted, 0);
+        assert_eq!(notes.notes.len(), 1);
+        // Note yang tersisa sekarang lebih baru dari tombstone, jadi menang di device lain juga
+        assert_eq!(compare(&notes.notes[0].version, &tombstone.version), Causality::After);
+    }
+
+    #[test]
+    fn remote_edit_restores_locally_deleted_note() {
+        let (mut notes, mut remote) = shared_note("first");
+        let id = notes.notes[0].id;
+        notes.delete(id);
+        remote_edit(&mut remote, "edited elsewhere");
+
+        let report = merge(&mut notes, from_remote(vec![remote], Vec::new()));
+        assert_eq!(report.added, 1);
+        assert!(notes.tombstones.is_empty());
+        assert_eq!(notes.notes[0].content, "edited elsewhere");
+    }
+
+    #[test]
+    fn stale_remote_note_stays_deleted() {
+        let (mut notes, remote) = shared_note("first");
+        let id = notes.notes[0].id;
+        notes.delete(id);
+
+        let report = merge(&mut notes, from_remote(vec![remote], Vec::new()));
+        assert!(report.is_empty());
+        assert!(notes.notes.is_empty());
+        assert_eq!(notes.tombstones.len(), 1);
+    }
+}
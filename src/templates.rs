@@ -0,0 +1,287 @@
+// Note templates dan jadwal recurring
+// Template disimpan sebagai file di folder templates/<name>.md
+// Jadwal disimpan di schedules.json dan dijalankan setiap binary start
+
+#![allow(dead_code)]
+
+use crate::notes::{data_path, save_notes, write_atomic, Notes};
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+pub const TEMPLATES_DIR: &str = "templates";
+pub const SCHEDULES_FILE: &str = "schedules.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Recurrence {
+    Daily,
+    Weekly(Weekday),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Schedule {
+    pub template: String,
+    pub every: Recurrence,
+    pub last_run: Option<NaiveDate>,
+}
+
+impl Recurrence {
+    pub fn parse(kind: &str, weekday: Option<&str>) -> Result<Self, String> {
+        match kind {
+            "daily" => Ok(Recurrence::Daily),
+            "weekly" => {
+                let day = weekday.ok_or_else(|| t!("schedule-weekday-missing"))?;
+                day.parse::<Weekday>()
+                    .map(Recurrence::Weekly)
+                    .map_err(|_| t!("schedule-invalid-weekday", day = day))
+            }
+            _ => Err(t!("schedule-invalid-recurrence", every = kind)),
+        }
+    }
+
+    /// Tanggal terakhir (<= today) saat jadwal ini seharusnya jalan
+    fn last_occurrence(&self, today: NaiveDate) -> NaiveDate {
+        match self {
+            Recurrence::Daily => today,
+            Recurrence::Weekly(day) => {
+                let diff = (today.weekday().num_days_from_monday() + 7
+                    - day.num_days_from_monday())
+                    % 7;
+                today - Duration::days(diff as i64)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Recurrence::Daily => write!(f, "daily"),
+            Recurrence::Weekly(day) => write!(f, "weekly on {}", day),
+        }
+    }
+}
+
+fn template_path(name: &str) -> PathBuf {
+    data_path(TEMPLATES_DIR).join(format!("{}.md", name))
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub fn load_template(name: &str) -> io::Result<String> {
+    if !valid_name(name) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            t!("template-invalid-name", name = name),
+        ));
+    }
+    fs::read_to_string(template_path(name))
+}
+
+pub fn save_template(name: &str, content: &str) -> io::Result<()> {
+    if !valid_name(name) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            t!("template-invalid-name", name = name),
+        ));
+    }
+    fs::create_dir_all(data_path(TEMPLATES_DIR))?;
+    fs::write(template_path(name), content)
+}
+
+pub fn list_templates() -> Vec<String> {
+    let mut names: Vec<String> = match fs::read_dir(data_path(TEMPLATES_DIR)) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().is_some_and(|ext| ext == "md") {
+                    path.file_stem().map(|s| s.to_string_lossy().to_string())
+                } else {
+                    None
+                }
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    names.sort();
+    names
+}
+
+/// Ganti placeholder {{date}}, {{weekday}} dan {{time}} dengan nilai untuk tanggal tertentu
+pub fn render(template: &str, date: NaiveDate) -> String {
+    let time = Local::now().format("%H:%M").to_string();
+    template
+        .replace("{{date}}", &date.format("%Y-%m-%d").to_string())
+        .replace("{{weekday}}", &date.format("%A").to_string())
+        .replace("{{time}}", &time)
+}
+
+/// File yang belum ada berarti belum ada jadwal. File yang rusak jadi error, bukan
+/// daftar kosong, supaya save berikutnya tidak menghapus semua jadwal.
+pub fn load_schedules() -> io::Result<Vec<Schedule>> {
+    let path = data_path(SCHEDULES_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    serde_json::from_str(&content).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            t!("schedules-invalid", path = path.display().to_string(), error = e.to_string()),
+        )
+    })
+}
+
+pub fn save_schedules(schedules: &[Schedule]) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(schedules)?;
+    write_atomic(&data_path(SCHEDULES_FILE), json.as_bytes())
+}
+
+/// Jadwal yang sudah jatuh tempo: isi note yang harus dibuat, dan semua jadwal
+/// dengan `last_run` yang sudah diperbarui (disimpan setelah notes-nya tersimpan)
+pub struct Due {
+    pub contents: Vec<String>,
+    pub schedules: Vec<Schedule>,
+}
+
+/// Baca jadwal dan template untuk hari ini. Hanya membaca file; notes tidak disentuh,
+/// jadi web server bisa memanggilnya tanpa memegang lock notes.
+pub fn due_schedules() -> io::Result<Due> {
+    Ok(due_on(load_schedules()?, Local::now().date_naive()))
+}
+
+fn due_on(mut schedules: Vec<Schedule>, today: NaiveDate) -> Due {
+    let mut contents = Vec::new();
+    for schedule in schedules.iter_mut() {
+        let due = schedule.every.last_occurrence(today);
+        if schedule.last_run.is_some_and(|last| last >= due) {
+            continue;
+        }
+        match load_template(&schedule.template) {
+            Ok(template) => {
+                contents.push(render(&template, due));
+                schedule.last_run = Some(due);
+            }
+            Err(e) => {
+                eprintln!("⚠️  {}", t!("schedule-skipped", name = &schedule.template, error = e.to_string()));
+            }
+        }
+    }
+    Due { contents, schedules }
+}
+
+/// Buat note dari setiap jadwal yang sudah jatuh tempo, lalu simpan notes dan jadwal.
+/// Return jumlah note yang dibuat.
+pub fn run_due_schedules(notes: &mut Notes) -> io::Result<usize> {
+    let due = due_schedules()?;
+    if due.contents.is_empty() {
+        return Ok(0);
+    }
+    for content in &due.contents {
+        notes.add(content.clone());
+    }
+    save_notes(notes)?;
+    save_schedules(&due.schedules)?;
+    Ok(due.contents.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notes::test_data_dir;
+
+    fn date(text: &str) -> NaiveDate {
+        text.parse().unwrap()
+    }
+
+    fn schedule(template: &str, every: Recurrence, last_run: Option<&str>) -> Schedule {
+        Schedule {
+            template: template.to_string(),
+            every,
+            last_run: last_run.map(date),
+        }
+    }
+
+    #[test]
+    fn last_occurrence_goes_back_to_the_weekday() {
+        // 2024-05-15 hari Rabu
+        let wednesday = date("2024-05-15");
+        assert_eq!(Recurrence::Daily.last_occurrence(wednesday), wednesday);
+        assert_eq!(Recurrence::Weekly(Weekday::Wed).last_occurrence(wednesday), wednesday);
+        assert_eq!(Recurrence::Weekly(Weekday::Mon).last_occurrence(wednesday), date("2024-05-13"));
+        assert_eq!(Recurrence::Weekly(Weekday::Thu).last_occurrence(wednesday), date("2024-05-09"));
+        assert_eq!(Recurrence::Weekly(Weekday::Sun).last_occurrence(date("2024-05-13")), date("2024-05-12"));
+        // Melewati batas bulan dan tahun
+        assert_eq!(Recurrence::Weekly(Weekday::Fri).last_occurrence(date("2024-01-01")), date("2023-12-29"));
+    }
+
+    #[test]
+    fn render_fills_placeholders() {
+        let text = render("{{date}} {{weekday}} {{time}} {{other}}", date("2024-05-15"));
+        let (start, rest) = text.split_at("2024-05-15 Wednesday ".len());
+        assert_eq!(start, "2024-05-15 Wednesday ");
+        let (time, rest) = rest.split_once(' ').unwrap();
+        assert!(chrono::NaiveTime::parse_from_str(time, "%H:%M").is_ok(), "{}", time);
+        assert_eq!(rest, "{{other}}");
+    }
+
+    #[test]
+    fn due_schedules_render_and_mark_last_run() {
+        test_data_dir();
+        save_template("due-standup", "Standup {{date}}").unwrap();
+        save_template("due-review", "Review {{weekday}}").unwrap();
+        let today = date("2024-05-15");
+        let schedules = vec![
+            schedule("due-standup", Recurrence::Daily, Some("2024-05-14")),
+            schedule("due-standup", Recurrence::Daily, Some("2024-05-15")),
+            schedule("due-review", Recurrence::Weekly(Weekday::Mon), None),
+            schedule("due-review", Recurrence::Weekly(Weekday::Mon), Some("2024-05-13")),
+            schedule("due-missing", Recurrence::Daily, None),
+        ];
+        let due = due_on(schedules, today);
+        // Jadwal mingguan yang terlewat memakai tanggal kejadian terakhirnya
+        assert_eq!(due.contents, ["Standup 2024-05-15", "Review Monday"]);
+        let last_runs: Vec<_> = due.schedules.iter().map(|schedule| schedule.last_run).collect();
+        assert_eq!(
+            last_runs,
+            [
+                Some(today),
+                Some(today),
+                Some(date("2024-05-13")),
+                Some(date("2024-05-13")),
+                // Template yang hilang dilewati dan dicoba lagi lain kali
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn schedules_file_round_trip_and_corruption() {
+        test_data_dir();
+        let path = data_path(SCHEDULES_FILE);
+        let _ = fs::remove_file(&path);
+        assert!(load_schedules().unwrap().is_empty());
+
+        let schedules = vec![schedule("daily", Recurrence::Weekly(Weekday::Fri), Some("2024-05-10"))];
+        save_schedules(&schedules).unwrap();
+        let loaded = load_schedules().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].every, Recurrence::Weekly(Weekday::Fri));
+        assert_eq!(loaded[0].last_run, Some(date("2024-05-10")));
+        assert!(!path.with_extension("tmp").exists());
+
+        fs::write(&path, "[{\"template\": ").unwrap();
+        let error = load_schedules().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
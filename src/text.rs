@@ -0,0 +1,60 @@
+// Helper teks untuk terminal yang tidak bisa menampilkan emoji atau Unicode
+// (pipe ke file, NO_COLOR, mode ASCII dan screen reader di TUI)
+
+#![allow(dead_code)]
+
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32, 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x1F000..=0x1FAFF | 0xFE0F)
+}
+
+/// Buang emoji beserta satu spasi setelahnya: "✅ Saved" -> "Saved"
+pub fn strip_emoji(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_emoji(c) {
+            while chars.peek().is_some_and(|next| is_emoji(*next)) {
+                chars.next();
+            }
+            if chars.peek() == Some(&' ') {
+                chars.next();
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Buang ikon di awal teks saja: "\n📝 Your Notes:" -> "\nYour Notes:".
+/// Emoji di tempat lain (nama file, isi note dalam pesan) dibiarkan.
+pub fn strip_icon(text: &str) -> String {
+    let rest = text.trim_start();
+    let indent = &text[..text.len() - rest.len()];
+    let after_icon = rest.trim_start_matches(is_emoji);
+    if after_icon.len() == rest.len() {
+        return text.to_string();
+    }
+    format!("{}{}", indent, after_icon.trim_start_matches(' '))
+}
+
+/// Ganti simbol Unicode yang sering dipakai UI dengan padanan ASCII.
+/// Emoji dibuang dulu; karakter non-ASCII lain (isi note) dibiarkan.
+pub fn to_ascii(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in strip_emoji(text).chars() {
+        match c {
+            '→' => out.push_str("->"),
+            '←' => out.push_str("<-"),
+            '↑' => out.push_str("Up"),
+            '↓' => out.push_str("Down"),
+            '—' | '–' | '─' | '═' => out.push('-'),
+            '│' | '║' => out.push('|'),
+            '…' => out.push_str("..."),
+            '“' | '”' => out.push('"'),
+            '‘' | '’' => out.push('\''),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,513 @@
+// State dan key handling TUI. Rendering ada di ui.rs, terminal di mod.rs.
+
+use super::editor::TextArea;
+use super::keymap::{display_keys, Action, Key, Keymap, Lookup};
+use crate::merge;
+use crate::notes::{now_timestamp, save_notes, try_load_notes, Conflict, Note, Notes};
+use crate::sync;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::widgets::ListState;
+
+pub enum Mode {
+    Normal,
+    /// Mengetik query di kotak pencarian
+    Search,
+    /// Popup editor
+    Editor { draft: Draft, area: TextArea },
+    SyncUrl(TextArea),
+    Resolve(usize),
+    /// Overlay daftar key binding aktif
+    Help,
+}
+
+/// Note yang sedang diedit; `id` None berarti note baru.
+/// `original` adalah isi saat editor dibuka, untuk mendeteksi perubahan dari luar.
+pub struct Draft {
+    pub id: Option<usize>,
+    pub original: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Focus {
+    List,
+    Detail,
+}
+
+/// Pekerjaan yang butuh akses ke terminal, dijalankan oleh loop di mod.rs
+pub enum Task {
+    /// Buka $EDITOR; `resolve` true kalau hasilnya untuk menyelesaikan konflik
+    ExternalEditor {
+        draft: Draft,
+        text: String,
+        resolve: bool,
+    },
+    /// Sync dijalankan setelah status "Syncing..." sempat digambar
+    Sync(String),
+}
+
+/// Note yang tampil di list: bukan arsip, cocok dengan pencarian, yang di-pin di atas.
+/// Pencarian mencocokkan isi dan tag, tanpa membedakan huruf besar/kecil.
+pub fn visible_notes<'a>(notes: &'a Notes, search: &str) -> Vec<&'a Note> {
+    let query = search.to_lowercase();
+    let mut visible: Vec<&Note> = notes
+        .notes
+        .iter()
+        .filter(|note| !note.archived)
+        .filter(|note| {
+            query.is_empty()
+                || note.display_content().to_lowercase().contains(&query)
+                || note.tags.iter().any(|tag| tag.to_lowercase().contains(&query))
+        })
+        .collect();
+    visible.sort_by_key(|note| !note.pinned);
+    visible
+}
+
+pub struct App {
+    pub notes: Notes,
+    /// Id note yang tampil di list, sudah difilter dan diurutkan
+    pub visible: Vec<usize>,
+    pub list_state: ListState,
+    pub search: TextArea,
+    pub mode: Mode,
+    pub focus: Focus,
+    pub detail_scroll: u16,
+    /// Pesan di status bar; bool true berarti error
+    pub status: Option<(String, bool)>,
+    pub task: Option<Task>,
+    pub quit: bool,
+    pub keymap: Keymap,
+    /// Tombol yang sudah ditekan untuk binding multi-tombol (mis. `g` dari `gg`)
+    pub pending: Vec<Key>,
+    /// Note yang dihapus, terbaru di akhir, untuk undo
+    deleted: Vec<Note>,
+    sync_url: String,
+}
+
+impl App {
+    pub fn new(notes: Notes) -> Self {
+        let (keymap, keymap_error) = Keymap::load();
+        let mut app = App {
+            notes,
+            visible: Vec::new(),
+            list_state: ListState::default(),
+            search: TextArea::new("", false),
+            mode: Mode::Normal,
+            focus: Focus::List,
+            detail_scroll: 0,
+            status: None,
+            task: None,
+            quit: false,
+            keymap,
+            pending: Vec::new(),
+            deleted: Vec::new(),
+            sync_url: std::env::var(sync::SYNC_URL_ENV).unwrap_or_default(),
+        };
+        app.refresh();
+        if let Some(e) = keymap_error {
+            app.error(e);
+        }
+        app
+    }
+
+    pub fn selected_id(&self) -> Option<usize> {
+        self.list_state.selected().and_then(|i| self.visible.get(i).copied())
+    }
+
+    pub fn selected(&self) -> Option<&Note> {
+        self.selected_id().and_then(|id| self.notes.get(id))
+    }
+
+    /// Hitung ulang isi list, usahakan note yang dipilih tetap terpilih
+    pub fn refresh(&mut self) {
+        let selected = self.selected_id();
+        self.visible = visible_notes(&self.notes, &self.search.text())
+            .iter()
+            .map(|note| note.id)
+            .collect();
+
+        let index = selected
+            .and_then(|id| self.visible.iter().position(|v| *v == id))
+            .or(if self.visible.is_empty() { None } else { Some(0) })
+            .map(|i| i.min(self.visible.len().saturating_sub(1)));
+        self.list_state.select(index);
+    }
+
+    fn select_index(&mut self, index: usize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        self.list_state.select(Some(index.min(self.visible.len() - 1)));
+        self.detail_scroll = 0;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        self.select_index((current + delta).max(0) as usize);
+    }
+
+    fn info(&mut self, message: impl Into<String>) {
+        self.status = Some((message.into(), false));
+    }
+
+    fn error(&mut self, message: impl Into<String>) {
+        self.status = Some((message.into(), true));
+    }
+
+    /// Simpan ke disk; pesan sukses hanya ditampilkan kalau save berhasil
+    fn save(&mut self, message: impl Into<String>) {
+        match save_notes(&self.notes) {
+            Ok(()) => self.info(message),
+            Err(e) => self.error(t!("error-saving", error = e.to_string())),
+        }
+        self.refresh();
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.quit = true;
+            return;
+        }
+        match std::mem::replace(&mut self.mode, Mode::Normal) {
+            Mode::Normal => self.handle_normal(key),
+            Mode::Search => self.handle_search(key),
+            Mode::Editor { draft, area } => self.handle_editor(key, draft, area),
+            Mode::SyncUrl(area) => self.handle_sync_url(key, area),
+            Mode::Resolve(id) => self.handle_resolve(key, id),
+            // Tombol apa pun menutup help
+            Mode::Help => {}
+        }
+    }
+
+    /// Kumpulkan tombol sampai cocok dengan satu binding
+    fn handle_normal(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Esc {
+            if !self.pending.is_empty() {
+                self.pending.clear();
+            } else if !self.search.is_empty() {
+                self.search = TextArea::new("", false);
+                self.refresh();
+            }
+            return;
+        }
+        self.pending.push(Key::from(key));
+        match self.keymap.lookup(&self.pending) {
+            Lookup::Action(action) => {
+                self.pending.clear();
+                self.perform(action);
+            }
+            Lookup::Pending => {}
+            Lookup::None => {
+                // Urutan tidak dikenal (mis. `gj`): coba lagi dengan tombol terakhir saja
+                let retry = self.pending.len() > 1;
+                self.pending.clear();
+                if retry {
+                    self.handle_normal(key);
+                }
+            }
+        }
+    }
+
+    pub fn pending_keys(&self) -> Option<String> {
+        (!self.pending.is_empty()).then(|| display_keys(&self.pending))
+    }
+
+    fn perform(&mut self, action: Action) {
+        let page = 10;
+        match (action, self.focus) {
+            (Action::Up, Focus::Detail) => self.detail_scroll = self.detail_scroll.saturating_sub(1),
+            (Action::Down, Focus::Detail) => self.detail_scroll = self.detail_scroll.saturating_add(1),
+            (Action::PageUp, Focus::Detail) => self.detail_scroll = self.detail_scroll.saturating_sub(page),
+            (Action::PageDown, Focus::Detail) => self.detail_scroll = self.detail_scroll.saturating_add(page),
+            (Action::Top, Focus::Detail) => self.detail_scroll = 0,
+            (Action::Up, _) => self.move_selection(-1),
+            (Action::Down, _) => self.move_selection(1),
+            (Action::PageUp, _) => self.move_selection(-(page as isize)),
+            (Action::PageDown, _) => self.move_selection(page as isize),
+            (Action::Top, _) => self.select_index(0),
+            (Action::Bottom, _) => self.select_index(self.visible.len().saturating_sub(1)),
+            (Action::ToggleFocus, _) => {
+                self.focus = match self.focus {
+                    Focus::List => Focus::Detail,
+                    Focus::Detail => Focus::List,
+                };
+            }
+            (Action::Search, _) => self.mode = Mode::Search,
+            (Action::Add, _) => {
+                self.mode = Mode::Editor {
+                    draft: Draft {
+                        id: None,
+                        original: String::new(),
+                    },
+                    area: TextArea::new("", true),
+                }
+            }
+            (Action::Edit, _) => self.start_edit(),
+            (Action::Delete, _) => self.delete_selected(),
+            (Action::Undo, _) => self.undo_delete(),
+            (Action::Pin, _) => self.toggle_pin(),
+            (Action::Sync, _) => self.mode = Mode::SyncUrl(TextArea::new(&self.sync_url, false)),
+            (Action::Resolve, _) => match self.selected() {
+                Some(note) if note.has_conflict() => self.mode = Mode::Resolve(note.id),
+                Some(_) => self.info(t!("tui-no-conflict")),
+                None => {}
+            },
+            (Action::Help, _) => self.mode = Mode::Help,
+            (Action::Quit, _) => self.quit = true,
+        }
+    }
+
+    fn handle_search(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.search = TextArea::new("", false);
+                self.refresh();
+            }
+            KeyCode::Enter => {}
+            KeyCode::Up | KeyCode::Down => {
+                self.move_selection(if key.code == KeyCode::Up { -1 } else { 1 });
+                self.mode = Mode::Search;
+            }
+            _ => {
+                self.search.input(key);
+                self.refresh();
+                self.mode = Mode::Search;
+            }
+        }
+    }
+
+    fn start_edit(&mut self) {
+        let Some(note) = self.selected() else {
+            return;
+        };
+        if note.is_locked() {
+            self.error(t!("tui-edit-locked"));
+            return;
+        }
+        self.mode = Mode::Editor {
+            draft: Draft {
+                id: Some(note.id),
+                original: note.content.clone(),
+            },
+            area: TextArea::new(&note.content, true),
+        };
+    }
+
+    fn handle_editor(&mut self, key: KeyEvent, draft: Draft, mut area: TextArea) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.info(t!("cancelled")),
+            KeyCode::Char('s') if ctrl => self.finish_edit(draft, area.text()),
+            KeyCode::Char('e') if ctrl => {
+                self.task = Some(Task::ExternalEditor {
+                    draft,
+                    text: area.text(),
+                    resolve: false,
+                })
+            }
+            _ => {
+                area.input(key);
+                self.mode = Mode::Editor { draft, area };
+            }
+        }
+    }
+
+    /// Simpan hasil editor (popup atau $EDITOR)
+    pub fn finish_edit(&mut self, draft: Draft, text: String) {
+        let content = text.trim_end_matches('\n').to_string();
+        if content.trim().is_empty() {
+            self.error(t!("tui-empty-note"));
+            return;
+        }
+        let Some(id) = draft.id else {
+            self.add_note(content, t!("tui-added"));
+            return;
+        };
+        let Some(note) = self.notes.get_mut(id) else {
+            // Dihapus dari luar selama diedit: jangan buang hasil edit
+            self.add_note(content, t!("tui-saved-as-new", id = id));
+            return;
+        };
+        if note.content == draft.original || note.content == content {
+            if note.edit(content) {
+                self.notes.rebuild_links();
+                self.save(t!("tui-updated", id = id));
+            } else {
+                self.info(t!("tui-no-changes"));
+            }
+            return;
+        }
+
+        // Diubah dari luar selama diedit: simpan kedua versi sebagai konflik
+        let theirs = std::mem::take(&mut note.content);
+        let theirs_updated_at = note.updated_at.clone();
+        note.edit(content.clone());
+        note.conflict = Some(Conflict {
+            base: Some(draft.original),
+            ours: content,
+            theirs,
+            theirs_updated_at,
+            detected_at: now_timestamp(),
+        });
+        self.notes.rebuild_links();
+        let resolve = self.keymap.keys_for(Action::Resolve).first().cloned().unwrap_or_default();
+        self.save(t!("tui-edit-conflict", id = id, key = resolve));
+    }
+
+    fn add_note(&mut self, content: String, message: String) {
+        let id = self.notes.add(content).id;
+        self.save(message);
+        if let Some(index) = self.visible.iter().position(|v| *v == id) {
+            self.select_index(index);
+        }
+    }
+
+    /// Baca ulang notebook setelah file berubah di disk (CLI, web, sync lain)
+    pub fn reload(&mut self) {
+        let notes = match try_load_notes() {
+            Ok(notes) => notes,
+            Err(e) => {
+                self.error(t!("tui-reload-failed", error = e.to_string()));
+                return;
+            }
+        };
+        // Event dari save TUI sendiri: isinya sama, tidak perlu apa-apa
+        if serde_json::to_string(&notes).ok() == serde_json::to_string(&self.notes).ok() {
+            return;
+        }
+        self.notes = notes;
+        self.refresh();
+
+        let warning = match &self.mode {
+            Mode::Editor {
+                draft: Draft { id: Some(id), original },
+                ..
+            } => match self.notes.get(*id) {
+                None => Some(t!("tui-editing-deleted", id = *id)),
+                Some(note) if note.content != *original => {
+                    Some(t!("tui-editing-changed", id = *id))
+                }
+                Some(_) => None,
+            },
+            _ => None,
+        };
+        match warning {
+            Some(warning) => self.error(warning),
+            None => self.info(t!("tui-reloaded")),
+        }
+    }
+
+    /// Hasil $EDITOR saat menyelesaikan konflik
+    pub fn finish_resolve(&mut self, id: usize, text: String) {
+        if merge::has_markers(&text) {
+            self.error(t!("conflict-markers-left"));
+            return;
+        }
+        self.resolve_with(id, text.trim_end_matches('\n').to_string());
+    }
+
+    fn delete_selected(&mut self) {
+        let Some(note) = self.selected().cloned() else {
+            return;
+        };
+        let id = note.id;
+        self.notes.delete(id);
+        self.deleted.push(note);
+        match self.keymap.keys_for(Action::Undo).first() {
+            Some(undo) => self.save(t!("tui-deleted-undo", id = id, key = undo)),
+            None => self.save(t!("tui-deleted", id = id)),
+        }
+    }
+
+    fn undo_delete(&mut self) {
+        let Some(note) = self.deleted.pop() else {
+            self.info(t!("tui-nothing-to-undo"));
+            return;
+        };
+        let id = self.notes.restore(note);
+        self.save(t!("tui-restored", id = id));
+        if let Some(index) = self.visible.iter().position(|v| *v == id) {
+            self.select_index(index);
+        }
+    }
+
+    fn toggle_pin(&mut self) {
+        let Some(id) = self.selected_id() else {
+            return;
+        };
+        let note = self.notes.get_mut(id).unwrap();
+        note.pinned = !note.pinned;
+        note.touch();
+        let message = if note.pinned {
+            t!("tui-pinned", id = id)
+        } else {
+            t!("tui-unpinned", id = id)
+        };
+        self.save(message);
+    }
+
+    fn handle_sync_url(&mut self, key: KeyEvent, mut area: TextArea) {
+        match key.code {
+            KeyCode::Esc => self.info(t!("cancelled")),
+            KeyCode::Enter => {
+                let url = area.text().trim().to_string();
+                if url.is_empty() {
+                    self.error(t!("tui-sync-no-url"));
+                    return;
+                }
+                self.sync_url = url.clone();
+                self.info(t!("tui-syncing", url = &url));
+                self.task = Some(Task::Sync(url));
+            }
+            _ => {
+                area.input(key);
+                self.mode = Mode::SyncUrl(area);
+            }
+        }
+    }
+
+    pub fn run_sync(&mut self, url: &str) {
+        match sync::sync_with(&mut self.notes, url) {
+            Ok(report) => self.save(t!("sync-done", url = url, report = report.to_string())),
+            Err(e) => self.error(t!("sync-failed", error = e.to_string())),
+        }
+    }
+
+    fn handle_resolve(&mut self, key: KeyEvent, id: usize) {
+        let Some(note) = self.notes.get(id) else {
+            return;
+        };
+        let (Some(conflict), Some(preview)) = (note.conflict.clone(), note.merge_preview()) else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('o') => self.resolve_with(id, conflict.ours),
+            KeyCode::Char('t') => self.resolve_with(id, conflict.theirs),
+            KeyCode::Char('m') if preview.clean => self.resolve_with(id, preview.text),
+            KeyCode::Char('m') => {
+                self.error(t!("tui-resolve-not-clean"));
+                self.mode = Mode::Resolve(id);
+            }
+            KeyCode::Char('e') => {
+                self.task = Some(Task::ExternalEditor {
+                    draft: Draft {
+                        id: Some(id),
+                        original: String::new(),
+                    },
+                    text: preview.text,
+                    resolve: true,
+                })
+            }
+            KeyCode::Esc => self.info(t!("cancelled")),
+            _ => self.mode = Mode::Resolve(id),
+        }
+    }
+
+    fn resolve_with(&mut self, id: usize, content: String) {
+        if let Some(note) = self.notes.get_mut(id) {
+            note.resolve_conflict(content);
+            self.notes.rebuild_links();
+            self.save(t!("tui-resolved", id = id));
+        }
+    }
+}
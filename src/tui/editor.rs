@@ -0,0 +1,130 @@
+// Editor teks sederhana untuk TUI: dipakai untuk isi note (multi-baris),
+// kotak pencarian dan input URL sync (satu baris).
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, Default)]
+pub struct TextArea {
+    lines: Vec<String>,
+    row: usize,
+    /// Posisi cursor dalam karakter (bukan byte)
+    col: usize,
+    multiline: bool,
+}
+
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len())
+}
+
+impl TextArea {
+    pub fn new(text: &str, multiline: bool) -> Self {
+        let mut lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        // Cursor di akhir teks
+        let row = lines.len() - 1;
+        let col = lines[row].chars().count();
+        TextArea {
+            lines,
+            row,
+            col,
+            multiline,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// (baris, kolom) cursor
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.trim().is_empty())
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// Return true kalau tombol dipakai oleh editor
+    pub fn input(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('u') if ctrl => {
+                let line = &mut self.lines[self.row];
+                *line = line.split_off(byte_index(line, self.col));
+                self.col = 0;
+            }
+            KeyCode::Char('a') if ctrl => self.col = 0,
+            KeyCode::Char(c) if !ctrl => {
+                let line = &mut self.lines[self.row];
+                line.insert(byte_index(line, self.col), c);
+                self.col += 1;
+            }
+            KeyCode::Enter if self.multiline => {
+                let line = &mut self.lines[self.row];
+                let rest = line.split_off(byte_index(line, self.col));
+                self.row += 1;
+                self.lines.insert(self.row, rest);
+                self.col = 0;
+            }
+            KeyCode::Backspace => {
+                if self.col > 0 {
+                    let line = &mut self.lines[self.row];
+                    line.remove(byte_index(line, self.col - 1));
+                    self.col -= 1;
+                } else if self.row > 0 {
+                    let line = self.lines.remove(self.row);
+                    self.row -= 1;
+                    self.col = self.line_len(self.row);
+                    self.lines[self.row].push_str(&line);
+                }
+            }
+            KeyCode::Delete => {
+                if self.col < self.line_len(self.row) {
+                    let line = &mut self.lines[self.row];
+                    line.remove(byte_index(line, self.col));
+                } else if self.row + 1 < self.lines.len() {
+                    let next = self.lines.remove(self.row + 1);
+                    self.lines[self.row].push_str(&next);
+                }
+            }
+            KeyCode::Left => {
+                if self.col > 0 {
+                    self.col -= 1;
+                } else if self.row > 0 {
+                    self.row -= 1;
+                    self.col = self.line_len(self.row);
+                }
+            }
+            KeyCode::Right => {
+                if self.col < self.line_len(self.row) {
+                    self.col += 1;
+                } else if self.row + 1 < self.lines.len() {
+                    self.row += 1;
+                    self.col = 0;
+                }
+            }
+            KeyCode::Up if self.row > 0 => {
+                self.row -= 1;
+                self.col = self.col.min(self.line_len(self.row));
+            }
+            KeyCode::Down if self.row + 1 < self.lines.len() => {
+                self.row += 1;
+                self.col = self.col.min(self.line_len(self.row));
+            }
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = self.line_len(self.row),
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,298 @@
+// Key binding TUI ala vim (j/k, gg/G, dd, u, /, ?)
+// Bisa diganti lewat <data dir>/keymap.json, contoh:
+//   { "delete": ["dd", "<Del>"], "add": ["o"], "page_down": ["<C-f>"] }
+// Action yang disebut di file menggantikan semua binding default-nya.
+
+use crate::notes::data_path;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+
+pub const KEYMAP_FILE: &str = "keymap.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    ToggleFocus,
+    Search,
+    Add,
+    Edit,
+    Delete,
+    Undo,
+    Pin,
+    Sync,
+    Resolve,
+    Help,
+    Quit,
+}
+
+impl Action {
+    /// Urutan tampil di help overlay
+    pub const ALL: [Action; 17] = [
+        Action::Up,
+        Action::Down,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Top,
+        Action::Bottom,
+        Action::ToggleFocus,
+        Action::Search,
+        Action::Add,
+        Action::Edit,
+        Action::Delete,
+        Action::Undo,
+        Action::Pin,
+        Action::Sync,
+        Action::Resolve,
+        Action::Help,
+        Action::Quit,
+    ];
+
+    pub fn description(self) -> String {
+        t!(match self {
+            Action::Up => "tui-action-up",
+            Action::Down => "tui-action-down",
+            Action::PageUp => "tui-action-page-up",
+            Action::PageDown => "tui-action-page-down",
+            Action::Top => "tui-action-top",
+            Action::Bottom => "tui-action-bottom",
+            Action::ToggleFocus => "tui-action-toggle-focus",
+            Action::Search => "tui-action-search",
+            Action::Add => "tui-action-add",
+            Action::Edit => "tui-action-edit",
+            Action::Delete => "tui-action-delete",
+            Action::Undo => "tui-action-undo",
+            Action::Pin => "tui-action-pin",
+            Action::Sync => "tui-action-sync",
+            Action::Resolve => "tui-action-resolve",
+            Action::Help => "tui-action-help",
+            Action::Quit => "tui-action-quit",
+        })
+    }
+
+    fn defaults(self) -> &'static [&'static str] {
+        match self {
+            Action::Up => &["k", "<Up>"],
+            Action::Down => &["j", "<Down>"],
+            Action::PageUp => &["<C-u>", "<PageUp>"],
+            Action::PageDown => &["<C-d>", "<PageDown>"],
+            Action::Top => &["gg", "<Home>"],
+            Action::Bottom => &["G", "<End>"],
+            Action::ToggleFocus => &["<Tab>"],
+            Action::Search => &["/"],
+            Action::Add => &["a", "o"],
+            Action::Edit => &["e", "<Enter>"],
+            Action::Delete => &["dd", "<Del>"],
+            Action::Undo => &["u"],
+            Action::Pin => &["p"],
+            Action::Sync => &["s"],
+            Action::Resolve => &["r"],
+            Action::Help => &["?"],
+            Action::Quit => &["q"],
+        }
+    }
+}
+
+/// Satu tombol; untuk karakter biasa Shift tidak disimpan (`G` sudah huruf besar)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    code: KeyCode,
+    ctrl: bool,
+    alt: bool,
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Key {
+            code: event.code,
+            ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+            alt: event.modifiers.contains(KeyModifiers::ALT),
+        }
+    }
+}
+
+const NAMED_KEYS: [(&str, KeyCode); 13] = [
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("Tab", KeyCode::Tab),
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Del", KeyCode::Delete),
+    ("BS", KeyCode::Backspace),
+];
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if !self.ctrl && !self.alt => return write!(f, "{}", c),
+            KeyCode::Char(c) => c.to_string(),
+            code => NAMED_KEYS
+                .iter()
+                .find(|(_, named)| *named == code)
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_else(|| format!("{:?}", code)),
+        };
+        let ctrl = if self.ctrl { "C-" } else { "" };
+        let alt = if self.alt { "A-" } else { "" };
+        write!(f, "<{}{}{}>", ctrl, alt, name)
+    }
+}
+
+fn parse_special(inner: &str) -> Option<Key> {
+    let (mut ctrl, mut alt, mut rest) = (false, false, inner);
+    loop {
+        if let Some(r) = rest.strip_prefix("C-") {
+            ctrl = true;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("A-") {
+            alt = true;
+            rest = r;
+        } else {
+            break;
+        }
+    }
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ if rest.eq_ignore_ascii_case("Space") => KeyCode::Char(' '),
+        _ => NAMED_KEYS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(rest))
+            .map(|(_, code)| *code)?,
+    };
+    Some(Key { code, ctrl, alt })
+}
+
+/// Parse notasi vim: `gg`, `G`, `<C-d>`, `<PageDown>`, `<A-x>`
+pub fn parse_keys(spec: &str) -> Result<Vec<Key>, String> {
+    let mut keys = Vec::new();
+    let mut rest = spec;
+    while let Some(c) = rest.chars().next() {
+        if c == '<' {
+            if let Some(end) = rest.find('>').filter(|end| *end > 1) {
+                let key = parse_special(&rest[1..end])
+                    .ok_or_else(|| t!("tui-keymap-unknown-key", key = &rest[..=end], spec = spec))?;
+                keys.push(key);
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        keys.push(Key {
+            code: KeyCode::Char(c),
+            ctrl: false,
+            alt: false,
+        });
+        rest = &rest[c.len_utf8()..];
+    }
+    if keys.is_empty() {
+        return Err(t!("tui-keymap-empty"));
+    }
+    Ok(keys)
+}
+
+pub enum Lookup {
+    Action(Action),
+    /// Tombol yang ditekan adalah awal dari binding yang lebih panjang (mis. `g` dari `gg`)
+    Pending,
+    None,
+}
+
+pub struct Keymap {
+    bindings: Vec<(Vec<Key>, Action)>,
+}
+
+impl Keymap {
+    fn from_specs(specs: &BTreeMap<Action, Vec<String>>) -> Result<Keymap, String> {
+        let mut bindings = Vec::new();
+        for action in Action::ALL {
+            let specs: Vec<&str> = match specs.get(&action) {
+                Some(specs) => specs.iter().map(String::as_str).collect(),
+                None => action.defaults().to_vec(),
+            };
+            for spec in specs {
+                bindings.push((parse_keys(spec)?, action));
+            }
+        }
+        // Binding yang merupakan awalan binding lain membuat binding itu tidak bisa dipakai
+        for (keys, action) in &bindings {
+            for (other, other_action) in &bindings {
+                if keys.len() <= other.len() && other.starts_with(keys) && action != other_action {
+                    return Err(t!(
+                        "tui-keymap-shadows",
+                        keys = display_keys(keys),
+                        action = format!("{:?}", action),
+                        other = display_keys(other),
+                        other_action = format!("{:?}", other_action),
+                    ));
+                }
+            }
+        }
+        Ok(Keymap { bindings })
+    }
+
+    pub fn default_keymap() -> Keymap {
+        Keymap::from_specs(&BTreeMap::new()).expect("default keymap is valid")
+    }
+
+    /// Baca keymap.json; kalau file tidak ada pakai default.
+    /// Kalau file tidak valid, return default beserta pesan error untuk status bar.
+    pub fn load() -> (Keymap, Option<String>) {
+        let path = data_path(KEYMAP_FILE);
+        let Ok(content) = fs::read_to_string(&path) else {
+            return (Keymap::default_keymap(), None);
+        };
+        let result = serde_json::from_str::<BTreeMap<Action, Vec<String>>>(&content)
+            .map_err(|e| e.to_string())
+            .and_then(|specs| Keymap::from_specs(&specs));
+        match result {
+            Ok(keymap) => (keymap, None),
+            Err(e) => (
+                Keymap::default_keymap(),
+                Some(t!("tui-keymap-ignored", path = path.display().to_string(), error = e)),
+            ),
+        }
+    }
+
+    pub fn lookup(&self, pending: &[Key]) -> Lookup {
+        let mut prefix = false;
+        for (keys, action) in &self.bindings {
+            if keys.as_slice() == pending {
+                return Lookup::Action(*action);
+            }
+            prefix |= keys.starts_with(pending);
+        }
+        if prefix {
+            Lookup::Pending
+        } else {
+            Lookup::None
+        }
+    }
+
+    /// Semua binding untuk satu action, dalam notasi vim
+    pub fn keys_for(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(keys, _)| display_keys(keys))
+            .collect()
+    }
+}
+
+pub fn display_keys(keys: &[Key]) -> String {
+    keys.iter().map(Key::to_string).collect()
+}
@@ -0,0 +1,199 @@
+// Mode screen reader: antarmuka baris per baris tanpa layout full-screen.
+// Tidak ada redraw, warna, border atau emoji; setiap output adalah kalimat biasa
+// sehingga bisa dibaca berurutan oleh screen reader (dan braille display).
+
+// Nama perintah (list, show, ...) sama di semua bahasa, seperti subcommand CLI.
+
+use super::app::visible_notes;
+use crate::i18n;
+use crate::notes::{save_notes, try_load_notes, Note, Notes};
+use crate::text::to_ascii;
+use std::io::{self, BufRead, Write};
+
+fn say(text: &str) {
+    println!("{}", to_ascii(text));
+}
+
+fn title(note: &Note) -> String {
+    if note.is_locked() {
+        t!("locked-note").to_lowercase()
+    } else {
+        note.title().to_string()
+    }
+}
+
+fn describe(note: &Note) -> String {
+    let mut text = t!("linear-note", id = note.id, title = title(note));
+    if note.pinned {
+        text.push(' ');
+        text.push_str(&t!("linear-pinned"));
+    }
+    if note.has_conflict() {
+        text.push(' ');
+        text.push_str(&t!("linear-has-conflict"));
+    }
+    text
+}
+
+fn list(notes: &Notes, search: &str) {
+    let visible = visible_notes(notes, search);
+    match visible.len() {
+        0 if search.is_empty() => say(&t!("linear-no-notes")),
+        0 => say(&t!("linear-no-match", search = search)),
+        n => say(&t!("linear-count", count = n)),
+    }
+    for note in visible {
+        say(&describe(note));
+    }
+}
+
+fn show(note: &Note) {
+    say(&describe(note));
+    say(&t!(
+        "linear-dates",
+        created = i18n::format_timestamp(&note.created_at),
+        updated = i18n::format_timestamp(&note.updated_at),
+    ));
+    if let Some(notebook) = &note.notebook {
+        say(&t!("linear-notebook", notebook = notebook));
+    }
+    if !note.tags.is_empty() {
+        say(&t!("linear-tags", tags = note.tags.join(", ")));
+    }
+    if note.is_locked() {
+        say(&t!("linear-locked"));
+    } else {
+        // Isi note dicetak apa adanya, hanya simbol UI yang diubah ke ASCII
+        println!("{}", note.content);
+    }
+    say(&t!("linear-end", id = note.id));
+}
+
+/// Baca isi note sampai baris yang hanya berisi titik
+fn read_content(lines: &mut impl Iterator<Item = io::Result<String>>) -> io::Result<Option<String>> {
+    say(&t!("linear-type-note"));
+    let mut content = Vec::new();
+    for line in lines.by_ref() {
+        let line = line?;
+        if line.trim() == "." {
+            let text = content.join("\n");
+            return Ok((!text.trim().is_empty()).then_some(text));
+        }
+        content.push(line);
+    }
+    Ok(None)
+}
+
+/// "yes" selalu diterima, juga kata "ya" dalam bahasa aktif
+fn is_yes(answer: &str) -> bool {
+    let answer = answer.trim();
+    answer.eq_ignore_ascii_case("yes") || answer.eq_ignore_ascii_case(&t!("cli-confirm-yes"))
+}
+
+fn save(notes: &Notes, message: &str) {
+    match save_notes(notes) {
+        Ok(()) => say(message),
+        Err(e) => say(&t!("error-saving", error = e.to_string())),
+    }
+}
+
+/// Baca ulang kalau notebook diubah CLI/web sejak perintah sebelumnya
+fn reload(notes: &mut Notes) {
+    if let Ok(latest) = try_load_notes() {
+        if serde_json::to_string(&latest).ok() != serde_json::to_string(notes).ok() {
+            *notes = latest;
+            say(&t!("linear-reloaded"));
+        }
+    }
+}
+
+pub fn run(mut notes: Notes) -> io::Result<()> {
+    say(&t!("linear-welcome"));
+    list(&notes, "");
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        let (command, arg) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        let arg = arg.trim();
+        reload(&mut notes);
+
+        let id = arg.parse::<usize>().ok();
+        let needs_note = matches!(command, "show" | "edit" | "delete" | "pin" | "unpin");
+        if needs_note && id.and_then(|id| notes.get(id)).is_none() {
+            match id {
+                Some(id) => say(&t!("linear-not-found", id = id)),
+                None => say(&t!("linear-need-id", command = command)),
+            }
+            continue;
+        }
+        let id = id.unwrap_or_default();
+
+        match command {
+            "" => {}
+            "list" | "l" => list(&notes, ""),
+            "search" | "/" => list(&notes, arg),
+            "show" => show(notes.get(id).unwrap()),
+            "add" => match read_content(&mut lines)? {
+                Some(content) => {
+                    let id = notes.add(content).id;
+                    save(&notes, &t!("linear-added", id = id));
+                }
+                None => say(&t!("linear-add-cancelled")),
+            },
+            "edit" => {
+                if notes.get(id).unwrap().is_locked() {
+                    say(&t!("linear-edit-locked"));
+                    continue;
+                }
+                match read_content(&mut lines)? {
+                    Some(content) => {
+                        if notes.get_mut(id).unwrap().edit(content) {
+                            notes.rebuild_links();
+                            save(&notes, &t!("linear-updated", id = id));
+                        } else {
+                            say(&t!("linear-no-changes"));
+                        }
+                    }
+                    None => say(&t!("linear-edit-cancelled")),
+                }
+            }
+            "delete" => {
+                say(&t!("linear-delete-confirm", id = id, title = title(notes.get(id).unwrap())));
+                print!("> ");
+                io::stdout().flush()?;
+                match lines.next().transpose()? {
+                    Some(answer) if is_yes(&answer) => {
+                        notes.delete(id);
+                        save(&notes, &t!("linear-deleted", id = id));
+                    }
+                    _ => say(&t!("cancelled")),
+                }
+            }
+            "pin" | "unpin" => {
+                let note = notes.get_mut(id).unwrap();
+                let pinned = command == "pin";
+                if note.pinned != pinned {
+                    note.pinned = pinned;
+                    note.touch();
+                }
+                let message = if pinned {
+                    t!("linear-pinned-note", id = id)
+                } else {
+                    t!("linear-unpinned-note", id = id)
+                };
+                save(&notes, &message);
+            }
+            "help" | "?" => say(&t!("linear-help")),
+            "quit" | "q" | "exit" => break,
+            _ => say(&t!("linear-unknown", command = command)),
+        }
+    }
+    say(&t!("linear-goodbye"));
+    Ok(())
+}
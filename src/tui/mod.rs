@@ -0,0 +1,107 @@
+// Full-screen TUI berbasis ratatui + crossterm
+// app.rs: state dan key handling, ui.rs: rendering, editor.rs: input teks,
+// keymap.rs: key binding (bisa diatur lewat keymap.json), watch.rs: live reload,
+// theme.rs: warna/ASCII/no-emoji, linear.rs: mode screen reader tanpa layout
+
+pub mod app;
+pub mod editor;
+pub mod keymap;
+pub mod linear;
+pub mod theme;
+pub mod ui;
+pub mod watch;
+
+use crate::merge;
+use crate::notes::Notes;
+use app::{App, Task};
+use theme::Theme;
+use watch::NotesWatcher;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::prelude::CrosstermBackend;
+use ratatui::Terminal;
+use std::io::{self, Stdout};
+use std::time::Duration;
+
+type Term = Terminal<CrosstermBackend<Stdout>>;
+
+fn enter() -> io::Result<()> {
+    terminal::enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)
+}
+
+fn leave() -> io::Result<()> {
+    terminal::disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)
+}
+
+/// Jalankan TUI sampai user keluar; terminal selalu dikembalikan ke mode normal
+pub fn run(notes: Notes, theme: Theme) -> io::Result<()> {
+    // Kalau panic, kembalikan terminal dulu supaya pesan panic terbaca
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = leave();
+        hook(info);
+    }));
+
+    enter()?;
+    let result = Terminal::new(CrosstermBackend::new(io::stdout())).and_then(|mut term| {
+        let mut app = App::new(notes);
+        // Tanpa watcher TUI tetap jalan, hanya tidak reload otomatis
+        let watcher = match NotesWatcher::new() {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                app.status = Some((t!("tui-watch-failed", error = e.to_string()), true));
+                None
+            }
+        };
+        event_loop(&mut term, &mut app, &theme, watcher)
+    });
+    leave()?;
+    result
+}
+
+fn event_loop(term: &mut Term, app: &mut App, theme: &Theme, mut watcher: Option<NotesWatcher>) -> io::Result<()> {
+    while !app.quit {
+        if watcher.as_mut().is_some_and(NotesWatcher::changed) {
+            app.reload();
+        }
+        term.draw(|frame| ui::draw(frame, app, theme))?;
+        if let Some(task) = app.task.take() {
+            run_task(term, app, task)?;
+            continue;
+        }
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => app.handle_key(key),
+            // Ukuran baru dipakai otomatis saat draw berikutnya
+            Event::Resize(_, _) => term.autoresize()?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn run_task(term: &mut Term, app: &mut App, task: Task) -> io::Result<()> {
+    match task {
+        Task::Sync(url) => app.run_sync(&url),
+        Task::ExternalEditor { draft, text, resolve } => {
+            // $EDITOR butuh terminal normal; keluar dari alternate screen sementara
+            leave()?;
+            let edited = merge::edit_in_editor(&text);
+            enter()?;
+            term.clear()?;
+            // Perubahan di disk selama $EDITOR terbuka harus masuk sebelum hasilnya disimpan
+            app.reload();
+            match (edited, draft.id) {
+                (Ok(text), Some(id)) if resolve => app.finish_resolve(id, text),
+                (Ok(text), _) => app.finish_edit(draft, text),
+                (Err(e), _) => app.status = Some((t!("error-editor", error = e.to_string()), true)),
+            }
+        }
+    }
+    Ok(())
+}
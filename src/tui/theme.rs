@@ -0,0 +1,136 @@
+// Tema TUI: warna, jenis border dan simbol.
+// Mode ASCII dan no-emoji untuk terminal/font yang tidak lengkap;
+// mode screen reader tidak memakai tema ini (lihat linear.rs).
+
+use crate::text::{strip_emoji, to_ascii};
+use clap::ValueEnum;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Borders};
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ThemeName {
+    Default,
+    /// Bright, bold colors
+    HighContrast,
+    /// No colors, only bold and reverse video
+    Mono,
+}
+
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+pub struct Theme {
+    /// Border panel yang aktif, nama tombol di help
+    pub accent: Style,
+    /// Id note, label metadata, hint di status bar
+    pub muted: Style,
+    pub warning: Style,
+    pub error: Style,
+    /// Baris yang dipilih di list
+    pub selected: Style,
+    border: border::Set,
+    ascii: bool,
+    emoji: bool,
+}
+
+impl Theme {
+    pub fn new(name: ThemeName, ascii: bool, no_emoji: bool) -> Theme {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let name = if no_color && name == ThemeName::Default {
+            ThemeName::Mono
+        } else {
+            name
+        };
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let (accent, muted, warning, error, selected) = match name {
+            ThemeName::Default => (
+                Style::default().fg(Color::Cyan),
+                Style::default().fg(Color::DarkGray),
+                Style::default().fg(Color::Yellow),
+                Style::default().fg(Color::Red),
+                Style::default().add_modifier(Modifier::REVERSED),
+            ),
+            ThemeName::HighContrast => (
+                bold.fg(Color::LightYellow),
+                Style::default().fg(Color::White),
+                bold.fg(Color::LightYellow),
+                bold.fg(Color::LightRed),
+                bold.fg(Color::Black).bg(Color::LightYellow),
+            ),
+            ThemeName::Mono => (
+                bold,
+                Style::default(),
+                bold,
+                bold,
+                Style::default().add_modifier(Modifier::REVERSED),
+            ),
+        };
+        Theme {
+            accent,
+            muted,
+            warning,
+            error,
+            selected,
+            border: if ascii { ASCII_BORDER } else { border::PLAIN },
+            ascii,
+            emoji: !ascii && !no_emoji,
+        }
+    }
+
+    pub fn block(&self, title: &str, focused: bool) -> Block<'static> {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.border)
+            .title(self.text(title).into_owned());
+        if focused {
+            block.border_style(self.accent)
+        } else {
+            block
+        }
+    }
+
+    /// Teks UI sesuai mode: tanpa emoji, dan tanpa simbol Unicode di mode ASCII
+    pub fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.ascii {
+            Cow::Owned(to_ascii(text))
+        } else if !self.emoji {
+            Cow::Owned(strip_emoji(text))
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    pub fn pin_marker(&self) -> &'static str {
+        if self.emoji {
+            "📌 "
+        } else {
+            "* "
+        }
+    }
+
+    pub fn conflict_marker(&self) -> &'static str {
+        if self.emoji {
+            "⚠️ "
+        } else {
+            "! "
+        }
+    }
+
+    pub fn locked_label(&self) -> String {
+        if self.emoji {
+            format!("🔒 {}", t!("locked-note"))
+        } else {
+            format!("[{}]", t!("locked-note").to_lowercase())
+        }
+    }
+}
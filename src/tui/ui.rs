@@ -0,0 +1,284 @@
+// Rendering TUI: list note di kiri, detail di kanan, status bar di bawah,
+// popup untuk editor, URL sync, resolve konflik dan help.
+// Semua warna, border dan simbol diambil dari Theme.
+
+use super::app::{App, Focus, Mode};
+use super::editor::TextArea;
+use super::keymap::Action;
+use super::theme::Theme;
+use crate::i18n;
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, List, ListItem, Paragraph, Wrap};
+use ratatui::Frame;
+
+/// Action yang ditampilkan di status bar; sisanya ada di help overlay
+const HINT_ACTIONS: [(Action, &str); 6] = [
+    (Action::Add, "tui-hint-add"),
+    (Action::Edit, "tui-hint-edit"),
+    (Action::Delete, "tui-hint-delete"),
+    (Action::Search, "tui-hint-search"),
+    (Action::Help, "tui-hint-help"),
+    (Action::Quit, "tui-hint-quit"),
+];
+
+fn first_key(app: &App, action: Action) -> String {
+    app.keymap.keys_for(action).into_iter().next().unwrap_or_default()
+}
+
+/// Kotak di tengah `area` dengan ukuran persen
+fn centered(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let width = area.width * width_percent / 100;
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+pub fn draw(frame: &mut Frame, app: &mut App, theme: &Theme) {
+    let [body, status] = Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+    let [list, detail] = Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)]).areas(body);
+
+    draw_list(frame, app, theme, list);
+    draw_detail(frame, app, theme, detail);
+    draw_status(frame, app, theme, status);
+
+    match &app.mode {
+        Mode::Editor { draft, area } => {
+            let title = match draft.id {
+                Some(id) => format!(" {} ", t!("tui-editor-edit-title", id = id)),
+                None => format!(" {} ", t!("tui-editor-new-title")),
+            };
+            let popup = centered(frame.area(), 80, frame.area().height * 7 / 10);
+            draw_text_area(frame, theme, area, popup, &title);
+        }
+        Mode::SyncUrl(area) => {
+            let popup = centered(frame.area(), 60, 3);
+            draw_text_area(frame, theme, area, popup, &format!(" {} ", t!("tui-sync-title")));
+        }
+        Mode::Resolve(id) => draw_resolve(frame, app, theme, *id),
+        Mode::Help => draw_help(frame, app, theme),
+        Mode::Normal | Mode::Search => {}
+    }
+}
+
+fn draw_list(frame: &mut Frame, app: &mut App, theme: &Theme, area: Rect) {
+    let items: Vec<ListItem> = app
+        .visible
+        .iter()
+        .filter_map(|id| app.notes.get(*id))
+        .map(|note| {
+            let mut spans = vec![Span::styled(format!("[{}] ", note.id), theme.muted)];
+            if note.pinned {
+                spans.push(Span::raw(theme.pin_marker()));
+            }
+            if note.has_conflict() {
+                spans.push(Span::styled(theme.conflict_marker(), theme.warning));
+            }
+            if note.is_locked() {
+                spans.push(Span::raw(theme.locked_label()));
+            } else {
+                spans.push(Span::raw(note.title().to_string()));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let searching = matches!(app.mode, Mode::Search);
+    let heading = format!(" {} ", t!("tui-list-title", count = app.visible.len()));
+    let title = if app.search.is_empty() && !searching {
+        heading.clone()
+    } else {
+        format!("{}/{} ", heading, app.search.text())
+    };
+    let list = List::new(items)
+        .block(theme.block(&title, app.focus == Focus::List))
+        .highlight_style(theme.selected)
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, area, &mut app.list_state);
+
+    if searching {
+        // Cursor di akhir query pada judul panel
+        let prefix = heading.chars().count() as u16 + 1;
+        let x = area.x + 1 + prefix + app.search.cursor().1 as u16;
+        frame.set_cursor_position(Position::new(x.min(area.right().saturating_sub(1)), area.y));
+    }
+}
+
+fn draw_detail(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
+    let block = theme.block(&format!(" {} ", t!("tui-detail-title")), app.focus == Focus::Detail);
+    let Some(note) = app.selected() else {
+        let empty = if app.notes.notes.is_empty() {
+            t!("tui-empty", key = first_key(app, Action::Add))
+        } else {
+            t!("tui-no-match")
+        };
+        frame.render_widget(Paragraph::new(empty).block(block), area);
+        return;
+    };
+
+    // Label dirata kiri dengan lebar label terpanjang dalam bahasa aktif
+    let labels = ["tui-field-created", "tui-field-updated", "tui-field-notebook", "tui-field-tags", "tui-field-files", "tui-field-links"];
+    let width = labels.iter().map(|key| t!(*key).chars().count()).max().unwrap_or(0) + 1;
+    let field = |key: &str, value: String| {
+        Line::from(vec![Span::styled(format!("{:<width$}", t!(key)), theme.muted), Span::raw(value)])
+    };
+    let mut lines = vec![
+        field("tui-field-created", i18n::format_timestamp(&note.created_at)),
+        field("tui-field-updated", i18n::format_timestamp(&note.updated_at)),
+    ];
+    if let Some(notebook) = &note.notebook {
+        lines.push(field("tui-field-notebook", notebook.clone()));
+    }
+    if !note.tags.is_empty() {
+        lines.push(field("tui-field-tags", note.tags.join(", ")));
+    }
+    if !note.attachments.is_empty() {
+        let names: Vec<&str> = note.attachments.iter().map(|a| a.name.as_str()).collect();
+        lines.push(field("tui-field-files", names.join(", ")));
+    }
+    let links = app.notes.links_from(note.id).unwrap_or_default();
+    if !links.is_empty() {
+        let text: Vec<String> = links
+            .iter()
+            .map(|(target, to)| match to {
+                Some(to) => format!("{} → {}", target, to),
+                None => format!("{} ({})", target, t!("tui-link-broken")),
+            })
+            .collect();
+        lines.push(field("tui-field-links", theme.text(&text.join(", ")).into_owned()));
+    }
+    let backlinks = app.notes.backlinks(note.id);
+    if !backlinks.is_empty() {
+        let text: Vec<String> = backlinks.iter().map(|id| id.to_string()).collect();
+        let label = format!("{} ", t!("tui-field-backlinks"));
+        lines.push(Line::from(vec![Span::styled(label, theme.muted), Span::raw(text.join(", "))]));
+    }
+    if note.has_conflict() {
+        let text = format!(
+            "{}{}",
+            theme.conflict_marker(),
+            t!("tui-conflict-hint", key = first_key(app, Action::Resolve))
+        );
+        lines.push(Line::styled(theme.text(&text).into_owned(), theme.warning));
+    }
+    lines.push(Line::default());
+    if note.is_locked() {
+        lines.push(Line::raw(theme.locked_label()));
+    } else {
+        lines.extend(note.content.lines().map(|line| Line::raw(line.to_string())));
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((app.detail_scroll, 0)),
+        area,
+    );
+}
+
+fn draw_status(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
+    let line = match (&app.mode, &app.status, app.pending_keys()) {
+        (Mode::Search, _, _) => Line::raw(theme.text(&t!("tui-search-hint")).into_owned()),
+        (_, _, Some(pending)) => Line::raw(pending),
+        (_, Some((message, true)), _) => Line::styled(theme.text(message).into_owned(), theme.error),
+        (_, Some((message, false)), _) => Line::raw(theme.text(message).into_owned()),
+        (_, None, _) => {
+            let hints: Vec<String> = HINT_ACTIONS
+                .iter()
+                .filter_map(|(action, label)| {
+                    let key = first_key(app, *action);
+                    (!key.is_empty()).then(|| format!("{} {}", key, t!(*label)))
+                })
+                .collect();
+            Line::styled(hints.join("  "), theme.muted)
+        }
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn draw_text_area(frame: &mut Frame, theme: &Theme, area: &TextArea, popup: Rect, title: &str) {
+    frame.render_widget(Clear, popup);
+    let block = theme.block(title, true);
+    let inner = block.inner(popup);
+
+    // Scroll supaya baris dan kolom cursor selalu terlihat
+    let (row, col) = area.cursor();
+    let row_offset = (row + 1).saturating_sub(inner.height as usize);
+    let col_offset = (col + 1).saturating_sub(inner.width as usize);
+    let lines: Vec<Line> = area.lines().iter().map(|line| Line::raw(line.as_str())).collect();
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((row_offset as u16, col_offset as u16)),
+        popup,
+    );
+    frame.set_cursor_position(Position::new(
+        inner.x + (col - col_offset) as u16,
+        inner.y + (row - row_offset) as u16,
+    ));
+}
+
+fn draw_resolve(frame: &mut Frame, app: &App, theme: &Theme, id: usize) {
+    let Some(note) = app.notes.get(id) else {
+        return;
+    };
+    let (Some(conflict), Some(preview)) = (&note.conflict, note.merge_preview()) else {
+        return;
+    };
+    let popup = centered(frame.area(), 90, frame.area().height * 9 / 10);
+    frame.render_widget(Clear, popup);
+    let block = theme.block(&format!(" {} ", t!("tui-resolve-title", id = id)), true);
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let [top, bottom] = Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(inner);
+    let [base, ours, theirs] = Layout::horizontal([Constraint::Ratio(1, 3); 3]).areas(top);
+    let pane = |title: String, text: &str, area: Rect, frame: &mut Frame| {
+        frame.render_widget(
+            Paragraph::new(text.to_string())
+                .block(theme.block(&title, false))
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+    };
+    let unknown = t!("conflict-unknown");
+    pane(format!(" {} ", t!("conflict-base")), conflict.base.as_deref().unwrap_or(&unknown), base, frame);
+    pane(format!(" {} ", t!("conflict-ours")), &conflict.ours, ours, frame);
+    let theirs_title = t!("conflict-theirs", date = i18n::format_timestamp(&conflict.theirs_updated_at));
+    pane(format!(" {} ", theirs_title), &conflict.theirs, theirs, frame);
+    let merged = if preview.clean {
+        t!("conflict-merged")
+    } else {
+        t!("conflict-merged-markers")
+    };
+    pane(format!(" {} ", merged), &preview.text, bottom, frame);
+}
+
+/// Help dibuat dari keymap aktif, jadi binding dari keymap.json ikut tampil
+fn draw_help(frame: &mut Frame, app: &App, theme: &Theme) {
+    let mut lines: Vec<Line> = Action::ALL
+        .iter()
+        .map(|action| {
+            let keys = app.keymap.keys_for(*action).join(" ");
+            Line::from(vec![
+                Span::styled(format!("{:<16}", keys), theme.accent),
+                Span::raw(action.description()),
+            ])
+        })
+        .collect();
+    lines.push(Line::default());
+    lines.push(Line::raw(t!("tui-help-editor")));
+    lines.push(Line::raw(t!("tui-help-escape")));
+
+    let popup = centered(frame.area(), 60, lines.len() as u16 + 2);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines).block(theme.block(&format!(" {} ", t!("tui-help-title")), true)),
+        popup,
+    );
+}
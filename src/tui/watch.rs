@@ -0,0 +1,65 @@
+// Watch file notebook supaya perubahan dari CLI/web langsung tampil di TUI.
+// Yang di-watch folder data (bukan file-nya), karena save menulis ke file
+// sementara lalu rename; watch pada file lama akan hilang setelah rename.
+
+use crate::git_store;
+use crate::notes::{data_dir, ENCRYPTED_NOTES_FILE, NOTES_FILE};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+/// Tunggu sebentar setelah event terakhir; satu save bisa memicu beberapa event
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+pub struct NotesWatcher {
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    due: Option<Instant>,
+}
+
+fn is_notebook_file(path: &Path) -> bool {
+    let in_git_store = path
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|dir| dir == git_store::GIT_STORE_DIR);
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) if in_git_store => name.ends_with(".md") || name == "notebook.json",
+        Some(name) => name == NOTES_FILE || name == ENCRYPTED_NOTES_FILE,
+        None => false,
+    }
+}
+
+impl NotesWatcher {
+    pub fn new() -> notify::Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        watcher.watch(&data_dir(), RecursiveMode::NonRecursive)?;
+        if git_store::is_git_backed() {
+            watcher.watch(&git_store::repo_dir(), RecursiveMode::NonRecursive)?;
+        }
+        Ok(NotesWatcher {
+            _watcher: watcher,
+            events,
+            due: None,
+        })
+    }
+
+    /// Return true kalau notebook berubah dan sudah tenang selama DEBOUNCE
+    pub fn changed(&mut self) -> bool {
+        while let Ok(event) = self.events.try_recv() {
+            if let Ok(event) = event {
+                if event.paths.iter().any(|path| is_notebook_file(path)) {
+                    self.due = Some(Instant::now() + DEBOUNCE);
+                }
+            }
+        }
+        match self.due {
+            Some(due) if Instant::now() >= due => {
+                self.due = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
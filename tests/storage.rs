@@ -0,0 +1,130 @@
+// Integration test CLI: pindah storage json -> git -> json, dua kali berturut-turut.
+// Setiap perpindahan ke json menyimpan repo lama sebagai backup dengan nama baru.
+// Juga enkripsi notebook beserta attachment-nya, yang ditolak di storage git.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Jalankan rust_app; `passphrase` diisi lewat RUST_APP_PASSPHRASE
+fn run(dir: &Path, passphrase: Option<&str>, args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_rust_app"));
+    command
+        .args(args)
+        .env("RUST_APP_DATA_DIR", dir)
+        .env("RUST_APP_LANG", "en")
+        .env_remove("RUST_APP_PASSPHRASE");
+    if let Some(passphrase) = passphrase {
+        command.env("RUST_APP_PASSPHRASE", passphrase);
+    }
+    command.output().expect("run rust_app")
+}
+
+fn rust_app(dir: &Path, args: &[&str]) -> Output {
+    let output = run(dir, None, args);
+    assert!(
+        output.status.success(),
+        "rust_app {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn contents(dir: &Path) -> Vec<String> {
+    let output = rust_app(dir, &["list", "--format", "json"]);
+    let notes: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    notes.iter().map(|note| note["content"].as_str().unwrap().to_string()).collect()
+}
+
+fn backups(dir: &Path) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().starts_with("notes_git.old"))
+        .count()
+}
+
+#[test]
+fn storage_round_trip_twice() {
+    let dir = tempfile::tempdir().unwrap();
+    let dir = dir.path();
+    rust_app(dir, &["add", "first note"]);
+
+    for round in 1..=2 {
+        rust_app(dir, &["storage", "git"]);
+        assert!(dir.join("notes_git/.git").exists());
+        rust_app(dir, &["add", &format!("added in git, round {}", round)]);
+
+        rust_app(dir, &["storage", "json"]);
+        assert!(!dir.join("notes_git").exists());
+        assert!(dir.join("notes.json").exists());
+        assert_eq!(backups(dir), round);
+    }
+
+    assert_eq!(
+        contents(dir),
+        ["first note", "added in git, round 1", "added in git, round 2"]
+    );
+}
+
+/// Semua file blob di folder attachments: (nama file, isi)
+fn blobs(dir: &Path) -> Vec<(String, Vec<u8>)> {
+    std::fs::read_dir(dir.join("attachments"))
+        .unwrap()
+        .flat_map(|shard| std::fs::read_dir(shard.unwrap().path()).unwrap())
+        .map(|blob| {
+            let blob = blob.unwrap();
+            (blob.file_name().to_string_lossy().to_string(), std::fs::read(blob.path()).unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn encrypted_notebook_seals_attachments() {
+    let dir = tempfile::tempdir().unwrap();
+    let dir = dir.path();
+    rust_app(dir, &["add", "secret note"]);
+    let file = dir.join("plan.txt");
+    std::fs::write(&file, "attachment plaintext").unwrap();
+    rust_app(dir, &["attach", "1", file.to_str().unwrap()]);
+
+    let empty = run(dir, Some(""), &["encrypt"]);
+    assert!(!empty.status.success());
+    assert!(String::from_utf8_lossy(&empty.stderr).contains("passphrase cannot be empty"));
+    assert!(!dir.join("notes.json.enc").exists());
+
+    assert!(run(dir, Some("correct horse"), &["encrypt"]).status.success());
+    let sealed = blobs(dir);
+    assert_eq!(sealed.len(), 1);
+    assert!(sealed[0].0.ends_with(".enc"), "{}", sealed[0].0);
+    let text = String::from_utf8_lossy(&sealed[0].1);
+    assert!(!text.contains("attachment plaintext"));
+
+    // Attachment baru di notebook terenkripsi langsung disimpan terenkripsi
+    std::fs::write(&file, "second plaintext").unwrap();
+    assert!(run(dir, Some("correct horse"), &["attach", "1", file.to_str().unwrap()]).status.success());
+    assert!(blobs(dir).iter().all(|(name, content)| {
+        name.ends_with(".enc") && !String::from_utf8_lossy(content).contains("plaintext")
+    }));
+    assert!(!run(dir, Some("wrong horse"), &["list"]).status.success());
+
+    assert!(run(dir, Some("correct horse"), &["decrypt"]).status.success());
+    let mut plain: Vec<Vec<u8>> = blobs(dir).into_iter().map(|(_, content)| content).collect();
+    plain.sort();
+    assert_eq!(plain, [b"attachment plaintext".to_vec(), b"second plaintext".to_vec()]);
+    assert_eq!(contents(dir), ["secret note"]);
+}
+
+#[test]
+fn git_storage_refuses_encryption() {
+    let dir = tempfile::tempdir().unwrap();
+    let dir = dir.path();
+    rust_app(dir, &["add", "plain note"]);
+    rust_app(dir, &["storage", "git"]);
+
+    let output = run(dir, Some("correct horse"), &["encrypt"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("storage json"));
+    assert!(!dir.join("notes.json.enc").exists());
+    // Notebook tetap bisa dibuka seperti sebelumnya
+    assert_eq!(contents(dir), ["plain note"]);
+}
@@ -0,0 +1,159 @@
+// Integration test sync: rust_app_web di 127.0.0.1 dengan port bebas, dua data dir
+// sementara sebagai dua device. Note, attachment, dan edit concurrent ikut diuji.
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+
+const TOKEN_ENV: &str = "RUST_APP_SYNC_TOKEN";
+
+fn command(program: &str, dir: &Path) -> Command {
+    let mut command = Command::new(program);
+    command
+        .env("RUST_APP_DATA_DIR", dir)
+        .env("RUST_APP_LANG", "en")
+        .env_remove("RUST_APP_PASSPHRASE")
+        .env_remove("RUST_APP_CONFIG")
+        .env_remove(TOKEN_ENV);
+    command
+}
+
+fn succeed(mut command: Command, args: &[&str]) -> Output {
+    let output = command.args(args).output().expect("run command");
+    assert!(
+        output.status.success(),
+        "{:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn rust_app(dir: &Path, token: &str, args: &[&str]) -> Output {
+    let mut command = command(env!("CARGO_BIN_EXE_rust_app"), dir);
+    command.env(TOKEN_ENV, token);
+    succeed(command, args)
+}
+
+/// `rust_app edit` dengan "editor" yang langsung menulis isi baru
+fn edit(dir: &Path, id: &str, content: &str) {
+    let mut command = command(env!("CARGO_BIN_EXE_rust_app"), dir);
+    command.env("VISUAL", format!("printf '{}' >", content));
+    succeed(command, &["edit", id]);
+}
+
+fn json(dir: &Path, args: &[&str]) -> Vec<serde_json::Value> {
+    let mut args = args.to_vec();
+    args.extend(["--format", "json"]);
+    serde_json::from_slice(&rust_app(dir, "", &args).stdout).unwrap()
+}
+
+fn contents(dir: &Path) -> Vec<String> {
+    json(dir, &["list"])
+        .iter()
+        .map(|note| note["content"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// Isi semua blob di folder attachments
+fn blobs(dir: &Path) -> Vec<Vec<u8>> {
+    let Ok(shards) = std::fs::read_dir(dir.join("attachments")) else {
+        return Vec::new();
+    };
+    shards
+        .flat_map(|shard| std::fs::read_dir(shard.unwrap().path()).unwrap())
+        .map(|blob| std::fs::read(blob.unwrap().path()).unwrap())
+        .collect()
+}
+
+/// rust_app_web yang berjalan selama test; dimatikan saat di-drop
+struct Server {
+    child: Child,
+    url: String,
+    token: String,
+    _dir: tempfile::TempDir,
+}
+
+impl Server {
+    fn start() -> Server {
+        let dir = tempfile::tempdir().unwrap();
+        let web = env!("CARGO_BIN_EXE_rust_app_web");
+        let mut add = command(web, dir.path());
+        add.env("RUST_APP_PASSWORD", "correct horse");
+        succeed(add, &["user", "add", "admin", "--admin"]);
+        let token = succeed(command(web, dir.path()), &["token", "create", "admin"]).stdout;
+        let token = String::from_utf8(token).unwrap().trim().to_string();
+
+        let mut child = command(web, dir.path())
+            .args(["--host", "127.0.0.1", "--port", "0"])
+            .env("RUST_APP_RATE_LIMIT", "0")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("start rust_app_web");
+        // Port dipilih sistem operasi; URL-nya dibaca dari pesan startup
+        let mut lines = BufReader::new(child.stdout.take().unwrap()).lines().map_while(Result::ok);
+        let url = lines
+            .find_map(|line| Some(line[line.find("http://")?..].trim().to_string()))
+            .expect("server URL");
+        // Tetap baca stdout; pipe yang tertutup membuat println! di server panic
+        std::thread::spawn(move || lines.for_each(drop));
+        Server {
+            child,
+            url,
+            token,
+            _dir: dir,
+        }
+    }
+
+    fn sync(&self, dir: &Path) -> String {
+        let output = rust_app(dir, &self.token, &["sync", &self.url]);
+        String::from_utf8(output.stdout).unwrap()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn two_devices_sync_notes_and_attachments() {
+    let server = Server::start();
+    let device_a = tempfile::tempdir().unwrap();
+    let device_b = tempfile::tempdir().unwrap();
+    let (a, b): (PathBuf, PathBuf) = (device_a.path().into(), device_b.path().into());
+
+    rust_app(&a, "", &["add", "shared note"]);
+    let file = a.join("report.txt");
+    std::fs::write(&file, "attachment body").unwrap();
+    rust_app(&a, "", &["attach", "1", file.to_str().unwrap()]);
+
+    let report = server.sync(&a);
+    assert!(report.contains("1 attachment sent"), "{}", report);
+    let report = server.sync(&b);
+    assert!(report.contains("1 received"), "{}", report);
+    assert_eq!(contents(&b), ["shared note"]);
+    assert_eq!(blobs(&b), [b"attachment body".to_vec()]);
+
+    // Kedua device mengubah note yang sama tanpa sync di antaranya
+    edit(&a, "1", "edited on a");
+    edit(&b, "1", "edited on b");
+    server.sync(&a);
+    let report = server.sync(&b);
+    assert!(report.contains("1 conflict"), "{}", report);
+    assert_eq!(json(&b, &["conflicts"]).len(), 1);
+    // Server menerima edit A lebih dulu, jadi edit B tersimpan sebagai "theirs"
+    assert_eq!(contents(&b), ["edited on a"]);
+    server.sync(&a);
+    assert_eq!(json(&a, &["conflicts"]).len(), 1);
+
+    // Pilihan di satu device menyelesaikan konflik di device lain juga
+    rust_app(&b, "", &["resolve", "1", "--theirs"]);
+    server.sync(&b);
+    server.sync(&a);
+    assert_eq!(contents(&a), ["edited on b"]);
+    assert!(json(&a, &["conflicts"]).is_empty());
+}